@@ -0,0 +1,79 @@
+use sbe_codegen::{
+    naming::{codec_module, type_name},
+    schema::Schema,
+    walk::walk_body,
+};
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The schemas `spot_sbe` generates codecs for, in the same order.
+const LATEST_SCHEMA: &str = "../spot_sbe/spot_latest.xml";
+const OTHER_SCHEMAS: &str = "../spot_sbe/schemas";
+
+fn main() {
+    let schemas =
+        sbe_codegen::schemas(LATEST_SCHEMA, OTHER_SCHEMAS).expect("Failed to list the schemas");
+
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("walk.rs"), walk(&schemas))
+        .expect("Failed to write the decoder walks");
+}
+
+/// Generates, for every schema, a `walk` reading every field, group entry and
+/// var-data of the message its header names through the checked `try_*` API,
+/// and `WALKS` listing them.
+fn walk(schemas: &[PathBuf]) -> String {
+    let mut out = String::new();
+    let mut walks = Vec::new();
+    for path in schemas {
+        let schema = Schema::parse(&fs::read_to_string(path).expect("Failed to read the schema"))
+            .expect("Invalid schema");
+        writeln!(
+            out,
+            "#[allow(clippy::all, unused)]
+pub mod schema_{id} {{
+    use spot_sbe::schema_{id} as sbe;
+    use spot_sbe::{{Decoder, ReadBuf, SbeResult}};
+    use std::hint::black_box;
+
+    pub fn walk(buf: &[u8]) -> SbeResult<()> {{
+        let header = sbe::message_header_codec::MessageHeaderDecoder::default()
+            .try_wrap(ReadBuf::new(buf), 0)?;
+        match header.template_id() {{",
+            id = schema.id
+        )
+        .unwrap();
+        for message in schema.messages() {
+            let module = codec_module(&message.name);
+            let mut body = String::new();
+            walk_body(
+                &mut body,
+                &message.body,
+                "decoder",
+                "                ",
+                &|value| format!("black_box({value});"),
+            );
+            writeln!(
+                out,
+                "            sbe::{module}::SBE_TEMPLATE_ID => {{
+                let mut decoder = sbe::{module}::{ty}Decoder::default().try_header(header, 0)?;
+{body}                black_box(decoder.get_limit());
+            }}",
+                ty = type_name(&message.name)
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            "            _ => {{}}\n        }}\n        Ok(())\n    }}\n}}\n"
+        )
+        .unwrap();
+        walks.push(format!("schema_{}::walk", schema.id));
+    }
+    writeln!(out, "pub const WALKS: &[Walk] = &[{}];", walks.join(", ")).unwrap();
+    out
+}
@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sbe_sample_app::{decimal::DecimalFormat, output::OutputFormat, render::render_payload};
+use sbe_sample_app_fuzz::with_header;
+use spot_sbe::exchange_info_response_codec::{SBE_BLOCK_LENGTH, SBE_TEMPLATE_ID};
+
+// The input is the body of an exchangeInfo response: its groups, their nested
+// filter messages and its var-data.
+fuzz_target!(|data: &[u8]| {
+    let payload = with_header(SBE_TEMPLATE_ID, SBE_BLOCK_LENGTH, data);
+    let _ = render_payload(&payload, OutputFormat::Json, DecimalFormat::String, false);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sbe_sample_app::exchange_info::{decode_exchange_filter, decode_symbol_filter};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = decode_symbol_filter(data);
+    let _ = decode_exchange_filter(data);
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sbe_sample_app_fuzz::WALKS;
+
+// Walks the groups and var-data of whichever message the header names, with
+// the codecs of every schema ID.
+fuzz_target!(|data: &[u8]| {
+    for walk in WALKS {
+        let _ = walk(data);
+    }
+});
@@ -0,0 +1,22 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sbe_sample_app::message::decode_any;
+use spot_sbe::{
+    message_header_codec::MessageHeaderDecoder, message_length::message_length, ReadBuf,
+};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(header) = MessageHeaderDecoder::default().try_wrap(ReadBuf::new(data), 0) {
+        let _ = (
+            header.block_length(),
+            header.template_id(),
+            header.schema_id(),
+            header.version(),
+        );
+    }
+    let _ = message_length(data);
+    if let Ok(message) = decode_any(data) {
+        let _ = (message.template_id(), message.acting_version());
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sbe_sample_app::{decimal::DecimalFormat, output::OutputFormat, render::render_payload};
+
+// The path `sbe-sample-app` takes for every payload, with the typed decoders
+// and with the embedded schema.
+fuzz_target!(|data: &[u8]| {
+    let _ = render_payload(data, OutputFormat::Json, DecimalFormat::String, false);
+    let _ = render_payload(data, OutputFormat::Json, DecimalFormat::String, true);
+});
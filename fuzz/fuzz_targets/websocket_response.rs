@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use sbe_sample_app::{
+    decimal::DecimalFormat, output::OutputFormat, websocket::decode_websocket_response,
+};
+use sbe_sample_app_fuzz::with_header;
+use spot_sbe::web_socket_response_codec::{SBE_BLOCK_LENGTH, SBE_TEMPLATE_ID};
+
+// The input is the body of a WebSocket API response envelope: its rate limits,
+// its ID and the nested response or error.
+fuzz_target!(|data: &[u8]| {
+    let payload = with_header(SBE_TEMPLATE_ID, SBE_BLOCK_LENGTH, data);
+    if let Ok(metadata) = decode_websocket_response(&payload) {
+        let _ = OutputFormat::Json.render(&metadata, DecimalFormat::String);
+    }
+});
@@ -0,0 +1,22 @@
+//! Helpers shared by the fuzz targets.
+
+use spot_sbe::{message_header_codec, SbeResult, WriteBuf, SBE_SCHEMA_ID, SBE_SCHEMA_VERSION};
+
+/// Reads a whole message with the codecs of one schema ID.
+pub type Walk = fn(&[u8]) -> SbeResult<()>;
+
+include!(concat!(env!("OUT_DIR"), "/walk.rs"));
+
+/// Prefixes `body` with a valid header of the current schema for the given
+/// message, so that every input reaches that message's decoder instead of
+/// mostly failing on the header.
+pub fn with_header(template_id: u16, block_length: u16, body: &[u8]) -> Vec<u8> {
+    let mut payload = vec![0; message_header_codec::ENCODED_LENGTH + body.len()];
+    let mut header = WriteBuf::new(&mut payload);
+    header.put_u16_at(0, block_length);
+    header.put_u16_at(2, template_id);
+    header.put_u16_at(4, SBE_SCHEMA_ID);
+    header.put_u16_at(6, SBE_SCHEMA_VERSION);
+    payload[message_header_codec::ENCODED_LENGTH..].copy_from_slice(body);
+    payload
+}
@@ -0,0 +1,2259 @@
+//! Rust codecs with the API of the ones SbeTool generates, plus the checked
+//! `try_*` variants and `message_length` this repository added to them.
+
+use crate::{
+    naming::{codec_module, snake_case, type_name},
+    schema::*,
+};
+use anyhow::{bail, Context};
+use std::collections::{BTreeMap, BTreeSet};
+
+const RUNTIME: &str = include_str!("../templates/runtime.rs");
+const MESSAGE_LENGTH: &str = include_str!("../templates/message_length.rs");
+
+/// Generated source, with blocks re-indented to the current nesting.
+#[derive(Default)]
+struct Source {
+    text: String,
+    indent: usize,
+}
+
+impl Source {
+    /// Appends `block` without its common indentation, nor the blank first and
+    /// last lines of a raw string literal.
+    fn push(&mut self, block: &str) {
+        let mut lines: Vec<&str> = block.lines().collect();
+        if lines.first().is_some_and(|line| line.trim().is_empty()) {
+            lines.remove(0);
+        }
+        if lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+        let common = lines
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        for line in lines {
+            if !line.trim().is_empty() {
+                for _ in 0..self.indent {
+                    self.text.push_str("    ");
+                }
+                self.text.push_str(&line[common..]);
+            }
+            self.text.push('\n');
+        }
+    }
+
+    fn open(&mut self, block: &str) {
+        self.push(block);
+        self.indent += 1;
+    }
+
+    fn close(&mut self, block: &str) {
+        self.indent -= 1;
+        self.push(block);
+    }
+}
+
+/// Replaces each `{key}` of `template`.
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(template.to_owned(), |text, (key, value)| {
+            text.replace(&format!("{{{key}}}"), value)
+        })
+}
+
+fn rust_type(primitive: Primitive) -> &'static str {
+    match primitive {
+        Primitive::Char | Primitive::UInt8 => "u8",
+        Primitive::Int8 => "i8",
+        Primitive::Int16 => "i16",
+        Primitive::Int32 => "i32",
+        Primitive::Int64 => "i64",
+        Primitive::UInt16 => "u16",
+        Primitive::UInt32 => "u32",
+        Primitive::UInt64 => "u64",
+        Primitive::Float => "f32",
+        Primitive::Double => "f64",
+    }
+}
+
+/// A literal of the primitive's type, hexadecimal for unsigned integers.
+fn literal(primitive: Primitive, value: i128) -> String {
+    let ty = rust_type(primitive);
+    if primitive.is_signed() {
+        format!("{value}_{ty}")
+    } else {
+        format!("{value:#x}_{ty}")
+    }
+}
+
+fn null_literal(primitive: Primitive) -> String {
+    match primitive {
+        Primitive::Float => "f32::NAN".to_owned(),
+        Primitive::Double => "f64::NAN".to_owned(),
+        Primitive::Char => literal(primitive, 0),
+        Primitive::Int8 => literal(primitive, i8::MIN.into()),
+        Primitive::Int16 => literal(primitive, i16::MIN.into()),
+        Primitive::Int32 => literal(primitive, i32::MIN.into()),
+        Primitive::Int64 => literal(primitive, i64::MIN.into()),
+        Primitive::UInt8 => literal(primitive, u8::MAX.into()),
+        Primitive::UInt16 => literal(primitive, u16::MAX.into()),
+        Primitive::UInt32 => literal(primitive, u32::MAX.into()),
+        Primitive::UInt64 => literal(primitive, u64::MAX.into()),
+    }
+}
+
+/// The null value of an encoded type: its own or its primitive's.
+fn encoded_null(encoded: &EncodedType) -> anyhow::Result<String> {
+    let Some(null_value) = &encoded.null_value else {
+        return Ok(null_literal(encoded.primitive));
+    };
+    if matches!(encoded.primitive, Primitive::Float | Primitive::Double) {
+        return Ok(format!(
+            "{}_{}",
+            null_value.trim(),
+            rust_type(encoded.primitive)
+        ));
+    }
+    let value = null_value
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid null value {null_value:?} of {}", encoded.name))?;
+    Ok(literal(encoded.primitive, value))
+}
+
+fn offset_expression(base: &str, offset: usize) -> String {
+    match offset {
+        0 => base.to_owned(),
+        offset => format!("{base} + {offset}"),
+    }
+}
+
+/// A field of a message or group, or a member of a composite.
+struct Accessor<'a> {
+    name: &'a str,
+    offset: usize,
+    ty: &'a Type,
+    presence: &'a Presence,
+    since_version: u16,
+}
+
+impl<'a> Accessor<'a> {
+    fn field(field: &'a Field) -> Self {
+        Self {
+            name: &field.name,
+            offset: field.offset,
+            ty: &field.ty,
+            presence: &field.presence,
+            since_version: field.since_version,
+        }
+    }
+
+    fn member(member: &'a Member) -> Self {
+        Self {
+            name: &member.name,
+            offset: member.offset,
+            ty: &member.ty,
+            presence: match &member.ty {
+                Type::Encoded(encoded) => &encoded.presence,
+                _ => &Presence::Required,
+            },
+            since_version: 0,
+        }
+    }
+
+    fn method(&self) -> String {
+        snake_case(self.name)
+    }
+
+    fn offset(&self) -> String {
+        offset_expression("self.offset", self.offset)
+    }
+
+    /// The early return of decoders for fields added after `acting_version`.
+    fn since_check(&self, absent: &str) -> String {
+        match self.since_version {
+            0 => String::new(),
+            version => {
+                format!("if self.acting_version() < {version} {{\n    return {absent};\n}}\n\n")
+            }
+        }
+    }
+}
+
+/// Module and type of an enum, set or composite.
+fn type_path(ty: &Type) -> String {
+    match ty {
+        Type::Composite(composite) => {
+            format!(
+                "{}::{}",
+                codec_module(&composite.name),
+                type_name(&composite.name)
+            )
+        }
+        ty => format!("{}::{}", snake_case(ty.name()), type_name(ty.name())),
+    }
+}
+
+fn encode_accessor(source: &mut Source, accessor: &Accessor) -> anyhow::Result<()> {
+    let method = accessor.method();
+    let offset = accessor.offset();
+    match accessor.ty {
+        Type::Enum(_) if matches!(accessor.presence, Presence::Constant(_)) => {
+            source.push(&format!("// skipping CONSTANT enum '{}'\n", accessor.name));
+        }
+        Type::Encoded(_) if matches!(accessor.presence, Presence::Constant(_)) => {
+            source.push(&format!("// skipping CONSTANT '{}'\n", accessor.name));
+        }
+        Type::Encoded(encoded) if encoded.length > 1 => {
+            let ty = rust_type(encoded.primitive);
+            let length = encoded.length.to_string();
+            let values = [
+                ("name", accessor.name),
+                ("method", &method),
+                ("offset", &offset),
+                ("ty", ty),
+                ("length", &length),
+            ];
+            if ty == "u8" {
+                source.push(&fill(
+                    r#"
+                    #[inline]
+                    pub fn {method}_at(&mut self, index: usize, value: u8) {
+                        let offset = {offset};
+                        let buf = self.get_buf_mut();
+                        buf.put_u8_at(offset + index, value);
+                    }
+
+                    /// primitive array field '{name}'
+                    #[inline]
+                    pub fn {method}(&mut self, value: &[u8]) {
+                        debug_assert_eq!({length}, value.len());
+                        let offset = {offset};
+                        let buf = self.get_buf_mut();
+                        buf.put_slice_at(offset, value);
+                    }
+
+                    /// primitive array field '{name}' from an Iterator
+                    #[inline]
+                    pub fn {method}_from_iter(&mut self, iter: impl Iterator<Item = u8>) {
+                        let offset = {offset};
+                        let buf = self.get_buf_mut();
+                        for (i, v) in iter.enumerate() {
+                            buf.put_u8_at(offset + i, v);
+                        }
+                    }
+
+                    /// primitive array field '{name}' with zero padding
+                    #[inline]
+                    pub fn {method}_zero_padded(&mut self, value: &[u8]) {
+                        let iter = value
+                            .iter()
+                            .copied()
+                            .chain(std::iter::repeat(0_u8))
+                            .take({length});
+                        self.{method}_from_iter(iter);
+                    }
+                    "#,
+                    &values,
+                ));
+            } else {
+                let size = encoded.primitive.size().to_string();
+                source.push(&fill(
+                    r#"
+                    /// primitive array field '{name}'
+                    #[inline]
+                    pub fn {method}(&mut self, value: &[{ty}; {length}]) {
+                        let offset = {offset};
+                        let buf = self.get_buf_mut();
+                        for (i, v) in value.iter().enumerate() {
+                            buf.put_{ty}_at(offset + i * {size}, *v);
+                        }
+                    }
+                    "#,
+                    &[values.as_slice(), &[("size", &size)]].concat(),
+                ));
+            }
+        }
+        Type::Encoded(encoded) => {
+            let ty = rust_type(encoded.primitive);
+            source.push(&fill(
+                r#"
+                /// primitive field '{name}'
+                /// - null value: {null}
+                /// - characterEncoding: {encoding}
+                /// - encodedOffset: {encoded_offset}
+                /// - encodedLength: {encoded_length}
+                /// - version: {version}
+                #[inline]
+                pub fn {method}(&mut self, value: {ty}) {
+                    let offset = {offset};
+                    self.get_buf_mut().put_{ty}_at(offset, value);
+                }
+                "#,
+                &[
+                    ("name", accessor.name),
+                    ("null", &encoded_null(encoded)?),
+                    (
+                        "encoding",
+                        encoded.character_encoding.as_deref().unwrap_or("null"),
+                    ),
+                    ("encoded_offset", &accessor.offset.to_string()),
+                    ("encoded_length", &accessor.ty.size().to_string()),
+                    ("version", &accessor.since_version.to_string()),
+                    ("method", &method),
+                    ("ty", ty),
+                    ("offset", &offset),
+                ],
+            ));
+        }
+        Type::Enum(enumeration) => {
+            let ty = rust_type(enumeration.encoding);
+            source.push(&fill(
+                r#"
+                /// {presence} enum
+                #[inline]
+                pub fn {method}(&mut self, value: {path}) {
+                    let offset = {offset};
+                    self.get_buf_mut().put_{ty}_at(offset, value as {ty})
+                }
+                "#,
+                &[
+                    ("presence", presence_name(accessor.presence)),
+                    ("method", &method),
+                    ("path", &type_path(accessor.ty)),
+                    ("offset", &offset),
+                    ("ty", ty),
+                ],
+            ));
+        }
+        Type::Set(set) => {
+            source.push(&fill(
+                r#"
+                #[inline]
+                pub fn {method}(&mut self, value: {path}) {
+                    let offset = {offset};
+                    self.get_buf_mut().put_{ty}_at(offset, value.0)
+                }
+                "#,
+                &[
+                    ("method", &method),
+                    ("path", &type_path(accessor.ty)),
+                    ("offset", &offset),
+                    ("ty", rust_type(set.encoding)),
+                ],
+            ));
+        }
+        Type::Composite(_) => {
+            source.push(&fill(
+                r#"
+                /// COMPOSITE ENCODER
+                #[inline]
+                pub fn {method}_encoder(self) -> {path}Encoder<Self> {
+                    let offset = {offset};
+                    {path}Encoder::default().wrap(self, offset)
+                }
+                "#,
+                &[
+                    ("method", &method),
+                    ("path", &type_path(accessor.ty)),
+                    ("offset", &offset),
+                ],
+            ));
+        }
+    }
+    source.push("");
+    Ok(())
+}
+
+fn presence_name(presence: &Presence) -> &'static str {
+    match presence {
+        Presence::Required => "REQUIRED",
+        Presence::Optional => "OPTIONAL",
+        Presence::Constant(_) => "CONSTANT",
+    }
+}
+
+fn decode_accessor(source: &mut Source, accessor: &Accessor) -> anyhow::Result<()> {
+    let method = accessor.method();
+    let offset = accessor.offset();
+    match accessor.ty {
+        Type::Encoded(encoded) if encoded.length > 1 => {
+            let ty = rust_type(encoded.primitive);
+            let length = encoded.length;
+            let since_check =
+                accessor.since_check(&format!("[{}; {length}]", encoded_null(encoded)?));
+            let body = if ty == "u8" {
+                format!("ReadBuf::get_bytes_at(buf.data, {offset})")
+            } else {
+                let elements: Vec<String> = (0..length)
+                    .map(|index| {
+                        let element = offset_expression(
+                            "self.offset",
+                            accessor.offset + index * encoded.primitive.size(),
+                        );
+                        format!("buf.get_{ty}_at({element})")
+                    })
+                    .collect();
+                format!("[{}]", elements.join(", "))
+            };
+            source.push(&fill(
+                r#"
+                #[inline]
+                pub fn {method}(&self) -> [{ty}; {length}] {
+                    {since_check}let buf = self.get_buf();
+                    {body}
+                }
+                "#,
+                &[
+                    ("method", &method),
+                    ("ty", ty),
+                    ("length", &length.to_string()),
+                    ("since_check", &since_check),
+                    ("body", &body),
+                ],
+            ));
+        }
+        Type::Encoded(encoded) => {
+            let ty = rust_type(encoded.primitive);
+            let null = encoded_null(encoded)?;
+            let doc = match &encoded.character_encoding {
+                Some(encoding) => format!("\n/// characterEncoding: '{encoding}'"),
+                None => String::new(),
+            };
+            let block = match accessor.presence {
+                Presence::Constant(value) => {
+                    let value = value.trim();
+                    let value = match (encoded.primitive, value.as_bytes()) {
+                        (Primitive::Char, [byte]) => literal(Primitive::Char, (*byte).into()),
+                        _ => format!("{value}_{ty}"),
+                    };
+                    format!(
+                        r#"
+                        /// CONSTANT
+                        #[inline]
+                        pub fn {method}(&self) -> {ty} {{
+                            {value}
+                        }}
+                        "#
+                    )
+                }
+                Presence::Required => {
+                    let since_check = accessor.since_check(&null);
+                    format!(
+                        r#"
+                        /// primitive field - 'REQUIRED'{doc}
+                        #[inline]
+                        pub fn {method}(&self) -> {ty} {{
+                            {since_check}self.get_buf().get_{ty}_at({offset})
+                        }}
+                        "#
+                    )
+                }
+                Presence::Optional => {
+                    let since_check = accessor.since_check("None");
+                    let condition =
+                        if matches!(encoded.primitive, Primitive::Float | Primitive::Double) {
+                            "value.is_nan()".to_owned()
+                        } else {
+                            format!("value == {null}")
+                        };
+                    format!(
+                        r#"
+                        /// primitive field - 'OPTIONAL' {{ null_value: '{null}' }}{doc}
+                        #[inline]
+                        pub fn {method}(&self) -> Option<{ty}> {{
+                            {since_check}let value = self.get_buf().get_{ty}_at({offset});
+                            if {condition} {{
+                                None
+                            }} else {{
+                                Some(value)
+                            }}
+                        }}
+                        "#
+                    )
+                }
+            };
+            source.push(&indent_interpolated(&block));
+        }
+        Type::Enum(enumeration) => {
+            let path = type_path(accessor.ty);
+            let block = match accessor.presence {
+                Presence::Constant(value) => {
+                    if enumeration.values.iter().all(|(name, _)| name != value) {
+                        bail!("Unknown constant {value} of enum {}", enumeration.name);
+                    }
+                    format!(
+                        r#"
+                        /// CONSTANT enum
+                        #[inline]
+                        pub fn {method}(&self) -> {path} {{
+                            {path}::{variant}
+                        }}
+                        "#,
+                        variant = type_name(value),
+                    )
+                }
+                presence => {
+                    let since_check = accessor.since_check(&format!("{path}::default()"));
+                    format!(
+                        r#"
+                        /// {presence} enum
+                        #[inline]
+                        pub fn {method}(&self) -> {path} {{
+                            {since_check}self.get_buf().get_{ty}_at({offset}).into()
+                        }}
+                        "#,
+                        presence = presence_name(presence),
+                        ty = rust_type(enumeration.encoding),
+                    )
+                }
+            };
+            source.push(&indent_interpolated(&block));
+        }
+        Type::Set(set) => {
+            let path = type_path(accessor.ty);
+            let since_check = accessor.since_check(&format!("{path}::default()"));
+            source.push(&indent_interpolated(&format!(
+                r#"
+                /// BIT SET DECODER
+                #[inline]
+                pub fn {method}(&self) -> {path} {{
+                    {since_check}{path}::new(self.get_buf().get_{ty}_at({offset}))
+                }}
+                "#,
+                ty = rust_type(set.encoding),
+            )));
+        }
+        Type::Composite(_) => {
+            source.push(&fill(
+                r#"
+                /// COMPOSITE DECODER
+                #[inline]
+                pub fn {method}_decoder(self) -> {path}Decoder<Self> {
+                    let offset = {offset};
+                    {path}Decoder::default().wrap(self, offset)
+                }
+                "#,
+                &[
+                    ("method", &method),
+                    ("path", &type_path(accessor.ty)),
+                    ("offset", &offset),
+                ],
+            ));
+        }
+    }
+    source.push("");
+    Ok(())
+}
+
+/// Re-indents the lines of multi-line values interpolated into a template at
+/// the indentation of the line they were interpolated into.
+fn indent_interpolated(block: &str) -> String {
+    let mut lines: Vec<&str> = block.lines().collect();
+    if lines.first().is_some_and(|line| line.trim().is_empty()) {
+        lines.remove(0);
+    }
+    let common = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let mut text = String::new();
+    let mut indent = 0;
+    for line in lines {
+        let stripped = if line.len() - line.trim_start().len() >= common {
+            indent = line.len() - line.trim_start().len() - common;
+            &line[common..]
+        } else {
+            // A line of an interpolated value, indented from its template line.
+            text.push_str(&" ".repeat(indent));
+            line
+        };
+        text.push_str(stripped);
+        text.push('\n');
+    }
+    text
+}
+
+/// The width of the length of var-data or of a group's `numInGroup`.
+fn width(primitive: Primitive) -> anyhow::Result<&'static str> {
+    Ok(match primitive {
+        Primitive::UInt8 => "U8",
+        Primitive::UInt16 => "U16",
+        Primitive::UInt32 => "U32",
+        primitive => bail!("Unsupported length encoding {primitive:?}"),
+    })
+}
+
+/// The offsets and encodings of a group's `blockLength` and `numInGroup`.
+struct Dimension {
+    block_length: (usize, Primitive),
+    num_in_group: (usize, Primitive),
+    size: usize,
+}
+
+impl Dimension {
+    fn of(group: &Group) -> anyhow::Result<Self> {
+        if group.since_version > 0 {
+            bail!(
+                "Group {} has a sinceVersion, which is not supported",
+                group.name
+            );
+        }
+        Ok(Self {
+            block_length: group.dimension.primitive_member("blockLength")?,
+            num_in_group: group.dimension.primitive_member("numInGroup")?,
+            size: Type::Composite(group.dimension.clone()).size(),
+        })
+    }
+}
+
+/// The offset and encoding of the length of var-data.
+fn data_length(data: &Data) -> anyhow::Result<(usize, Primitive)> {
+    let (offset, primitive) = data.ty.primitive_member("length")?;
+    let (data_offset, _) = match data.ty.member("varData") {
+        Some(member) => (member.offset, &member.ty),
+        None => bail!("Composite {} has no varData member", data.ty.name),
+    };
+    if offset != 0 || data_offset != primitive.size() {
+        bail!("Composite {} does not start with its length", data.ty.name);
+    }
+    width(primitive)?;
+    Ok((primitive.size(), primitive))
+}
+
+fn conversion(from: Primitive, to: &str) -> String {
+    if rust_type(from) == to {
+        String::new()
+    } else {
+        format!(" as {to}")
+    }
+}
+
+/// The minimum block length in each version adding fields, newest first, then
+/// in version 0.
+fn min_block_lengths(fields: &[Field]) -> Vec<(u16, usize)> {
+    let length = |version: u16| {
+        fields
+            .iter()
+            .filter(|field| field.since_version <= version)
+            .map(|field| match field.presence {
+                Presence::Constant(_) => field.offset,
+                _ => field.offset + field.ty.size(),
+            })
+            .max()
+            .unwrap_or(0)
+    };
+    let versions: BTreeSet<u16> = fields
+        .iter()
+        .map(|field| field.since_version)
+        .filter(|&version| version > 0)
+        .collect();
+    versions
+        .into_iter()
+        .rev()
+        .chain([0])
+        .map(|version| (version, length(version)))
+        .collect()
+}
+
+fn min_block_length(source: &mut Source, fields: &[Field]) {
+    let lengths = min_block_lengths(fields);
+    source.push(
+        "/// Number of bytes the fixed block must hold for the fields present in `acting_version`.\n#[inline]",
+    );
+    if let [(_, length)] = lengths.as_slice() {
+        source.push(&format!(
+            "fn min_block_length(_acting_version: u16) -> usize {{\n    {length}\n}}\n"
+        ));
+        return;
+    }
+    source.open("fn min_block_length(acting_version: u16) -> usize {");
+    for (index, (version, length)) in lengths.iter().enumerate() {
+        let branch = match (index, index + 1 == lengths.len()) {
+            (_, true) => "} else {".to_owned(),
+            (0, _) => format!("if acting_version >= {version} {{"),
+            _ => format!("}} else if acting_version >= {version} {{"),
+        };
+        source.push(&branch);
+        source.push(&format!("    {length}"));
+    }
+    source.push("}");
+    source.close("}\n");
+}
+
+fn encode_body(source: &mut Source, body: &Body) -> anyhow::Result<()> {
+    for field in &body.fields {
+        encode_accessor(source, &Accessor::field(field))?;
+    }
+    for group in &body.groups {
+        let dimension = Dimension::of(group)?;
+        source.push(&fill(
+            r#"
+            /// GROUP ENCODER (id={id})
+            #[inline]
+            pub fn {method}_encoder(self, count: {count}, {method}_encoder: {ty}Encoder<Self>) -> {ty}Encoder<Self> {
+                {method}_encoder.wrap(self, count)
+            }
+
+            "#,
+            &[
+                ("id", &group.id.to_string()),
+                ("method", &snake_case(&group.name)),
+                ("count", rust_type(dimension.num_in_group.1)),
+                ("ty", &type_name(&group.name)),
+            ],
+        ));
+    }
+    for data in &body.data {
+        let (length, primitive) = data_length(data)?;
+        let ty = rust_type(primitive);
+        let (value_type, bytes) = match data.character_encoding() {
+            Some(_) => ("&str", "value.as_bytes()"),
+            None => ("&[u8]", "value"),
+        };
+        source.push(&fill(
+            r#"
+            /// VAR_DATA ENCODER - character encoding: '{encoding}'
+            #[inline]
+            pub fn {method}(&mut self, value: {value_type}) {
+                let limit = self.get_limit();
+                let data_length = value.len();
+                self.set_limit(limit + {length} + data_length);
+                self.get_buf_mut().put_{ty}_at(limit, data_length as {ty});
+                self.get_buf_mut().put_slice_at(limit + {length}, {bytes});
+            }
+
+            "#,
+            &[
+                ("encoding", data.character_encoding().unwrap_or("None")),
+                ("method", &snake_case(&data.name)),
+                ("value_type", value_type),
+                ("length", &length.to_string()),
+                ("ty", ty),
+                ("bytes", bytes),
+            ],
+        ));
+    }
+    Ok(())
+}
+
+/// Decodes `body`, whose var-data limit lives in its parent when it is a group's.
+fn decode_body(source: &mut Source, body: &Body, in_group: bool) -> anyhow::Result<()> {
+    for field in &body.fields {
+        decode_accessor(source, &Accessor::field(field))?;
+    }
+    for group in &body.groups {
+        source.push(&fill(
+            r#"
+            /// GROUP DECODER (id={id})
+            #[inline]
+            pub fn {method}_decoder(self) -> {ty}Decoder<Self> {
+                {ty}Decoder::default().wrap(self)
+            }
+
+            /// GROUP DECODER (id={id})
+            #[inline]
+            pub fn try_{method}_decoder(self) -> SbeResult<{ty}Decoder<Self>> {
+                {ty}Decoder::default().try_wrap(self)
+            }
+
+            "#,
+            &[
+                ("id", &group.id.to_string()),
+                ("method", &snake_case(&group.name)),
+                ("ty", &type_name(&group.name)),
+            ],
+        ));
+    }
+    for data in &body.data {
+        let (length, primitive) = data_length(data)?;
+        let (absent, try_absent) = match data.since_version {
+            0 => (String::new(), String::new()),
+            version => (
+                format!("if self.acting_version() < {version} {{\n    return (self.get_limit(), 0);\n}}\n\n"),
+                format!("if self.acting_version() < {version} {{\n    return Ok((self.get_limit(), 0));\n}}\n\n"),
+            ),
+        };
+        let (limit, set_limit, try_limit, try_set_limit) = if in_group {
+            (
+                r#"self.parent.as_ref().expect("parent missing").get_limit()"#,
+                "self.parent.as_mut().unwrap().set_limit",
+                "self.parent.as_ref().ok_or(SbeErr::ParentNotSet)?.get_limit()",
+                "self.parent.as_mut().ok_or(SbeErr::ParentNotSet)?.set_limit",
+            )
+        } else {
+            (
+                "self.get_limit()",
+                "self.set_limit",
+                "self.get_limit()",
+                "self.set_limit",
+            )
+        };
+        let block = format!(
+            r#"
+            /// VAR_DATA DECODER - character encoding: '{encoding}'
+            #[inline]
+            pub fn {method}_decoder(&mut self) -> (usize, usize) {{
+                {absent}let offset = {limit};
+                let data_length = self.get_buf().get_{ty}_at(offset) as usize;
+                {set_limit}(offset + {length} + data_length);
+                (offset + {length}, data_length)
+            }}
+
+            /// VAR_DATA DECODER - character encoding: '{encoding}'
+            #[inline]
+            pub fn try_{method}_decoder(&mut self) -> SbeResult<(usize, usize)> {{
+                {try_absent}let offset = {try_limit};
+                let data_length = self.get_buf().try_get_{ty}_at(offset)? as usize;
+                self.get_buf().check_bounds(offset + {length}, data_length)?;
+                {try_set_limit}(offset + {length} + data_length);
+                Ok((offset + {length}, data_length))
+            }}
+
+            #[inline]
+            pub fn {method}_slice(&'a self, coordinates: (usize, usize)) -> &'a [u8] {{
+                debug_assert!(self.get_limit() >= coordinates.0 + coordinates.1);
+                self.get_buf().get_slice_at(coordinates.0, coordinates.1)
+            }}
+
+            "#,
+            encoding = data.character_encoding().unwrap_or("None"),
+            method = snake_case(&data.name),
+            ty = rust_type(primitive),
+        );
+        source.push(&indent_interpolated(&block));
+    }
+    Ok(())
+}
+
+/// The encoder structs of `groups` and of the groups nested in them, depth first.
+fn group_encoders(source: &mut Source, groups: &[Group]) -> anyhow::Result<()> {
+    for group in groups {
+        let dimension = Dimension::of(group)?;
+        let count = rust_type(dimension.num_in_group.1);
+        let (block_length_offset, block_length_type) = dimension.block_length;
+        source.push(&fill(
+            r#"
+            #[derive(Debug, Default)]
+            pub struct {ty}Encoder<P> {
+                parent: Option<P>,
+                count: {count},
+                index: usize,
+                offset: usize,
+                initial_limit: usize,
+            }
+
+            impl<'a, P> Writer<'a> for {ty}Encoder<P>
+            where
+                P: Writer<'a> + Default,
+            {
+                #[inline]
+                fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+                    if let Some(parent) = self.parent.as_mut() {
+                        parent.get_buf_mut()
+                    } else {
+                        panic!("parent was None")
+                    }
+                }
+            }
+
+            impl<'a, P> Encoder<'a> for {ty}Encoder<P>
+            where
+                P: Encoder<'a> + Default,
+            {
+                #[inline]
+                fn get_limit(&self) -> usize {
+                    self.parent.as_ref().expect("parent missing").get_limit()
+                }
+
+                #[inline]
+                fn set_limit(&mut self, limit: usize) {
+                    self.parent.as_mut().expect("parent missing").set_limit(limit);
+                }
+            }
+            "#,
+            &[("ty", &type_name(&group.name)), ("count", count)],
+        ));
+        source.open(&format!(
+            "\nimpl<'a, P> {}Encoder<P>\nwhere\n    P: Encoder<'a> + Default,\n{{",
+            type_name(&group.name)
+        ));
+        source.push(&fill(
+            r#"
+            #[inline]
+            pub fn wrap(mut self, mut parent: P, count: {count}) -> Self {
+                let initial_limit = parent.get_limit();
+                parent.set_limit(initial_limit + {size});
+                parent.get_buf_mut().put_{block_length_type}_at({block_length_offset}, Self::block_length(){block_length_conversion});
+                parent.get_buf_mut().put_{count}_at({count_offset}, count);
+                self.parent = Some(parent);
+                self.count = count;
+                self.index = usize::MAX;
+                self.offset = usize::MAX;
+                self.initial_limit = initial_limit;
+                self
+            }
+
+            #[inline]
+            pub fn block_length() -> u16 {
+                {block_length}
+            }
+
+            #[inline]
+            pub fn parent(&mut self) -> SbeResult<P> {
+                self.parent.take().ok_or(SbeErr::ParentNotSet)
+            }
+
+            /// will return Some(current index) when successful otherwise None
+            #[inline]
+            pub fn advance(&mut self) -> SbeResult<Option<usize>> {
+                let index = self.index.wrapping_add(1);
+                if index >= self.count as usize {
+                    return Ok(None);
+                }
+                if let Some(parent) = self.parent.as_mut() {
+                    self.offset = parent.get_limit();
+                    parent.set_limit(self.offset + Self::block_length() as usize);
+                    self.index = index;
+                    Ok(Some(index))
+                } else {
+                    Err(SbeErr::ParentNotSet)
+                }
+            }
+
+            "#,
+            &[
+                ("count", count),
+                ("size", &dimension.size.to_string()),
+                ("block_length_type", rust_type(block_length_type)),
+                (
+                    "block_length_offset",
+                    &offset_expression("initial_limit", block_length_offset),
+                ),
+                (
+                    "block_length_conversion",
+                    &conversion(Primitive::UInt16, rust_type(block_length_type)),
+                ),
+                (
+                    "count_offset",
+                    &offset_expression("initial_limit", dimension.num_in_group.0),
+                ),
+                ("block_length", &group.block_length.to_string()),
+            ],
+        ));
+        encode_body(source, &group.body)?;
+        source.close("}\n");
+        group_encoders(source, &group.body.groups)?;
+    }
+    Ok(())
+}
+
+/// The decoder structs of `groups` and of the groups nested in them, depth first.
+fn group_decoders(source: &mut Source, groups: &[Group]) -> anyhow::Result<()> {
+    for group in groups {
+        let dimension = Dimension::of(group)?;
+        let count = rust_type(dimension.num_in_group.1);
+        let (block_length_offset, block_length_type) = dimension.block_length;
+        source.push(&fill(
+            r#"
+            #[derive(Debug, Default)]
+            pub struct {ty}Decoder<P> {
+                parent: Option<P>,
+                block_length: u16,
+                count: {count},
+                index: usize,
+                offset: usize,
+            }
+
+            impl<'a, P> ActingVersion for {ty}Decoder<P>
+            where
+                P: Reader<'a> + ActingVersion + Default,
+            {
+                #[inline]
+                fn acting_version(&self) -> u16 {
+                    self.parent.as_ref().unwrap().acting_version()
+                }
+            }
+
+            impl<'a, P> Reader<'a> for {ty}Decoder<P>
+            where
+                P: Reader<'a> + Default,
+            {
+                #[inline]
+                fn get_buf(&self) -> &ReadBuf<'a> {
+                    self.parent.as_ref().expect("parent missing").get_buf()
+                }
+            }
+
+            impl<'a, P> Decoder<'a> for {ty}Decoder<P>
+            where
+                P: Decoder<'a> + ActingVersion + Default,
+            {
+                #[inline]
+                fn get_limit(&self) -> usize {
+                    self.parent.as_ref().expect("parent missing").get_limit()
+                }
+
+                #[inline]
+                fn set_limit(&mut self, limit: usize) {
+                    self.parent.as_mut().expect("parent missing").set_limit(limit);
+                }
+            }
+            "#,
+            &[("ty", &type_name(&group.name)), ("count", count)],
+        ));
+        source.open(&format!(
+            "\nimpl<'a, P> {}Decoder<P>\nwhere\n    P: Decoder<'a> + ActingVersion + Default,\n{{",
+            type_name(&group.name)
+        ));
+        source.push(&fill(
+            r#"
+            pub fn wrap(mut self, mut parent: P) -> Self {
+                let initial_offset = parent.get_limit();
+                let block_length = parent.get_buf().get_{block_length_type}_at({block_length_offset}){block_length_conversion};
+                let count = parent.get_buf().get_{count}_at({count_offset});
+                parent.set_limit(initial_offset + {size});
+                self.parent = Some(parent);
+                self.block_length = block_length;
+                self.count = count;
+                self.index = usize::MAX;
+                self.offset = 0;
+                self
+            }
+
+            /// Checked variant of `wrap` that fails with `SbeErr::BufferTooShort`
+            /// instead of panicking when the group dimensions do not fit in the buffer,
+            /// and with `SbeErr::BlockLengthTooShort` when the entries are shorter than
+            /// the fields of the acting version, so that every entry moves the limit.
+            pub fn try_wrap(self, parent: P) -> SbeResult<Self> {
+                parent.get_buf().check_bounds(parent.get_limit(), {size})?;
+                let needed = Self::min_block_length(parent.acting_version());
+                let group = self.wrap(parent);
+                if (group.block_length as usize) < needed {
+                    return Err(SbeErr::BlockLengthTooShort {
+                        block_length: group.block_length as usize,
+                        needed,
+                    });
+                }
+                Ok(group)
+            }
+
+            "#,
+            &[
+                ("count", count),
+                ("size", &dimension.size.to_string()),
+                ("block_length_type", rust_type(block_length_type)),
+                (
+                    "block_length_offset",
+                    &offset_expression("initial_offset", block_length_offset),
+                ),
+                (
+                    "block_length_conversion",
+                    &conversion(block_length_type, "u16"),
+                ),
+                (
+                    "count_offset",
+                    &offset_expression("initial_offset", dimension.num_in_group.0),
+                ),
+            ],
+        ));
+        min_block_length(source, &group.body.fields);
+        source.push(&fill(
+            r#"
+            /// group token - name='{name}', id={id}, encodedLength={block_length}
+            #[inline]
+            pub fn parent(&mut self) -> SbeResult<P> {
+                self.parent.take().ok_or(SbeErr::ParentNotSet)
+            }
+
+            #[inline]
+            pub fn acting_version(&mut self) -> u16 {
+                self.parent.as_ref().unwrap().acting_version()
+            }
+
+            #[inline]
+            pub fn count(&self) -> {count} {
+                self.count
+            }
+
+            /// will return Some(current index) when successful otherwise None
+            pub fn advance(&mut self) -> SbeResult<Option<usize>> {
+                let index = self.index.wrapping_add(1);
+                if index >= self.count as usize {
+                    return Ok(None);
+                }
+                if let Some(parent) = self.parent.as_mut() {
+                    self.offset = parent.get_limit();
+                    parent.set_limit(self.offset + self.block_length as usize);
+                    self.index = index;
+                    Ok(Some(index))
+                } else {
+                    Err(SbeErr::ParentNotSet)
+                }
+            }
+
+            /// Checked variant of `advance` that fails with `SbeErr::BufferTooShort`
+            /// instead of letting field accessors read past the end of the buffer.
+            pub fn try_advance(&mut self) -> SbeResult<Option<usize>> {
+                if self.index.wrapping_add(1) >= self.count as usize {
+                    return Ok(None);
+                }
+                let parent = self.parent.as_ref().ok_or(SbeErr::ParentNotSet)?;
+                let needed =
+                    Self::min_block_length(parent.acting_version()).max(self.block_length as usize);
+                parent.get_buf().check_bounds(parent.get_limit(), needed)?;
+                self.advance()
+            }
+
+            "#,
+            &[
+                ("name", &group.name),
+                ("id", &group.id.to_string()),
+                ("block_length", &group.block_length.to_string()),
+                ("count", count),
+            ],
+        ));
+        decode_body(source, &group.body, true)?;
+        source.close("}\n");
+        group_decoders(source, &group.body.groups)?;
+    }
+    Ok(())
+}
+
+fn message_codec(schema: &Schema, message: &Message) -> anyhow::Result<String> {
+    let ty = type_name(&message.name);
+    let header_module = codec_module(&schema.header.name);
+    let header = type_name(&schema.header.name);
+    let values = [
+        ("ty", ty.as_str()),
+        ("header_module", &header_module),
+        ("header", &header),
+    ];
+    let mut source = Source::default();
+    source.push(&fill(
+        r#"
+        use super::*;
+
+        pub use decoder::{ty}Decoder;
+        pub use encoder::{ty}Encoder;
+
+        pub use super::SBE_SCHEMA_ID;
+        pub use super::SBE_SCHEMA_VERSION;
+        pub use super::SBE_SEMANTIC_VERSION;
+
+        "#,
+        &values,
+    ));
+    source.push(&format!(
+        "pub const SBE_BLOCK_LENGTH: u16 = {};\npub const SBE_TEMPLATE_ID: u16 = {};\n",
+        message.block_length, message.id
+    ));
+
+    source.open(&fill(
+        r#"
+        pub mod encoder {
+            use super::*;
+            use {header_module}::*;
+
+            #[derive(Debug, Default)]
+            pub struct {ty}Encoder<'a> {
+                buf: WriteBuf<'a>,
+                initial_offset: usize,
+                offset: usize,
+                limit: usize,
+            }
+
+            impl<'a> Writer<'a> for {ty}Encoder<'a> {
+                #[inline]
+                fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+                    &mut self.buf
+                }
+            }
+
+            impl<'a> Encoder<'a> for {ty}Encoder<'a> {
+                #[inline]
+                fn get_limit(&self) -> usize {
+                    self.limit
+                }
+
+                #[inline]
+                fn set_limit(&mut self, limit: usize) {
+                    self.limit = limit;
+                }
+            }
+
+            impl<'a> {ty}Encoder<'a> {
+                pub fn wrap(mut self, buf: WriteBuf<'a>, offset: usize) -> Self {
+                    let limit = offset + SBE_BLOCK_LENGTH as usize;
+                    self.buf = buf;
+                    self.initial_offset = offset;
+                    self.offset = offset;
+                    self.limit = limit;
+                    self
+                }
+
+                #[inline]
+                pub fn encoded_length(&self) -> usize {
+                    self.limit - self.offset
+                }
+
+                pub fn header(self, offset: usize) -> {header}Encoder<Self> {
+                    let mut header = {header}Encoder::default().wrap(self, offset);
+                    header.block_length(SBE_BLOCK_LENGTH);
+                    header.template_id(SBE_TEMPLATE_ID);
+                    header.schema_id(SBE_SCHEMA_ID);
+                    header.version(SBE_SCHEMA_VERSION);
+                    header
+                }
+
+        "#,
+        &values,
+    ));
+    source.indent += 1;
+    encode_body(&mut source, &message.body)?;
+    source.close("}\n");
+    group_encoders(&mut source, &message.body.groups)?;
+    source.close("} // end encoder\n");
+
+    source.open(&fill(
+        r#"
+        pub mod decoder {
+            use super::*;
+            use {header_module}::*;
+
+            #[derive(Clone, Copy, Debug, Default)]
+            pub struct {ty}Decoder<'a> {
+                buf: ReadBuf<'a>,
+                initial_offset: usize,
+                offset: usize,
+                limit: usize,
+                pub acting_block_length: u16,
+                pub acting_version: u16,
+            }
+
+            impl ActingVersion for {ty}Decoder<'_> {
+                #[inline]
+                fn acting_version(&self) -> u16 {
+                    self.acting_version
+                }
+            }
+
+            impl<'a> Reader<'a> for {ty}Decoder<'a> {
+                #[inline]
+                fn get_buf(&self) -> &ReadBuf<'a> {
+                    &self.buf
+                }
+            }
+
+            impl<'a> Decoder<'a> for {ty}Decoder<'a> {
+                #[inline]
+                fn get_limit(&self) -> usize {
+                    self.limit
+                }
+
+                #[inline]
+                fn set_limit(&mut self, limit: usize) {
+                    self.limit = limit;
+                }
+            }
+
+            impl<'a> {ty}Decoder<'a> {
+                pub fn wrap(
+                    mut self,
+                    buf: ReadBuf<'a>,
+                    offset: usize,
+                    acting_block_length: u16,
+                    acting_version: u16,
+                ) -> Self {
+                    let limit = offset + acting_block_length as usize;
+                    self.buf = buf;
+                    self.initial_offset = offset;
+                    self.offset = offset;
+                    self.limit = limit;
+                    self.acting_block_length = acting_block_length;
+                    self.acting_version = acting_version;
+                    self
+                }
+
+                #[inline]
+                pub fn encoded_length(&self) -> usize {
+                    self.limit - self.offset
+                }
+
+                pub fn header(self, mut header: {header}Decoder<ReadBuf<'a>>, offset: usize) -> Self {
+                    debug_assert_eq!(SBE_TEMPLATE_ID, header.template_id());
+                    let acting_block_length = header.block_length();
+                    let acting_version = header.version();
+
+                    self.wrap(
+                        header.parent().unwrap(),
+                        offset + {header_module}::ENCODED_LENGTH,
+                        acting_block_length,
+                        acting_version,
+                    )
+                }
+
+                /// Checked variant of `header` that fails with `SbeErr::BufferTooShort`
+                /// instead of panicking when the fixed block does not fit in the buffer.
+                pub fn try_header(
+                    self,
+                    mut header: {header}Decoder<ReadBuf<'a>>,
+                    offset: usize,
+                ) -> SbeResult<Self> {
+                    header
+                        .get_buf()
+                        .check_bounds(offset, {header_module}::ENCODED_LENGTH)?;
+                    let acting_block_length = header.block_length();
+                    let acting_version = header.version();
+                    let buf = header.parent()?;
+                    let offset = offset + {header_module}::ENCODED_LENGTH;
+                    let needed = Self::min_block_length(acting_version).max(acting_block_length as usize);
+                    buf.check_bounds(offset, needed)?;
+
+                    Ok(self.wrap(buf, offset, acting_block_length, acting_version))
+                }
+
+        "#,
+        &values,
+    ));
+    source.indent += 1;
+    min_block_length(&mut source, &message.body.fields);
+    decode_body(&mut source, &message.body, false)?;
+    source.close("}\n");
+    group_decoders(&mut source, &message.body.groups)?;
+    source.close("} // end decoder");
+    Ok(source.text)
+}
+
+fn composite_codec(schema: &Schema, composite: &CompositeType) -> anyhow::Result<String> {
+    let ty = type_name(&composite.name);
+    let mut source = Source::default();
+    source.push(&fill(
+        r#"
+        use super::*;
+
+        pub use decoder::{ty}Decoder;
+        pub use encoder::{ty}Encoder;
+
+        "#,
+        &[("ty", &ty)],
+    ));
+    let var_data = composite
+        .members
+        .iter()
+        .any(|member| matches!(&member.ty, Type::Encoded(encoded) if encoded.length == 0));
+    if !var_data {
+        source.push(&format!(
+            "pub const ENCODED_LENGTH: usize = {};\n",
+            Type::Composite(composite.clone()).size()
+        ));
+    }
+
+    source.open(&fill(
+        r#"
+        pub mod encoder {
+            use super::*;
+
+            #[derive(Debug, Default)]
+            pub struct {ty}Encoder<P> {
+                parent: Option<P>,
+                offset: usize,
+            }
+
+            impl<'a, P> Writer<'a> for {ty}Encoder<P>
+            where
+                P: Writer<'a> + Default,
+            {
+                #[inline]
+                fn get_buf_mut(&mut self) -> &mut WriteBuf<'a> {
+                    if let Some(parent) = self.parent.as_mut() {
+                        parent.get_buf_mut()
+                    } else {
+                        panic!("parent was None")
+                    }
+                }
+            }
+
+            impl<'a, P> {ty}Encoder<P>
+            where
+                P: Writer<'a> + Default,
+            {
+                pub fn wrap(mut self, parent: P, offset: usize) -> Self {
+                    self.parent = Some(parent);
+                    self.offset = offset;
+                    self
+                }
+
+                #[inline]
+                pub fn parent(&mut self) -> SbeResult<P> {
+                    self.parent.take().ok_or(SbeErr::ParentNotSet)
+                }
+
+        "#,
+        &[("ty", &ty)],
+    ));
+    source.indent += 1;
+    for member in &composite.members {
+        encode_accessor(&mut source, &Accessor::member(member))?;
+    }
+    source.close("}");
+    source.close("} // end encoder mod\n");
+
+    let try_wrap = if composite.name == schema.header.name {
+        r#"
+        /// Checked variant of `wrap` that fails with `SbeErr::BufferTooShort`
+        /// instead of panicking when the header does not fit in the buffer.
+        pub fn try_wrap(self, parent: P, offset: usize) -> SbeResult<Self> {
+            parent.get_buf().check_bounds(offset, ENCODED_LENGTH)?;
+            Ok(self.wrap(parent, offset))
+        }
+        "#
+    } else {
+        ""
+    };
+    source.open(&fill(
+        r#"
+        pub mod decoder {
+            use super::*;
+
+            #[derive(Debug, Default)]
+            pub struct {ty}Decoder<P> {
+                parent: Option<P>,
+                offset: usize,
+            }
+
+            impl<'a, P> ActingVersion for {ty}Decoder<P>
+            where
+                P: Reader<'a> + ActingVersion + Default,
+            {
+                #[inline]
+                fn acting_version(&self) -> u16 {
+                    self.parent.as_ref().unwrap().acting_version()
+                }
+            }
+
+            impl<'a, P> Reader<'a> for {ty}Decoder<P>
+            where
+                P: Reader<'a> + Default,
+            {
+                #[inline]
+                fn get_buf(&self) -> &ReadBuf<'a> {
+                    self.parent.as_ref().expect("parent missing").get_buf()
+                }
+            }
+
+            impl<'a, P> {ty}Decoder<P>
+            where
+                P: Reader<'a> + Default,
+            {
+                pub fn wrap(mut self, parent: P, offset: usize) -> Self {
+                    self.parent = Some(parent);
+                    self.offset = offset;
+                    self
+                }
+        "#,
+        &[("ty", &ty)],
+    ));
+    source.indent += 1;
+    source.push(try_wrap);
+    source.push(
+        r#"
+        #[inline]
+        pub fn parent(&mut self) -> SbeResult<P> {
+            self.parent.take().ok_or(SbeErr::ParentNotSet)
+        }
+
+        "#,
+    );
+    for member in &composite.members {
+        decode_accessor(&mut source, &Accessor::member(member))?;
+    }
+    source.close("}");
+    source.close("} // end decoder mod");
+    Ok(source.text)
+}
+
+fn enum_type(enumeration: &EnumType) -> anyhow::Result<String> {
+    let ty = type_name(&enumeration.name);
+    let repr = rust_type(enumeration.encoding);
+    let null = null_literal(enumeration.encoding);
+    let mut variants = Vec::new();
+    for (name, value) in &enumeration.values {
+        variants.push((
+            name.as_str(),
+            type_name(name),
+            literal(enumeration.encoding, (*value).into()),
+        ));
+    }
+
+    let mut source = Source::default();
+    source.push(&format!(
+        "#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]\n#[repr({repr})]"
+    ));
+    source.open(&format!("pub enum {ty} {{"));
+    for (_, variant, value) in &variants {
+        source.push(&format!("{variant} = {value},"));
+    }
+    source.push(&format!("#[default]\nNullVal = {null},"));
+    source.close("}");
+
+    source.open(&format!("impl From<{repr}> for {ty} {{\n    #[inline]\n    fn from(v: {repr}) -> Self {{\n        match v {{"));
+    source.indent += 2;
+    for (_, variant, value) in &variants {
+        source.push(&format!("{value} => Self::{variant},"));
+    }
+    source.push("_ => Self::NullVal,");
+    source.indent -= 2;
+    source.close("        }\n    }\n}");
+
+    source.open(&format!("impl From<{ty}> for {repr} {{\n    #[inline]\n    fn from(v: {ty}) -> Self {{\n        match v {{"));
+    source.indent += 2;
+    for (_, variant, value) in &variants {
+        source.push(&format!("{ty}::{variant} => {value},"));
+    }
+    source.push(&format!("{ty}::NullVal => {null},"));
+    source.indent -= 2;
+    source.close("        }\n    }\n}");
+
+    source.open(&format!(
+        "impl core::str::FromStr for {ty} {{\n    type Err = ();\n\n    #[inline]\n    fn from_str(v: &str) -> core::result::Result<Self, Self::Err> {{\n        match v {{"
+    ));
+    source.indent += 2;
+    for (name, variant, _) in &variants {
+        source.push(&format!("{name:?} => Ok(Self::{variant}),"));
+    }
+    source.push("_ => Ok(Self::NullVal),");
+    source.indent -= 2;
+    source.close("        }\n    }\n}");
+
+    source.open(&format!(
+        "impl core::fmt::Display for {ty} {{\n    #[inline]\n    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {{\n        match self {{"
+    ));
+    source.indent += 2;
+    for (name, variant, _) in &variants {
+        source.push(&format!("Self::{variant} => write!(f, {name:?}),"));
+    }
+    source.push("Self::NullVal => write!(f, \"NullVal\"),");
+    source.indent -= 2;
+    source.close("        }\n    }\n}");
+    Ok(source.text)
+}
+
+fn set_type(set: &SetType) -> anyhow::Result<String> {
+    let ty = type_name(&set.name);
+    let repr = rust_type(set.encoding);
+    let bits = set.encoding.size() * 8;
+    let mut source = Source::default();
+    source.push(&format!(
+        "#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]\npub struct {ty}(pub {repr});"
+    ));
+    source.open(&format!("impl {ty} {{"));
+    source.push(&fill(
+        r#"
+        #[inline]
+        pub fn new(value: {repr}) -> Self {
+            {ty}(value)
+        }
+
+        #[inline]
+        pub fn clear(&mut self) -> &mut Self {
+            self.0 = 0;
+            self
+        }
+        "#,
+        &[("ty", &ty), ("repr", repr)],
+    ));
+    for (name, bit) in &set.choices {
+        if *bit as usize >= bits {
+            bail!("Choice {name} of {} is out of range", set.name);
+        }
+        source.push(&fill(
+            r#"
+
+            #[inline]
+            pub fn get_{method}(&self) -> bool {
+                0 != self.0 & (1 << {bit})
+            }
+
+            #[inline]
+            pub fn set_{method}(&mut self, value: bool) -> &mut Self {
+                self.0 = if value {
+                    self.0 | (1 << {bit})
+                } else {
+                    self.0 & !(1 << {bit})
+                };
+                self
+            }
+            "#,
+            &[("method", &snake_case(name)), ("bit", &bit.to_string())],
+        ));
+    }
+    source.close("}");
+    let format: Vec<String> = set
+        .choices
+        .iter()
+        .map(|(name, bit)| format!("{}({bit})={{}}", snake_case(name)))
+        .collect();
+    let arguments: String = set
+        .choices
+        .iter()
+        .map(|(name, _)| format!("self.get_{}(),", snake_case(name)))
+        .collect();
+    source.push(&format!(
+        r#"
+        impl core::fmt::Debug for {ty} {{
+            #[inline]
+            fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {{
+                write!(fmt, "{ty}[{format}]",
+                    {arguments})
+            }}
+        }}
+        "#,
+        format = format.join(","),
+    ));
+    Ok(source.text)
+}
+
+fn message_length(schema: &Schema) -> anyhow::Result<String> {
+    fn body_parts(body: &Body) -> anyhow::Result<String> {
+        let mut parts = Vec::new();
+        for group in &body.groups {
+            let dimension = Dimension::of(group)?;
+            parts.push(format!(
+                "Group({}, {})",
+                width(dimension.num_in_group.1)?,
+                body_parts(&group.body)?
+            ));
+        }
+        for data in &body.data {
+            parts.push(format!("VarData({})", width(data_length(data)?.1)?));
+        }
+        Ok(format!("&[{}]", parts.join(", ")))
+    }
+
+    let mut layout = Source::default();
+    layout.open(
+        r#"
+        fn layout(template_id: u16) -> Option<&'static [Part]> {
+            use Part::*;
+            use Width::*;
+            Some(match template_id {
+        "#,
+    );
+    layout.indent += 1;
+    for message in schema.messages() {
+        layout.push(&format!(
+            "{}::SBE_TEMPLATE_ID => {},",
+            codec_module(&message.name),
+            body_parts(&message.body)?
+        ));
+    }
+    layout.push("_ => return None,");
+    layout.indent -= 1;
+    layout.close("    })\n}");
+    Ok(MESSAGE_LENGTH.replace("// {layout}\n", &layout.text))
+}
+
+/// The codecs of `schema`, as the source of the inline modules of its types and
+/// messages.
+fn schema_codecs(schema: &Schema) -> anyhow::Result<String> {
+    if schema.byte_order != ByteOrder::LittleEndian {
+        bail!("Only little-endian schemas are supported");
+    }
+    for member in ["blockLength", "templateId", "schemaId", "version"] {
+        schema.header.primitive_member(member)?;
+    }
+    let mut modules = Vec::new();
+    for ty in schema.types() {
+        let module = match ty {
+            Type::Encoded(_) => continue,
+            Type::Enum(enumeration) => (snake_case(&enumeration.name), enum_type(enumeration)),
+            Type::Set(set) => (snake_case(&set.name), set_type(set)),
+            Type::Composite(composite) => (
+                codec_module(&composite.name),
+                composite_codec(schema, composite),
+            ),
+        };
+        let (name, source) = module;
+        modules.push((name, source.with_context(|| format!("Type {}", ty.name()))?));
+    }
+    for message in schema.messages() {
+        let source =
+            message_codec(schema, message).with_context(|| format!("Message {}", message.name))?;
+        modules.push((codec_module(&message.name), source));
+    }
+    modules.push(("message_length".to_owned(), message_length(schema)?));
+    modules.sort();
+    for pair in modules.windows(2) {
+        if pair[0].0 == pair[1].0 {
+            bail!("Two schema names map to the module {}", pair[0].0);
+        }
+    }
+
+    let mut source = Source::default();
+    source.push("use super::*;\n");
+    for (name, module) in &modules {
+        source.open(&format!("pub mod {name} {{"));
+        source.push(module);
+        source.close("}\n");
+    }
+    source.push(&format!(
+        "pub const SBE_SCHEMA_ID: u16 = {};\npub const SBE_SCHEMA_VERSION: u16 = {};\npub const SBE_SEMANTIC_VERSION: &str = {:?};",
+        schema.id, schema.version, schema.semantic_version
+    ));
+    Ok(source.text)
+}
+
+/// Enums converted by an upgrade, by the name of the function converting them.
+type UpgradedEnums = BTreeMap<String, (EnumType, EnumType)>;
+
+/// The value to encode in the field `to` of the target schema, read from the
+/// same-named field `from`, or the value decoders read from versions without
+/// the field when `from` has none. Enums are converted by variant name, through
+/// a function added to `enums`.
+fn upgraded_value(
+    from: Option<&Field>,
+    to: &Field,
+    decoder: &str,
+    enums: &mut UpgradedEnums,
+) -> anyhow::Result<(String, &'static str)> {
+    let method = snake_case(&to.name);
+    let absent = to.since_version > 0 || matches!(to.presence, Presence::Optional);
+    Ok(
+        match (&to.ty, from.map(|from| (&from.ty, &from.presence))) {
+            (Type::Encoded(to), Some((Type::Encoded(from), _))) if to.length > 1 => {
+                if (from.primitive, from.length) != (to.primitive, to.length) {
+                    bail!("Field {} changes type", method);
+                }
+                (format!("{decoder}.{method}()"), "&")
+            }
+            (Type::Encoded(to), None) if to.length > 1 && absent => {
+                (format!("[{}; {}]", encoded_null(to)?, to.length), "&")
+            }
+            (Type::Encoded(to), Some((Type::Encoded(from), presence))) => {
+                if from.primitive != to.primitive || from.length != to.length {
+                    bail!("Field {} changes type", method);
+                }
+                match presence {
+                    Presence::Optional => (
+                        format!("{decoder}.{method}().unwrap_or({})", encoded_null(to)?),
+                        "",
+                    ),
+                    _ => (format!("{decoder}.{method}()"), ""),
+                }
+            }
+            (Type::Encoded(to), None) if absent => (encoded_null(to)?, ""),
+            (Type::Enum(to), Some((Type::Enum(from), _))) => {
+                let function = format!("upgrade_{}", snake_case(&from.name));
+                let pair = enums
+                    .entry(function.clone())
+                    .or_insert_with(|| (from.clone(), to.clone()));
+                if pair.1.name != to.name {
+                    bail!(
+                        "Enum {} upgrades to both {} and {}",
+                        from.name,
+                        pair.1.name,
+                        to.name
+                    );
+                }
+                (format!("{function}({decoder}.{method}())?"), "")
+            }
+            (Type::Enum(to), None) if absent => (
+                format!("target::{}::NullVal", type_path(&Type::Enum(to.clone()))),
+                "",
+            ),
+            (Type::Set(to), Some((Type::Set(from), _))) => {
+                if from.encoding != to.encoding {
+                    bail!("Field {} changes type", method);
+                }
+                (
+                    format!(
+                        "target::{}::new({decoder}.{method}().0)",
+                        type_path(&Type::Set(to.clone()))
+                    ),
+                    "",
+                )
+            }
+            (Type::Set(to), None) if absent => (
+                format!("target::{}::default()", type_path(&Type::Set(to.clone()))),
+                "",
+            ),
+            (_, None) => bail!("Required field {} is missing", method),
+            _ => bail!("Field {} changes type", method),
+        },
+    )
+}
+
+/// Whether the bodies have the same groups and var-data, in the same order, so
+/// that walking one walks the other.
+fn check_same_layout(from: &Body, to: &Body) -> anyhow::Result<()> {
+    let groups = |body: &Body| -> Vec<(String, Option<Primitive>)> {
+        body.groups
+            .iter()
+            .map(|group| {
+                let count = group.dimension.primitive_member("numInGroup").ok();
+                (group.name.clone(), count.map(|(_, primitive)| primitive))
+            })
+            .collect()
+    };
+    let data = |body: &Body| -> Vec<(String, Option<Primitive>)> {
+        body.data
+            .iter()
+            .map(|data| (data.name.clone(), data_length(data).ok().map(|(_, ty)| ty)))
+            .collect()
+    };
+    if groups(from) != groups(to) {
+        bail!("Groups differ");
+    }
+    if data(from) != data(to) {
+        bail!("Var-data differ");
+    }
+    Ok(())
+}
+
+/// The newest version, up to `version`, whose fields of `to` are all read from
+/// `from`: the version an upgraded message holds, as the fields `from` lacks
+/// are encoded as decoders read them from versions without them.
+fn upgraded_version(from: &Body, to: &Body, version: u16) -> u16 {
+    let mut version = version;
+    for field in &to.fields {
+        let read = from
+            .fields
+            .iter()
+            .any(|from| from.name == field.name && !matches!(from.presence, Presence::Constant(_)));
+        if !read && field.since_version > 0 && !matches!(field.presence, Presence::Constant(_)) {
+            version = version.min(field.since_version - 1);
+        }
+    }
+    for (from, to) in from.groups.iter().zip(&to.groups) {
+        version = upgraded_version(&from.body, &to.body, version);
+    }
+    version
+}
+
+/// A function converting the enum `from` to the enum `to` by variant name,
+/// failing with `SbeErr::UnsupportedVariant` for the variants `to` lacks.
+fn upgraded_enum(function: &str, from: &EnumType, to: &EnumType) -> String {
+    let from_path = type_path(&Type::Enum(from.clone()));
+    let to_path = format!("target::{}", type_path(&Type::Enum(to.clone())));
+    let mut source = Source::default();
+    source.open(&format!(
+        "fn {function}(value: {from_path}) -> SbeResult<{to_path}> {{\n    match value {{"
+    ));
+    source.indent += 1;
+    for (name, _) in &from.values {
+        let variant = type_name(name);
+        if to.values.iter().any(|(candidate, _)| candidate == name) {
+            source.push(&format!(
+                "{from_path}::{variant} => Ok({to_path}::{variant}),"
+            ));
+        } else {
+            source.push(&format!(
+                "{from_path}::{variant} => Err(SbeErr::UnsupportedVariant {{\n    enumeration: {:?},\n    variant: {name:?},\n}}),",
+                from.name
+            ));
+        }
+    }
+    source.push(&format!("{from_path}::NullVal => Ok({to_path}::NullVal),"));
+    source.indent -= 1;
+    source.close("    }\n}\n");
+    source.text
+}
+
+/// Whether the group at `index` is the last part of `body` to walk.
+fn is_last_walk(body: &Body, index: usize) -> bool {
+    index + 1 == body.groups.len() && body.data.is_empty()
+}
+
+/// Adds to `length` what the groups and var-data of `to` take once encoded,
+/// walking them in `from` through `decoder`, which is a group's when `nested`.
+fn upgraded_length(
+    source: &mut Source,
+    to: &Body,
+    decoder: &str,
+    nested: bool,
+) -> anyhow::Result<()> {
+    for (index, group) in to.groups.iter().enumerate() {
+        let method = snake_case(&group.name);
+        let dimension = Dimension::of(group)?;
+        source.open(&format!(
+            "let mut {method} = {decoder}.try_{method}_decoder()?;\nlength += {};\nfor _ in 0..{method}.count() {{",
+            dimension.size
+        ));
+        source.push(&format!("{method}.try_advance()?;"));
+        if group.block_length > 0 {
+            source.push(&format!("length += {};", group.block_length));
+        }
+        upgraded_length(source, &group.body, &method, true)?;
+        source.close("}");
+        if nested || !is_last_walk(to, index) {
+            source.push(&format!("{decoder} = {method}.parent()?;"));
+        }
+    }
+    for data in &to.data {
+        let (length, _) = data_length(data)?;
+        source.push(&format!(
+            "let (_, data_length) = {decoder}.try_{}_decoder()?;\nlength += {length} + data_length;",
+            snake_case(&data.name)
+        ));
+    }
+    Ok(())
+}
+
+/// Encodes `to` through `encoder` from `from`, read through `decoder`, which
+/// are a group's when `nested`.
+fn upgraded_body(
+    source: &mut Source,
+    from: &Body,
+    to: &Body,
+    (decoder, encoder): (&str, &str),
+    nested: bool,
+    enums: &mut UpgradedEnums,
+) -> anyhow::Result<()> {
+    check_same_layout(from, to)?;
+    for field in &to.fields {
+        if matches!(field.presence, Presence::Constant(_)) {
+            continue;
+        }
+        if matches!(field.ty, Type::Composite(_)) {
+            bail!("Composite field {} is not supported", field.name);
+        }
+        let same = from.fields.iter().find(|from| {
+            from.name == field.name && !matches!(from.presence, Presence::Constant(_))
+        });
+        let (value, reference) = upgraded_value(same, field, decoder, enums)?;
+        source.push(&format!(
+            "{encoder}.{}({reference}{value});",
+            snake_case(&field.name)
+        ));
+    }
+    for (index, (from, group)) in from.groups.iter().zip(&to.groups).enumerate() {
+        let method = snake_case(&group.name);
+        source.push(&format!(
+            "let mut {method} = {decoder}.try_{method}_decoder()?;\nlet count = {method}.count();\nlet mut {method}_encoder = {encoder}.{method}_encoder(count, Default::default());"
+        ));
+        source.open("for _ in 0..count {");
+        source.push(&format!(
+            "{method}.try_advance()?;\n{method}_encoder.advance()?;"
+        ));
+        upgraded_body(
+            source,
+            &from.body,
+            &group.body,
+            (&method, &format!("{method}_encoder")),
+            true,
+            enums,
+        )
+        .with_context(|| format!("Group {}", group.name))?;
+        source.close("}");
+        if nested || !is_last_walk(to, index) {
+            source.push(&format!(
+                "{decoder} = {method}.parent()?;\n{encoder} = {method}_encoder.parent()?;"
+            ));
+        }
+    }
+    for data in &to.data {
+        let (length, primitive) = data_length(data)?;
+        source.push(&fill(
+            r#"
+            let coordinates = {decoder}.try_{method}_decoder()?;
+            let value = {decoder}.{method}_slice(coordinates);
+            let limit = {encoder}.get_limit();
+            {encoder}.set_limit(limit + {length} + value.len());
+            {encoder}.get_buf_mut().put_{ty}_at(limit, value.len() as {ty});
+            {encoder}.get_buf_mut().put_slice_at(limit + {length}, value);
+            "#,
+            &[
+                ("decoder", decoder),
+                ("encoder", encoder),
+                ("method", &snake_case(&data.name)),
+                ("length", &length.to_string()),
+                ("ty", rust_type(primitive)),
+            ],
+        ));
+    }
+    Ok(())
+}
+
+/// The `upgrade` module of the codecs of `from`, re-encoding each message as
+/// the same-named message of `to` for code written against the codecs of `to`.
+fn upgrade_codec(from: &Schema, to: &Schema) -> anyhow::Result<String> {
+    let header = codec_module(&from.header.name);
+    let target_header = codec_module(&to.header.name);
+    let mut arms = String::new();
+    let mut functions = Source::default();
+    let mut enums = UpgradedEnums::new();
+    for message in from.messages() {
+        let Some(target) = to.messages().find(|target| target.name == message.name) else {
+            continue;
+        };
+        let name = snake_case(&message.name);
+        let module = codec_module(&message.name);
+        let ty = type_name(&message.name);
+        let walks = !target.body.groups.is_empty() || !target.body.data.is_empty();
+        // Var-data decoders borrow their parent and group decoders take it, so
+        // it is only reassigned when something follows the last group.
+        let reassigned = !target.body.data.is_empty() || target.body.groups.len() > 1;
+        let fields = || {
+            target
+                .body
+                .fields
+                .iter()
+                .filter(|field| !matches!(field.presence, Presence::Constant(_)))
+        };
+        let reads = walks
+            || fields().any(|field| {
+                message.body.fields.iter().any(|from| {
+                    from.name == field.name && !matches!(from.presence, Presence::Constant(_))
+                })
+            });
+        let mutable = |mutable: bool| if mutable { "mut " } else { "" };
+        arms.push_str(&format!(
+            "                {module}::SBE_TEMPLATE_ID => {name}(header),\n"
+        ));
+        functions.open(&format!(
+            "fn {name}(header: {header}::{}Decoder<ReadBuf<'_>>) -> SbeResult<Vec<u8>> {{",
+            type_name(&from.header.name)
+        ));
+        let decoder = format!("{module}::{ty}Decoder::default().try_header(header, 0)?;");
+        functions.push(&if reads {
+            format!("let {}decoder = {decoder}", mutable(reassigned))
+        } else {
+            decoder
+        });
+        functions.push(&format!(
+            "let {}length = target::{target_header}::ENCODED_LENGTH + target::{module}::SBE_BLOCK_LENGTH as usize;",
+            mutable(walks)
+        ));
+        if walks {
+            // The decoders are `Copy`: the walk leaves `decoder` at the start.
+            functions.open("{");
+            if reassigned {
+                functions.push("let mut decoder = decoder;");
+            }
+            upgraded_length(&mut functions, &target.body, "decoder", false)
+                .with_context(|| format!("Message {}", message.name))?;
+            functions.close("}");
+        }
+        functions.push(&format!(
+            "let mut buf = vec![0; length];\nlet encoder = target::{module}::{ty}Encoder::default()\n    .wrap(WriteBuf::new(&mut buf), target::{target_header}::ENCODED_LENGTH);\nlet mut header = encoder.header(0);"
+        ));
+        let version = upgraded_version(&message.body, &target.body, to.version);
+        if version < to.version {
+            functions.push(&format!("header.version({version});"));
+        }
+        functions.push(&if walks || fields().next().is_some() {
+            format!(
+                "let {}encoder = header.parent()?;",
+                mutable(reassigned || fields().next().is_some())
+            )
+        } else {
+            "header.parent()?;".to_owned()
+        });
+        upgraded_body(
+            &mut functions,
+            &message.body,
+            &target.body,
+            ("decoder", "encoder"),
+            false,
+            &mut enums,
+        )
+        .with_context(|| format!("Message {}", message.name))?;
+        functions.close("    Ok(buf)\n}\n");
+    }
+    let mut source = Source::default();
+    source.push(&format!(
+        r#"
+        use super::*;
+        use super::super::schema_{target} as target;
+
+        /// Re-encodes the message at the start of `buf` as the message of the
+        /// same name of schema {target}, version {version}, for code written against
+        /// those codecs. The fields this schema lacks are encoded as a decoder
+        /// reads them from a version without them, and the header carries the
+        /// newest version with none of them, so decoders see them as absent.
+        /// Enums are converted by variant name, failing with
+        /// `SbeErr::UnsupportedVariant` for variants schema {target} lacks.
+        pub fn upgrade(buf: &[u8]) -> SbeResult<Vec<u8>> {{
+            let header = {header}::{header_type}Decoder::default().try_wrap(ReadBuf::new(buf), 0)?;
+            match header.template_id() {{
+{arms}                template_id => Err(SbeErr::UnknownTemplateId {{ template_id }}),
+            }}
+        }}
+        "#,
+        target = to.id,
+        version = to.version,
+        header_type = type_name(&from.header.name),
+    ));
+    source.text.push_str(&functions.text);
+    for (function, (from, to)) in &enums {
+        source.text.push_str(&upgraded_enum(function, from, to));
+    }
+    Ok(source.text)
+}
+
+/// A raw string literal of `text`, with enough `#`s to hold any `"#` in it.
+fn raw_string(text: &str) -> String {
+    let mut hashes = 1;
+    while text.contains(&format!("\"{}", "#".repeat(hashes))) {
+        hashes += 1;
+    }
+    let hashes = "#".repeat(hashes);
+    format!("r{hashes}\"{text}\"{hashes}")
+}
+
+/// An enum of the schemas, to select the codecs of a message from the schema
+/// ID in its header.
+fn schema_enum(schemas: &[(&str, Schema)]) -> String {
+    let variants: Vec<String> = schemas
+        .iter()
+        .map(|(_, schema)| format!("Schema{}", schema.id))
+        .collect();
+    let arms = |value: &dyn Fn(&str, &Schema) -> String| -> String {
+        schemas
+            .iter()
+            .zip(&variants)
+            .map(|((xml, schema), variant)| {
+                format!("            Self::{variant} => {},\n", value(xml, schema))
+            })
+            .collect()
+    };
+    let constant = |name: &'static str| {
+        move |_: &str, schema: &Schema| format!("schema_{}::{name}", schema.id)
+    };
+    let from_id: String = schemas
+        .iter()
+        .zip(&variants)
+        .map(|((_, schema), variant)| {
+            format!("            {} => Some(Self::{variant}),\n", schema.id)
+        })
+        .collect();
+    format!(
+        r#"
+/// The schemas with codecs in this crate, one per schema ID. The codecs of a
+/// schema decode every version of its ID up to the one they were generated
+/// from, and newer versions with the fields added since skipped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SbeSchema {{
+{variants}}}
+
+impl SbeSchema {{
+    /// Every schema, the one whose codecs are re-exported at the crate root first.
+    pub const ALL: &'static [Self] = &[{all}];
+
+    /// The schema of the codecs decoding messages whose header has `schema_id`.
+    pub fn from_id(schema_id: u16) -> Option<Self> {{
+        match schema_id {{
+{from_id}            _ => None,
+        }}
+    }}
+
+    pub fn id(self) -> u16 {{
+        match self {{
+{id}        }}
+    }}
+
+    /// The version the codecs were generated from.
+    pub fn version(self) -> u16 {{
+        match self {{
+{version}        }}
+    }}
+
+    pub fn semantic_version(self) -> &'static str {{
+        match self {{
+{semantic_version}        }}
+    }}
+
+    /// The XML message schema the codecs were generated from.
+    pub fn xml(self) -> &'static str {{
+        match self {{
+{xml}        }}
+    }}
+
+    /// The message at the start of `buf`, of this schema, re-encoded through
+    /// the codecs of this schema and of the first one as the message of the
+    /// same name of the first schema, or `buf` itself for the first schema.
+    pub fn upgrade(self, buf: &[u8]) -> SbeResult<::std::borrow::Cow<'_, [u8]>> {{
+        match self {{
+{upgrade}        }}
+    }}
+}}
+"#,
+        variants = variants
+            .iter()
+            .map(|variant| format!("    {variant},\n"))
+            .collect::<String>(),
+        all = variants
+            .iter()
+            .map(|variant| format!("Self::{variant}"))
+            .collect::<Vec<_>>()
+            .join(", "),
+        id = arms(&constant("SBE_SCHEMA_ID")),
+        version = arms(&constant("SBE_SCHEMA_VERSION")),
+        semantic_version = arms(&constant("SBE_SEMANTIC_VERSION")),
+        xml = arms(&|xml, _| raw_string(xml)),
+        upgrade = arms(&|_, schema| if schema.id == schemas[0].1.id {
+            "Ok(::std::borrow::Cow::Borrowed(buf))".to_owned()
+        } else {
+            format!(
+                "schema_{}::upgrade::upgrade(buf).map(::std::borrow::Cow::Owned)",
+                schema.id
+            )
+        }),
+    )
+}
+
+/// Generates the codecs of each XML schema into a `schema_<id>` module, with
+/// the runtime they share, an `SbeSchema` enum selecting between them and the
+/// codecs of the first schema re-exported, as the source to `include!` at the
+/// root of a crate.
+///
+/// Only one version of each schema ID is accepted: the newest, whose codecs
+/// also decode the older ones.
+pub fn generate(xmls: &[&str]) -> anyhow::Result<String> {
+    let mut schemas: Vec<(&str, Schema)> = Vec::new();
+    for (index, xml) in xmls.iter().enumerate() {
+        let schema = Schema::parse(xml).with_context(|| format!("Schema {index}"))?;
+        if let Some((_, previous)) = schemas.iter().find(|(_, other)| other.id == schema.id) {
+            bail!(
+                "Schemas {}:{} and {}:{} share an ID; keep only the newest version",
+                previous.id,
+                previous.version,
+                schema.id,
+                schema.version
+            );
+        }
+        schemas.push((xml, schema));
+    }
+    let Some((_, default)) = schemas.first() else {
+        bail!("No schema to generate codecs for");
+    };
+
+    let mut source = Source::default();
+    source.push("use ::core::convert::TryInto;\n");
+    for (index, (_, schema)) in schemas.iter().enumerate() {
+        let context = || format!("Schema {}:{}", schema.id, schema.version);
+        let codecs = schema_codecs(schema).with_context(context)?;
+        source.open(&format!("pub mod schema_{} {{", schema.id));
+        source.push(&codecs);
+        if index > 0 {
+            let upgrade = upgrade_codec(schema, default).with_context(context)?;
+            source.open("\npub mod upgrade {");
+            source.push(&upgrade);
+            source.close("}");
+        }
+        source.close("}\n");
+    }
+    source.push(&format!("pub use schema_{}::*;", default.id));
+    source.text.push_str(&schema_enum(&schemas));
+    source.text.push('\n');
+    source.text.push_str(RUNTIME);
+    Ok(source.text)
+}
@@ -0,0 +1,62 @@
+//! Generates the Rust codecs of SBE XML message schemas, for crates to build
+//! from the schemas they vendor instead of checking in SbeTool's output.
+
+mod generator;
+pub mod naming;
+pub mod schema;
+pub mod walk;
+
+pub use generator::generate;
+
+use anyhow::Context;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Lists `first` then the XML schemas in `others`, sorted by path, for a build
+/// script generating code from them, and reruns it when they change or a schema
+/// is added to `others`.
+pub fn schemas(first: impl AsRef<Path>, others: impl AsRef<Path>) -> anyhow::Result<Vec<PathBuf>> {
+    let others = others.as_ref();
+    let mut paths = Vec::new();
+    for entry in
+        fs::read_dir(others).with_context(|| format!("Failed to list {}", others.display()))?
+    {
+        let path = entry
+            .with_context(|| format!("Failed to list {}", others.display()))?
+            .path();
+        if path.extension().is_some_and(|extension| extension == "xml") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    paths.insert(0, first.as_ref().to_path_buf());
+    println!("cargo:rerun-if-changed={}", others.display());
+    for path in &paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+    Ok(paths)
+}
+
+/// Generates the codecs of the schemas at `xmls` into `$OUT_DIR/<file>`, for a
+/// build script, and reruns it when a schema changes. The codecs of the first
+/// schema are re-exported at the root.
+pub fn build(xmls: &[impl AsRef<Path>], file: &str) -> anyhow::Result<()> {
+    let mut texts = Vec::with_capacity(xmls.len());
+    for xml in xmls {
+        let xml = xml.as_ref();
+        println!("cargo:rerun-if-changed={}", xml.display());
+        texts.push(
+            fs::read_to_string(xml).with_context(|| format!("Failed to read {}", xml.display()))?,
+        );
+    }
+    let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+    let source = generate(&texts).with_context(|| {
+        let paths: Vec<_> = xmls.iter().map(|xml| xml.as_ref().display()).collect();
+        format!("Failed to generate codecs from {paths:?}")
+    })?;
+    let out_dir = env::var_os("OUT_DIR").context("OUT_DIR is not set")?;
+    let out = Path::new(&out_dir).join(file);
+    fs::write(&out, source).with_context(|| format!("Failed to write {}", out.display()))
+}
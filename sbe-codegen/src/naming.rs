@@ -0,0 +1,66 @@
+//! The Rust names SbeTool gives to schema names, so that generated codecs keep
+//! the module, type and method names of the ones it generated.
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Lower,
+    Upper,
+    Digit,
+}
+
+fn class(c: char) -> Class {
+    if c.is_ascii_digit() {
+        Class::Digit
+    } else if c.is_uppercase() {
+        Class::Upper
+    } else {
+        Class::Lower
+    }
+}
+
+/// Splits camel case into words at digit boundaries, before an upper case
+/// letter ending a word of two or more letters, and after the upper case run
+/// of an acronym, e.g. `Ticker24hFullResponse` becomes
+/// `ticker_24_hf_ull_response`, as SbeTool names it.
+pub fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    let mut previous: Option<Class> = None;
+    let mut word_length = 0;
+    let mut previous_upper_starts_word = false;
+    for c in name.chars() {
+        let current = class(c);
+        let split = match (previous, current) {
+            (None, _) => false,
+            (Some(Class::Digit), Class::Digit) => false,
+            (Some(Class::Digit), _) | (Some(_), Class::Digit) => true,
+            (Some(Class::Lower), Class::Upper) => word_length > 1,
+            (Some(Class::Upper), Class::Lower) => !previous_upper_starts_word,
+            _ => false,
+        };
+        if split {
+            snake.push('_');
+            word_length = 0;
+        }
+        if current == Class::Upper {
+            previous_upper_starts_word = word_length == 0;
+        }
+        snake.extend(c.to_lowercase());
+        word_length += 1;
+        previous = Some(current);
+    }
+    snake
+}
+
+/// The name of a type generated for a schema type, message or group.
+pub fn type_name(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// The module holding the codecs of a message or composite.
+pub fn codec_module(name: &str) -> String {
+    format!("{}_codec", snake_case(name))
+}
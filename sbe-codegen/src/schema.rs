@@ -0,0 +1,594 @@
+use anyhow::{anyhow, bail, Context};
+use roxmltree::{Document, Node};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Primitive {
+    Char,
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Float,
+    Double,
+}
+
+impl Primitive {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "char" => Self::Char,
+            "int8" => Self::Int8,
+            "int16" => Self::Int16,
+            "int32" => Self::Int32,
+            "int64" => Self::Int64,
+            "uint8" => Self::UInt8,
+            "uint16" => Self::UInt16,
+            "uint32" => Self::UInt32,
+            "uint64" => Self::UInt64,
+            "float" => Self::Float,
+            "double" => Self::Double,
+            _ => return None,
+        })
+    }
+
+    pub fn size(self) -> usize {
+        match self {
+            Self::Char | Self::Int8 | Self::UInt8 => 1,
+            Self::Int16 | Self::UInt16 => 2,
+            Self::Int32 | Self::UInt32 | Self::Float => 4,
+            Self::Int64 | Self::UInt64 | Self::Double => 8,
+        }
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(
+            self,
+            Self::Int8 | Self::Int16 | Self::Int32 | Self::Int64 | Self::Float | Self::Double
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Presence {
+    Required,
+    /// The field holds its null value when absent.
+    Optional,
+    /// The field is not on the wire and always has the given value: the name
+    /// of an enum's valid value, or the literal value of a primitive.
+    Constant(String),
+}
+
+/// A primitive, or a fixed-length array of primitives when `length != 1`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EncodedType {
+    pub name: String,
+    pub primitive: Primitive,
+    pub length: usize,
+    pub presence: Presence,
+    /// Overrides the primitive's default null value.
+    pub null_value: Option<String>,
+    pub character_encoding: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnumType {
+    pub name: String,
+    pub encoding: Primitive,
+    /// Valid value names and their encodings.
+    pub values: Vec<(String, i64)>,
+}
+
+impl EnumType {
+    pub fn value_name(&self, value: i64) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(_, candidate)| *candidate == value)
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetType {
+    pub name: String,
+    pub encoding: Primitive,
+    /// Choice names and their bit positions.
+    pub choices: Vec<(String, u32)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub name: String,
+    pub offset: usize,
+    pub ty: Type,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompositeType {
+    pub name: String,
+    pub members: Vec<Member>,
+}
+
+impl CompositeType {
+    pub fn member(&self, name: &str) -> Option<&Member> {
+        self.members.iter().find(|member| member.name == name)
+    }
+
+    /// The offset and encoding of a primitive member, as needed for the
+    /// message header, group dimensions and var-data lengths.
+    pub fn primitive_member(&self, name: &str) -> anyhow::Result<(usize, Primitive)> {
+        match self.member(name) {
+            Some(Member {
+                offset,
+                ty: Type::Encoded(encoded),
+                ..
+            }) if encoded.length == 1 => Ok((*offset, encoded.primitive)),
+            _ => bail!("Composite {} has no primitive {name} member", self.name),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Type {
+    Encoded(EncodedType),
+    Enum(EnumType),
+    Set(SetType),
+    Composite(CompositeType),
+}
+
+impl Type {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Encoded(ty) => &ty.name,
+            Self::Enum(ty) => &ty.name,
+            Self::Set(ty) => &ty.name,
+            Self::Composite(ty) => &ty.name,
+        }
+    }
+
+    /// The number of bytes the type occupies on the wire.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Encoded(ty) if matches!(ty.presence, Presence::Constant(_)) => 0,
+            Self::Encoded(ty) => ty.primitive.size() * ty.length,
+            Self::Enum(ty) => ty.encoding.size(),
+            Self::Set(ty) => ty.encoding.size(),
+            Self::Composite(ty) => ty
+                .members
+                .iter()
+                .map(|member| member.offset + member.ty.size())
+                .max()
+                .unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub name: String,
+    pub id: u16,
+    /// The offset from the start of the enclosing block.
+    pub offset: usize,
+    pub ty: Type,
+    pub presence: Presence,
+    pub since_version: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Group {
+    pub name: String,
+    pub id: u16,
+    pub block_length: usize,
+    /// The composite holding `blockLength` and `numInGroup`.
+    pub dimension: CompositeType,
+    pub since_version: u16,
+    pub body: Body,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Data {
+    pub name: String,
+    pub id: u16,
+    /// The composite holding `length` and `varData`.
+    pub ty: CompositeType,
+    pub since_version: u16,
+}
+
+impl Data {
+    /// The character encoding of text fields; `None` for binary ones.
+    pub fn character_encoding(&self) -> Option<&str> {
+        match self.ty.member("varData") {
+            Some(Member {
+                ty: Type::Encoded(encoded),
+                ..
+            }) => encoded.character_encoding.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// The fields, repeating groups and var-data of a message or group, in wire order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Body {
+    pub fields: Vec<Field>,
+    pub groups: Vec<Group>,
+    pub data: Vec<Data>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    pub name: String,
+    pub id: u16,
+    pub block_length: usize,
+    pub since_version: u16,
+    pub body: Body,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ByteOrder {
+    #[default]
+    LittleEndian,
+    BigEndian,
+}
+
+/// An SBE message schema, resolved into the types of every message field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Schema {
+    pub package: String,
+    pub id: u16,
+    pub version: u16,
+    pub semantic_version: String,
+    pub byte_order: ByteOrder,
+    pub header: CompositeType,
+    types: BTreeMap<String, Type>,
+    messages: BTreeMap<u16, Message>,
+}
+
+impl Schema {
+    pub fn parse(xml: &str) -> anyhow::Result<Self> {
+        let document = Document::parse(xml)?;
+        let root = document.root_element();
+        if root.tag_name().name() != "messageSchema" {
+            bail!(
+                "Expected a messageSchema element; got {}",
+                root.tag_name().name()
+            );
+        }
+        let mut types = TypeResolver::default();
+        for node in elements(root).filter(|node| node.has_tag_name("types")) {
+            for node in elements(node) {
+                types
+                    .nodes
+                    .insert(attribute(node, "name")?.to_owned(), node);
+            }
+        }
+        let header = match types.resolve(root.attribute("headerType").unwrap_or("messageHeader"))? {
+            Type::Composite(header) => header,
+            header => bail!("Header type {} is not a composite", header.name()),
+        };
+        let mut messages = BTreeMap::new();
+        for node in elements(root).filter(|node| node.tag_name().name() == "message") {
+            let name = attribute(node, "name")?;
+            let message =
+                parse_message(node, &mut types).with_context(|| format!("Message {name}"))?;
+            if let Some(previous) = messages.insert(message.id, message) {
+                bail!(
+                    "Template ID {} is used by {} and {name}",
+                    previous.id,
+                    previous.name
+                );
+            }
+        }
+        let names: Vec<String> = types.nodes.keys().cloned().collect();
+        let types = names
+            .into_iter()
+            .map(|name| Ok((name.clone(), types.resolve(&name)?)))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            package: root.attribute("package").unwrap_or_default().to_owned(),
+            id: number(root, "id")?,
+            version: root
+                .attribute("version")
+                .map_or(Ok(0), |_| number(root, "version"))?,
+            semantic_version: root
+                .attribute("semanticVersion")
+                .unwrap_or_default()
+                .to_owned(),
+            byte_order: match root.attribute("byteOrder") {
+                None | Some("littleEndian") => ByteOrder::LittleEndian,
+                Some("bigEndian") => ByteOrder::BigEndian,
+                Some(byte_order) => bail!("Unknown byte order {byte_order:?}"),
+            },
+            header,
+            types,
+            messages,
+        })
+    }
+
+    pub fn message(&self, template_id: u16) -> Option<&Message> {
+        self.messages.get(&template_id)
+    }
+
+    pub fn message_named(&self, name: &str) -> Option<&Message> {
+        self.messages().find(|message| message.name == name)
+    }
+
+    /// Messages in template ID order.
+    pub fn messages(&self) -> impl Iterator<Item = &Message> {
+        self.messages.values()
+    }
+
+    pub fn type_named(&self, name: &str) -> Option<&Type> {
+        self.types.get(name)
+    }
+
+    /// Named types in name order.
+    pub fn types(&self) -> impl Iterator<Item = &Type> {
+        self.types.values()
+    }
+}
+
+fn elements<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(Node::is_element)
+}
+
+fn attribute<'a>(node: Node<'a, '_>, name: &str) -> anyhow::Result<&'a str> {
+    node.attribute(name).ok_or_else(|| {
+        anyhow!(
+            "Missing {name} attribute on {} element",
+            node.tag_name().name()
+        )
+    })
+}
+
+fn number<T: std::str::FromStr>(node: Node, name: &str) -> anyhow::Result<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let value = attribute(node, name)?;
+    value
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid {name} attribute {value:?}"))
+}
+
+fn since_version(node: Node) -> anyhow::Result<u16> {
+    match node.attribute("sinceVersion") {
+        Some(_) => number(node, "sinceVersion"),
+        None => Ok(0),
+    }
+}
+
+fn presence(node: Node) -> anyhow::Result<Option<Presence>> {
+    Ok(match node.attribute("presence") {
+        None => None,
+        Some("required") => Some(Presence::Required),
+        Some("optional") => Some(Presence::Optional),
+        Some("constant") => Some(Presence::Constant(
+            node.text().unwrap_or_default().trim().to_owned(),
+        )),
+        Some(presence) => bail!("Unknown presence {presence:?}"),
+    })
+}
+
+/// Resolves named types on demand, so that they may refer to types declared
+/// after them.
+#[derive(Default)]
+struct TypeResolver<'a, 'input> {
+    nodes: HashMap<String, Node<'a, 'input>>,
+    resolved: HashMap<String, Type>,
+}
+
+impl TypeResolver<'_, '_> {
+    fn resolve(&mut self, name: &str) -> anyhow::Result<Type> {
+        if let Some(ty) = self.resolved.get(name) {
+            return Ok(ty.clone());
+        }
+        let ty = match self.nodes.get(name) {
+            Some(&node) => self.parse(node).with_context(|| format!("Type {name}"))?,
+            None => match Primitive::from_name(name) {
+                Some(primitive) => Type::Encoded(EncodedType {
+                    name: name.to_owned(),
+                    primitive,
+                    length: 1,
+                    presence: Presence::Required,
+                    null_value: None,
+                    character_encoding: None,
+                }),
+                None => bail!("Unknown type {name}"),
+            },
+        };
+        self.resolved.insert(name.to_owned(), ty.clone());
+        Ok(ty)
+    }
+
+    /// The primitive encoding of an enum or set, which may name another type.
+    fn encoding(&mut self, node: Node) -> anyhow::Result<Primitive> {
+        match self.resolve(attribute(node, "encodingType")?)? {
+            Type::Encoded(encoded) if encoded.length == 1 => Ok(encoded.primitive),
+            ty => bail!("Invalid encoding type {}", ty.name()),
+        }
+    }
+
+    fn parse(&mut self, node: Node) -> anyhow::Result<Type> {
+        let name = attribute(node, "name")?.to_owned();
+        Ok(match node.tag_name().name() {
+            "type" => {
+                let primitive_type = attribute(node, "primitiveType")?;
+                Type::Encoded(EncodedType {
+                    name,
+                    primitive: Primitive::from_name(primitive_type)
+                        .ok_or_else(|| anyhow!("Unknown primitive type {primitive_type:?}"))?,
+                    length: match node.attribute("length") {
+                        Some(_) => number(node, "length")?,
+                        None => 1,
+                    },
+                    presence: presence(node)?.unwrap_or(Presence::Required),
+                    null_value: node.attribute("nullValue").map(str::to_owned),
+                    character_encoding: node.attribute("characterEncoding").map(str::to_owned),
+                })
+            }
+            "enum" => {
+                let encoding = self.encoding(node)?;
+                let values = elements(node)
+                    .map(|value| {
+                        let text = value.text().unwrap_or_default().trim();
+                        let encoded = match (encoding, text.as_bytes()) {
+                            (Primitive::Char, [byte]) => i64::from(*byte),
+                            _ => text
+                                .parse()
+                                .with_context(|| format!("Invalid valid value {text:?}"))?,
+                        };
+                        Ok((attribute(value, "name")?.to_owned(), encoded))
+                    })
+                    .collect::<anyhow::Result<_>>()?;
+                Type::Enum(EnumType {
+                    name,
+                    encoding,
+                    values,
+                })
+            }
+            "set" => {
+                let encoding = self.encoding(node)?;
+                let choices = elements(node)
+                    .map(|choice| {
+                        let text = choice.text().unwrap_or_default().trim();
+                        let bit = text
+                            .parse()
+                            .with_context(|| format!("Invalid choice {text:?}"))?;
+                        Ok((attribute(choice, "name")?.to_owned(), bit))
+                    })
+                    .collect::<anyhow::Result<_>>()?;
+                Type::Set(SetType {
+                    name,
+                    encoding,
+                    choices,
+                })
+            }
+            "composite" => {
+                let mut members = Vec::new();
+                let mut offset = 0;
+                for member in elements(node) {
+                    let ty = match member.tag_name().name() {
+                        "ref" => self.resolve(attribute(member, "type")?)?,
+                        _ => self.parse(member)?,
+                    };
+                    if member.attribute("offset").is_some() {
+                        offset = number(member, "offset")?;
+                    }
+                    let size = ty.size();
+                    members.push(Member {
+                        name: attribute(member, "name")?.to_owned(),
+                        offset,
+                        ty,
+                    });
+                    offset += size;
+                }
+                Type::Composite(CompositeType { name, members })
+            }
+            tag => bail!("Unknown type element {tag}"),
+        })
+    }
+
+    fn composite(&mut self, name: &str) -> anyhow::Result<CompositeType> {
+        match self.resolve(name)? {
+            Type::Composite(composite) => Ok(composite),
+            ty => bail!("Type {} is not a composite", ty.name()),
+        }
+    }
+}
+
+fn parse_message(node: Node, types: &mut TypeResolver) -> anyhow::Result<Message> {
+    let body = parse_body(node, types)?;
+    Ok(Message {
+        name: attribute(node, "name")?.to_owned(),
+        id: number(node, "id")?,
+        block_length: block_length(node, &body)?,
+        since_version: since_version(node)?,
+        body,
+    })
+}
+
+/// The declared block length, or else the end of the last fixed-size field.
+fn block_length(node: Node, body: &Body) -> anyhow::Result<usize> {
+    if node.attribute("blockLength").is_some() {
+        return number(node, "blockLength");
+    }
+    Ok(body
+        .fields
+        .iter()
+        .map(|field| field.offset + field.ty.size())
+        .max()
+        .unwrap_or(0))
+}
+
+fn parse_body(node: Node, types: &mut TypeResolver) -> anyhow::Result<Body> {
+    let mut body = Body::default();
+    let mut offset = 0;
+    for child in elements(node) {
+        let name = attribute(child, "name")?;
+        match child.tag_name().name() {
+            "field" => {
+                let ty = types.resolve(attribute(child, "type")?)?;
+                let mut presence = match presence(child)? {
+                    Some(presence) => presence,
+                    None => match &ty {
+                        Type::Encoded(encoded) => encoded.presence.clone(),
+                        _ => Presence::Required,
+                    },
+                };
+                if let (Presence::Constant(value), Some(value_ref)) =
+                    (&mut presence, child.attribute("valueRef"))
+                {
+                    // `valueRef` is `enumType.validValue`.
+                    *value = value_ref
+                        .rsplit_once('.')
+                        .map_or(value_ref, |(_, value)| value)
+                        .to_owned();
+                }
+                if child.attribute("offset").is_some() {
+                    offset = number(child, "offset")?;
+                }
+                let field_offset = offset;
+                if !matches!(presence, Presence::Constant(_)) {
+                    offset += ty.size();
+                }
+                body.fields.push(Field {
+                    name: name.to_owned(),
+                    id: number(child, "id")?,
+                    offset: field_offset,
+                    ty,
+                    presence,
+                    since_version: since_version(child)?,
+                });
+            }
+            "group" => {
+                let group_body =
+                    parse_body(child, types).with_context(|| format!("Group {name}"))?;
+                body.groups.push(Group {
+                    name: name.to_owned(),
+                    id: number(child, "id")?,
+                    block_length: block_length(child, &group_body)?,
+                    dimension: types.composite(
+                        child
+                            .attribute("dimensionType")
+                            .unwrap_or("groupSizeEncoding"),
+                    )?,
+                    since_version: since_version(child)?,
+                    body: group_body,
+                });
+            }
+            "data" => body.data.push(Data {
+                name: name.to_owned(),
+                id: number(child, "id")?,
+                ty: types.composite(attribute(child, "type")?)?,
+                since_version: since_version(child)?,
+            }),
+            tag => bail!("Unknown element {tag} in {name}"),
+        }
+    }
+    Ok(body)
+}
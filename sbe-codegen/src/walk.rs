@@ -0,0 +1,55 @@
+//! Emits code walking a decoded message through the checked `try_*` API, for
+//! the build scripts of the crates testing the generated decoders.
+
+use crate::{
+    naming::snake_case,
+    schema::{Body, Presence},
+};
+use std::fmt::Write;
+
+/// Emits, into `out`, statements reading every field, group count, group entry
+/// and var-data of `body` from the decoder bound to `decoder`, indented by
+/// `indent`. `read` turns the expression of each value read into the statement
+/// consuming it. The decoder is reassigned from its last group, so it must be
+/// bound mutably when `body` has groups.
+pub fn walk_body(
+    out: &mut String,
+    body: &Body,
+    decoder: &str,
+    indent: &str,
+    read: &dyn Fn(&str) -> String,
+) {
+    for field in &body.fields {
+        if matches!(field.presence, Presence::Constant(_)) {
+            continue;
+        }
+        let value = format!("{decoder}.{}()", snake_case(&field.name));
+        writeln!(out, "{indent}{}", read(&value)).unwrap();
+    }
+    for group in &body.groups {
+        let method = snake_case(&group.name);
+        let mut entries = String::new();
+        walk_body(&mut entries, &group.body, &method, indent, read);
+        writeln!(
+            out,
+            "{indent}let mut {method} = {decoder}.try_{method}_decoder()?;
+{indent}{count}
+{indent}for _ in 0..{method}.count() {{
+{indent}    {method}.try_advance()?;
+{entries}{indent}}}
+{indent}{decoder} = {method}.parent()?;",
+            count = read(&format!("{method}.count()"))
+        )
+        .unwrap();
+    }
+    for data in &body.data {
+        let method = snake_case(&data.name);
+        writeln!(
+            out,
+            "{indent}let coordinates = {decoder}.try_{method}_decoder()?;
+{indent}{}",
+            read(&format!("{decoder}.{method}_slice(coordinates)"))
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,89 @@
+//! Message lengths computed from the schema's layout of repeating groups and
+//! variable-length data, without decoding any field.
+
+use super::*;
+
+/// The width of a group's `numInGroup` or of a var-data length prefix.
+#[derive(Clone, Copy, Debug)]
+enum Width {
+    U8,
+    U16,
+    U32,
+}
+
+impl Width {
+    fn size(self) -> usize {
+        match self {
+            Self::U8 => 1,
+            Self::U16 => 2,
+            Self::U32 => 4,
+        }
+    }
+
+    fn read(self, buf: &ReadBuf<'_>, offset: usize) -> SbeResult<usize> {
+        Ok(match self {
+            Self::U8 => buf.try_get_u8_at(offset)? as usize,
+            Self::U16 => buf.try_get_u16_at(offset)? as usize,
+            Self::U32 => buf.try_get_u32_at(offset)? as usize,
+        })
+    }
+}
+
+/// What follows the fixed block of a message or group, in schema order.
+#[derive(Clone, Copy, Debug)]
+enum Part {
+    /// A repeating group, by the width of its `numInGroup`, then each entry's
+    /// block and parts.
+    Group(Width, &'static [Part]),
+    /// Variable-length data, by the width of its length prefix.
+    VarData(Width),
+}
+
+// {layout}
+
+fn skip_parts(buf: &ReadBuf<'_>, mut offset: usize, parts: &[Part]) -> SbeResult<usize> {
+    for part in parts {
+        offset = match *part {
+            Part::Group(count_width, entry_parts) => {
+                let block_length = buf.try_get_u16_at(offset)? as usize;
+                let count = count_width.read(buf, offset + 2)?;
+                offset += 2 + count_width.size();
+                if entry_parts.is_empty() {
+                    let length = block_length.checked_mul(count).unwrap_or(usize::MAX);
+                    buf.check_bounds(offset, length)?;
+                    offset + length
+                } else {
+                    for _ in 0..count {
+                        buf.check_bounds(offset, block_length)?;
+                        offset = skip_parts(buf, offset + block_length, entry_parts)?;
+                    }
+                    offset
+                }
+            }
+            Part::VarData(length_width) => {
+                let length = length_width.read(buf, offset)?;
+                offset += length_width.size();
+                buf.check_bounds(offset, length)?;
+                offset + length
+            }
+        };
+    }
+    Ok(offset)
+}
+
+/// The number of bytes taken by the message at the start of `buffer`, header
+/// included.
+///
+/// Only the message header, group dimensions and var-data lengths are read.
+/// Fails with `SbeErr::BufferTooShort` if the message does not fit in
+/// `buffer` and with `SbeErr::UnknownTemplateId` for templates outside the
+/// schema.
+pub fn message_length(buffer: &[u8]) -> SbeResult<usize> {
+    let buf = ReadBuf::new(buffer);
+    let header = message_header_codec::MessageHeaderDecoder::default().try_wrap(buf, 0)?;
+    let template_id = header.template_id();
+    let parts = layout(template_id).ok_or(SbeErr::UnknownTemplateId { template_id })?;
+    let offset = message_header_codec::ENCODED_LENGTH + header.block_length() as usize;
+    buf.check_bounds(0, offset)?;
+    skip_parts(&buf, offset, parts)
+}
@@ -0,0 +1,311 @@
+pub type SbeResult<T> = core::result::Result<T, SbeErr>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SbeErr {
+    ParentNotSet,
+    BufferTooShort {
+        offset: usize,
+        needed: usize,
+        available: usize,
+    },
+    UnknownTemplateId {
+        template_id: u16,
+    },
+    BlockLengthTooShort {
+        block_length: usize,
+        needed: usize,
+    },
+    UnsupportedVariant {
+        enumeration: &'static str,
+        variant: &'static str,
+    },
+}
+impl core::fmt::Display for SbeErr {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+impl std::error::Error for SbeErr {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+pub trait Writer<'a>: Sized {
+    fn get_buf_mut(&mut self) -> &mut WriteBuf<'a>;
+}
+
+pub trait Encoder<'a>: Writer<'a> {
+    fn get_limit(&self) -> usize;
+    fn set_limit(&mut self, limit: usize);
+}
+
+pub trait ActingVersion {
+    fn acting_version(&self) -> u16;
+}
+
+pub trait Reader<'a>: Sized {
+    fn get_buf(&self) -> &ReadBuf<'a>;
+}
+
+pub trait Decoder<'a>: Reader<'a> {
+    fn get_limit(&self) -> usize;
+    fn set_limit(&mut self, limit: usize);
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ReadBuf<'a> {
+    data: &'a [u8],
+}
+impl<'a> Reader<'a> for ReadBuf<'a> {
+    #[inline]
+    fn get_buf(&self) -> &ReadBuf<'a> {
+        self
+    }
+}
+#[allow(dead_code)]
+impl<'a> ReadBuf<'a> {
+    #[inline]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    #[inline]
+    pub(crate) fn get_bytes_at<const N: usize>(slice: &[u8], index: usize) -> [u8; N] {
+        slice[index..index + N]
+            .try_into()
+            .expect("slice with incorrect length")
+    }
+
+    #[inline]
+    pub(crate) fn try_get_bytes_at<const N: usize>(
+        slice: &[u8],
+        index: usize,
+    ) -> SbeResult<[u8; N]> {
+        match index.checked_add(N).and_then(|end| slice.get(index..end)) {
+            Some(bytes) => Ok(bytes.try_into().expect("slice with incorrect length")),
+            None => Err(SbeErr::BufferTooShort {
+                offset: index,
+                needed: N,
+                available: slice.len().saturating_sub(index),
+            }),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Fails with `SbeErr::BufferTooShort` unless `needed` bytes are available at `offset`.
+    #[inline]
+    pub fn check_bounds(&self, offset: usize, needed: usize) -> SbeResult<()> {
+        match offset.checked_add(needed) {
+            Some(end) if end <= self.data.len() => Ok(()),
+            _ => Err(SbeErr::BufferTooShort {
+                offset,
+                needed,
+                available: self.data.len().saturating_sub(offset),
+            }),
+        }
+    }
+
+    #[inline]
+    pub fn get_u8_at(&self, index: usize) -> u8 {
+        self.data[index]
+    }
+
+    #[inline]
+    pub fn get_i8_at(&self, index: usize) -> i8 {
+        i8::from_le_bytes(Self::get_bytes_at(self.data, index))
+    }
+
+    #[inline]
+    pub fn get_i16_at(&self, index: usize) -> i16 {
+        i16::from_le_bytes(Self::get_bytes_at(self.data, index))
+    }
+
+    #[inline]
+    pub fn get_i32_at(&self, index: usize) -> i32 {
+        i32::from_le_bytes(Self::get_bytes_at(self.data, index))
+    }
+
+    #[inline]
+    pub fn get_i64_at(&self, index: usize) -> i64 {
+        i64::from_le_bytes(Self::get_bytes_at(self.data, index))
+    }
+
+    #[inline]
+    pub fn get_u16_at(&self, index: usize) -> u16 {
+        u16::from_le_bytes(Self::get_bytes_at(self.data, index))
+    }
+
+    #[inline]
+    pub fn get_u32_at(&self, index: usize) -> u32 {
+        u32::from_le_bytes(Self::get_bytes_at(self.data, index))
+    }
+
+    #[inline]
+    pub fn get_u64_at(&self, index: usize) -> u64 {
+        u64::from_le_bytes(Self::get_bytes_at(self.data, index))
+    }
+
+    #[inline]
+    pub fn get_f32_at(&self, index: usize) -> f32 {
+        f32::from_le_bytes(Self::get_bytes_at(self.data, index))
+    }
+
+    #[inline]
+    pub fn get_f64_at(&self, index: usize) -> f64 {
+        f64::from_le_bytes(Self::get_bytes_at(self.data, index))
+    }
+
+    #[inline]
+    pub fn get_slice_at(&self, index: usize, len: usize) -> &[u8] {
+        &self.data[index..index + len]
+    }
+
+    #[inline]
+    pub fn try_get_u8_at(&self, index: usize) -> SbeResult<u8> {
+        Self::try_get_bytes_at(self.data, index).map(u8::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn try_get_i8_at(&self, index: usize) -> SbeResult<i8> {
+        Self::try_get_bytes_at(self.data, index).map(i8::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn try_get_i16_at(&self, index: usize) -> SbeResult<i16> {
+        Self::try_get_bytes_at(self.data, index).map(i16::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn try_get_i32_at(&self, index: usize) -> SbeResult<i32> {
+        Self::try_get_bytes_at(self.data, index).map(i32::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn try_get_i64_at(&self, index: usize) -> SbeResult<i64> {
+        Self::try_get_bytes_at(self.data, index).map(i64::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn try_get_u16_at(&self, index: usize) -> SbeResult<u16> {
+        Self::try_get_bytes_at(self.data, index).map(u16::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn try_get_u32_at(&self, index: usize) -> SbeResult<u32> {
+        Self::try_get_bytes_at(self.data, index).map(u32::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn try_get_u64_at(&self, index: usize) -> SbeResult<u64> {
+        Self::try_get_bytes_at(self.data, index).map(u64::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn try_get_f32_at(&self, index: usize) -> SbeResult<f32> {
+        Self::try_get_bytes_at(self.data, index).map(f32::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn try_get_f64_at(&self, index: usize) -> SbeResult<f64> {
+        Self::try_get_bytes_at(self.data, index).map(f64::from_le_bytes)
+    }
+
+    #[inline]
+    pub fn try_get_slice_at(&self, index: usize, len: usize) -> SbeResult<&[u8]> {
+        self.check_bounds(index, len)?;
+        Ok(&self.data[index..index + len])
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct WriteBuf<'a> {
+    data: &'a mut [u8],
+}
+impl<'a> WriteBuf<'a> {
+    pub fn new(data: &'a mut [u8]) -> Self {
+        Self { data }
+    }
+
+    #[inline]
+    pub fn put_bytes_at<const COUNT: usize>(&mut self, index: usize, bytes: &[u8; COUNT]) -> usize {
+        self.data[index..index + COUNT].copy_from_slice(bytes);
+        COUNT
+    }
+
+    #[inline]
+    pub fn put_u8_at(&mut self, index: usize, value: u8) {
+        self.data[index] = value;
+    }
+
+    #[inline]
+    pub fn put_i8_at(&mut self, index: usize, value: i8) {
+        self.put_bytes_at(index, &i8::to_le_bytes(value));
+    }
+
+    #[inline]
+    pub fn put_i16_at(&mut self, index: usize, value: i16) {
+        self.put_bytes_at(index, &i16::to_le_bytes(value));
+    }
+
+    #[inline]
+    pub fn put_i32_at(&mut self, index: usize, value: i32) {
+        self.put_bytes_at(index, &i32::to_le_bytes(value));
+    }
+
+    #[inline]
+    pub fn put_i64_at(&mut self, index: usize, value: i64) {
+        self.put_bytes_at(index, &i64::to_le_bytes(value));
+    }
+
+    #[inline]
+    pub fn put_u16_at(&mut self, index: usize, value: u16) {
+        self.put_bytes_at(index, &u16::to_le_bytes(value));
+    }
+
+    #[inline]
+    pub fn put_u32_at(&mut self, index: usize, value: u32) {
+        self.put_bytes_at(index, &u32::to_le_bytes(value));
+    }
+
+    #[inline]
+    pub fn put_u64_at(&mut self, index: usize, value: u64) {
+        self.put_bytes_at(index, &u64::to_le_bytes(value));
+    }
+
+    #[inline]
+    pub fn put_f32_at(&mut self, index: usize, value: f32) {
+        self.put_bytes_at(index, &f32::to_le_bytes(value));
+    }
+
+    #[inline]
+    pub fn put_f64_at(&mut self, index: usize, value: f64) {
+        self.put_bytes_at(index, &f64::to_le_bytes(value));
+    }
+
+    #[inline]
+    pub fn put_slice_at(&mut self, index: usize, src: &[u8]) -> usize {
+        let len = src.len();
+        let dest = self.data.split_at_mut(index).1.split_at_mut(len).0;
+        dest.clone_from_slice(src);
+        len
+    }
+}
+impl<'a> From<&'a mut WriteBuf<'a>> for &'a mut [u8] {
+    #[inline]
+    fn from(buf: &'a mut WriteBuf<'a>) -> &'a mut [u8] {
+        buf.data
+    }
+}
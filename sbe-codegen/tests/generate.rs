@@ -0,0 +1,147 @@
+use sbe_codegen::generate;
+
+fn schema(id: u16, version: u16, byte_order: &str) -> String {
+    schema_with(
+        id,
+        version,
+        byte_order,
+        r#"<field name="serverTime" id="1" type="int64"/>"#,
+    )
+}
+
+fn schema_with(id: u16, version: u16, byte_order: &str, fields: &str) -> String {
+    schema_with_types(id, version, byte_order, "", fields)
+}
+
+fn schema_with_types(id: u16, version: u16, byte_order: &str, types: &str, fields: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<sbe:messageSchema xmlns:sbe="http://fixprotocol.io/2016/sbe" package="test" id="{id}" version="{version}" semanticVersion="1.0" byteOrder="{byte_order}">
+    <types>
+        <composite name="messageHeader">
+            <type name="blockLength" primitiveType="uint16"/>
+            <type name="templateId" primitiveType="uint16"/>
+            <type name="schemaId" primitiveType="uint16"/>
+            <type name="version" primitiveType="uint16"/>
+        </composite>
+        {types}
+    </types>
+    <sbe:message name="PingResponse" id="1">
+        {fields}
+    </sbe:message>
+</sbe:messageSchema>
+"#
+    )
+}
+
+#[test]
+fn generates_a_module_per_schema_id() {
+    let latest = schema(3, 1, "littleEndian");
+    let previous = schema(2, 0, "littleEndian");
+    let source = generate(&[&latest, &previous]).unwrap();
+    assert!(source.contains("pub mod schema_3 {"));
+    assert!(source.contains("pub mod schema_2 {"));
+    assert!(source.contains("pub use schema_3::*;"));
+    assert!(source.contains("pub const ALL: &'static [Self] = &[Self::Schema3, Self::Schema2];"));
+    assert!(source.contains("2 => Some(Self::Schema2),"));
+    assert!(source.contains(&format!("Self::Schema2 => r#\"{previous}\"#,")));
+}
+
+#[test]
+fn upgrades_other_schemas_to_the_first() {
+    let latest = schema_with(
+        3,
+        1,
+        "littleEndian",
+        r#"<field name="serverTime" id="1" type="int64"/>
+        <field name="offset" id="2" type="int32" sinceVersion="1"/>"#,
+    );
+    let previous = schema(2, 0, "littleEndian");
+    let source = generate(&[&latest, &previous]).unwrap();
+    assert_eq!(source.matches("pub mod upgrade {").count(), 1);
+    assert!(source.contains("use super::super::schema_3 as target;"));
+    assert!(source.contains("ping_response_codec::SBE_TEMPLATE_ID => ping_response(header),"));
+    assert!(source.contains("encoder.server_time(decoder.server_time());"));
+    // Decoders read a field from versions before it as its null value.
+    assert!(source.contains("encoder.offset(-2147483648_i32);"));
+    // The message holds no more than version 0, which lacks the field.
+    assert!(source.contains("header.version(0);"));
+    assert!(source.contains("Self::Schema3 => Ok(::std::borrow::Cow::Borrowed(buf)),"));
+    assert!(source.contains(
+        "Self::Schema2 => schema_2::upgrade::upgrade(buf).map(::std::borrow::Cow::Owned),"
+    ));
+}
+
+#[test]
+fn upgrades_enums_by_variant_name() {
+    let side =
+        |values: &str| format!(r#"<enum name="OrderSide" encodingType="uint8">{values}</enum>"#);
+    let latest = schema_with_types(
+        3,
+        0,
+        "littleEndian",
+        &side(r#"<validValue name="Sell">0</validValue><validValue name="Buy">1</validValue>"#),
+        r#"<field name="side" id="1" type="OrderSide"/>"#,
+    );
+    let previous = schema_with_types(
+        2,
+        0,
+        "littleEndian",
+        &side(
+            r#"<validValue name="Buy">0</validValue><validValue name="Sell">1</validValue><validValue name="Short">2</validValue>"#,
+        ),
+        r#"<field name="side" id="1" type="OrderSide"/>"#,
+    );
+    let source = generate(&[&latest, &previous]).unwrap();
+    assert!(source.contains("encoder.side(upgrade_order_side(decoder.side())?);"));
+    assert!(
+        source.contains("order_side::OrderSide::Buy => Ok(target::order_side::OrderSide::Buy),")
+    );
+    assert!(
+        source.contains("order_side::OrderSide::Sell => Ok(target::order_side::OrderSide::Sell),")
+    );
+    assert!(source.contains("order_side::OrderSide::Short => Err(SbeErr::UnsupportedVariant {"));
+    assert!(source.contains(r#"variant: "Short","#));
+}
+
+#[test]
+fn rejects_upgrades_losing_fields() {
+    let latest = schema(3, 1, "littleEndian");
+    let changed = schema_with(
+        2,
+        0,
+        "littleEndian",
+        r#"<field name="serverTime" id="1" type="int32"/>"#,
+    );
+    let error = generate(&[&latest, &changed]).unwrap_err();
+    assert_eq!(
+        format!("{error:#}"),
+        "Schema 2:0: Message PingResponse: Field server_time changes type"
+    );
+    let missing = schema_with(2, 0, "littleEndian", "");
+    let error = generate(&[&latest, &missing]).unwrap_err();
+    assert_eq!(
+        format!("{error:#}"),
+        "Schema 2:0: Message PingResponse: Required field server_time is missing"
+    );
+}
+
+#[test]
+fn rejects_two_versions_of_a_schema_id() {
+    let latest = schema(3, 1, "littleEndian");
+    let previous = schema(3, 0, "littleEndian");
+    let error = generate(&[&latest, &previous]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Schemas 3:1 and 3:0 share an ID; keep only the newest version"
+    );
+}
+
+#[test]
+fn rejects_big_endian_schemas() {
+    let error = generate(&[&schema(3, 1, "bigEndian")]).unwrap_err();
+    assert_eq!(
+        format!("{error:#}"),
+        "Schema 3:1: Only little-endian schemas are supported"
+    );
+}
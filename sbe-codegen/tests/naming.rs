@@ -0,0 +1,37 @@
+use sbe_codegen::naming::{codec_module, snake_case, type_name};
+
+#[test]
+fn snake_case_splits_camel_case_words() {
+    assert_eq!(snake_case("orderId"), "order_id");
+    assert_eq!(
+        snake_case("ExchangeMaxNumAlgoOrdersFilter"),
+        "exchange_max_num_algo_orders_filter"
+    );
+}
+
+#[test]
+fn snake_case_splits_digits() {
+    assert_eq!(snake_case("groupSize16Encoding"), "group_size_16_encoding");
+    assert_eq!(snake_case("messageData8"), "message_data_8");
+    assert_eq!(snake_case("varString8"), "var_string_8");
+}
+
+#[test]
+fn snake_case_keeps_sbetool_quirks() {
+    assert_eq!(
+        snake_case("Ticker24hFullResponse"),
+        "ticker_24_hf_ull_response"
+    );
+    assert_eq!(
+        snake_case("Ticker24hSymbolMiniResponse"),
+        "ticker_24_hs_ymbol_mini_response"
+    );
+    assert_eq!(snake_case("TPlusSellFilter"), "tp_lus_sell_filter");
+}
+
+#[test]
+fn type_and_module_names() {
+    assert_eq!(type_name("boolEnum"), "BoolEnum");
+    assert_eq!(type_name("TPlusSellFilter"), "TPlusSellFilter");
+    assert_eq!(codec_module("messageHeader"), "message_header_codec");
+}
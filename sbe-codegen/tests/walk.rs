@@ -0,0 +1,55 @@
+use sbe_codegen::{schema::Schema, walk::walk_body};
+
+const SCHEMA: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<sbe:messageSchema xmlns:sbe="http://fixprotocol.io/2016/sbe" package="test" id="3" version="0" semanticVersion="1.0" byteOrder="littleEndian">
+    <types>
+        <composite name="messageHeader">
+            <type name="blockLength" primitiveType="uint16"/>
+            <type name="templateId" primitiveType="uint16"/>
+            <type name="schemaId" primitiveType="uint16"/>
+            <type name="version" primitiveType="uint16"/>
+        </composite>
+        <composite name="groupSize16Encoding">
+            <type name="blockLength" primitiveType="uint16"/>
+            <type name="numInGroup" primitiveType="uint16"/>
+        </composite>
+        <composite name="varString8">
+            <type name="length" primitiveType="uint8"/>
+            <type name="varData" primitiveType="uint8" length="0" characterEncoding="UTF-8"/>
+        </composite>
+        <type name="Int8" primitiveType="int8"/>
+    </types>
+    <sbe:message name="DepthResponse" id="1">
+        <field name="lastUpdateId" id="1" type="int64"/>
+        <field name="priceExponent" id="2" type="Int8" presence="constant">-8</field>
+        <group name="bids" id="100" dimensionType="groupSize16Encoding">
+            <field name="price" id="1" type="int64"/>
+        </group>
+        <data name="symbol" id="200" type="varString8"/>
+    </sbe:message>
+</sbe:messageSchema>
+"#;
+
+#[test]
+fn walks_fields_groups_and_var_data() {
+    let schema = Schema::parse(SCHEMA).unwrap();
+    let message = schema.messages().next().unwrap();
+    let mut out = String::new();
+    walk_body(&mut out, &message.body, "decoder", "  ", &|value| {
+        format!("read({value});")
+    });
+    assert_eq!(
+        out,
+        "  read(decoder.last_update_id());
+  let mut bids = decoder.try_bids_decoder()?;
+  read(bids.count());
+  for _ in 0..bids.count() {
+      bids.try_advance()?;
+  read(bids.price());
+  }
+  decoder = bids.parent()?;
+  let coordinates = decoder.try_symbol_decoder()?;
+  read(decoder.symbol_slice(coordinates));
+"
+    );
+}
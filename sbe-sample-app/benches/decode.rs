@@ -0,0 +1,616 @@
+//! Decode throughput of the SBE payloads the app handles most, in ns/message as
+//! timed by Criterion and in allocations/message as counted by the global
+//! allocator below, printed before each benchmark. Each payload is decoded by
+//! reading the fields in place (`zero_copy`), into the owned models (`owned`)
+//! and, as a baseline, from the equivalent JSON with serde_json (`json`).
+//!
+//! Run with `cargo bench -p sbe-sample-app`.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::execution_report;
+use criterion::{
+    black_box, criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion,
+    Throughput,
+};
+use sbe_sample_app::{
+    decimal::Decimal,
+    encode::{encode_depth, encode_execution_report},
+    exchange_info::decode_exchange_info,
+    market_data::{decode_depth, Depth, PriceLevel},
+    message::{decode_any, SpotMessage},
+    user_data::decode_execution_report,
+};
+use serde::{de, Deserialize, Deserializer};
+use spot_sbe::{
+    allowed_self_trade_prevention_modes::AllowedSelfTradePreventionModes,
+    bool_enum::BoolEnum,
+    exchange_info_response_codec::{
+        encoder::{
+            ExchangeFiltersEncoder, FiltersEncoder, PermissionSetsEncoder, PermissionsEncoder,
+            RateLimitsEncoder, SorsEncoder, SymbolsEncoder,
+        },
+        ExchangeInfoResponseEncoder,
+    },
+    lot_size_filter_codec::LotSizeFilterEncoder,
+    message_header_codec,
+    order_types::OrderTypes,
+    price_filter_codec::PriceFilterEncoder,
+    rate_limit_interval::RateLimitInterval,
+    rate_limit_type::RateLimitType,
+    self_trade_prevention_mode::SelfTradePreventionMode,
+    symbol_status::SymbolStatus,
+    Encoder, WriteBuf,
+};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The levels on each side of the depth benchmark, the most the API returns.
+const DEPTH_LEVELS: usize = 5000;
+
+/// The symbols of the exchangeInfo benchmark, about as many as Spot lists.
+const SYMBOLS: usize = 3000;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts allocations and reallocations, which both call into the system
+/// allocator.
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Prints the allocations `decode` makes for one message, then benchmarks it.
+fn bench<R>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    group_name: &str,
+    name: &str,
+    mut decode: impl FnMut() -> R,
+) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(decode());
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!("{group_name}/{name}: {allocations} allocations/message");
+    group.bench_function(name, |b| b.iter(&mut decode));
+}
+
+fn depth() -> Depth {
+    let level = |i: usize, base: i64, step: i64| PriceLevel {
+        price: Decimal::new(base + step * i as i64, -2),
+        qty: Decimal::new(100000 + 37 * i as i64, -8),
+    };
+    Depth {
+        last_update_id: 1027024,
+        bids: (0..DEPTH_LEVELS).map(|i| level(i, 3000000, -1)).collect(),
+        asks: (0..DEPTH_LEVELS).map(|i| level(i, 3000001, 1)).collect(),
+    }
+}
+
+/// Encodes a complete filter message, as nested in an exchangeInfo response.
+fn filter(encode: impl FnOnce(&mut [u8]) -> usize) -> Vec<u8> {
+    let mut buf = vec![0u8; 64];
+    let limit = encode(&mut buf);
+    buf.truncate(limit);
+    buf
+}
+
+fn price_filter() -> Vec<u8> {
+    filter(|buf| {
+        let encoder = PriceFilterEncoder::default()
+            .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH);
+        let mut header = encoder.header(0);
+        let mut encoder = header.parent().unwrap();
+        encoder.price_exponent(-8);
+        encoder.min_price(1);
+        encoder.max_price(100000000000000);
+        encoder.tick_size(1);
+        encoder.get_limit()
+    })
+}
+
+fn lot_size_filter() -> Vec<u8> {
+    filter(|buf| {
+        let encoder = LotSizeFilterEncoder::default()
+            .wrap(WriteBuf::new(buf), message_header_codec::ENCODED_LENGTH);
+        let mut header = encoder.header(0);
+        let mut encoder = header.parent().unwrap();
+        encoder.qty_exponent(-5);
+        encoder.min_qty(1);
+        encoder.max_qty(900000000);
+        encoder.step_size(1);
+        encoder.get_limit()
+    })
+}
+
+fn exchange_info() -> Vec<u8> {
+    let price_filter = price_filter();
+    let lot_size_filter = lot_size_filter();
+    let mut buf = vec![0u8; 1024 + SYMBOLS * 256];
+    let encoder = ExchangeInfoResponseEncoder::default().wrap(
+        WriteBuf::new(&mut buf),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let mut header = encoder.header(0);
+    let encoder = header.parent().unwrap();
+
+    let mut rate_limits = encoder.rate_limits_encoder(1, RateLimitsEncoder::default());
+    rate_limits.advance().unwrap();
+    rate_limits.rate_limit_type(RateLimitType::RequestWeight);
+    rate_limits.interval(RateLimitInterval::Minute);
+    rate_limits.interval_num(1);
+    rate_limits.rate_limit(6000);
+    let encoder = rate_limits.parent().unwrap();
+    let mut exchange_filters =
+        encoder.exchange_filters_encoder(0, ExchangeFiltersEncoder::default());
+    let encoder = exchange_filters.parent().unwrap();
+
+    let mut symbols = encoder.symbols_encoder(SYMBOLS as u32, SymbolsEncoder::default());
+    for i in 0..SYMBOLS {
+        symbols.advance().unwrap();
+        symbols.status(SymbolStatus::Trading);
+        symbols.base_asset_precision(8);
+        symbols.quote_asset_precision(8);
+        symbols.base_commission_precision(8);
+        symbols.quote_commission_precision(8);
+        let mut order_types = OrderTypes::default();
+        order_types.set_limit(true).set_market(true);
+        symbols.order_types(order_types);
+        symbols.iceberg_allowed(BoolEnum::True);
+        symbols.oco_allowed(BoolEnum::True);
+        symbols.oto_allowed(BoolEnum::True);
+        symbols.quote_order_qty_market_allowed(BoolEnum::True);
+        symbols.allow_trailing_stop(BoolEnum::True);
+        symbols.cancel_replace_allowed(BoolEnum::True);
+        symbols.amend_allowed(BoolEnum::True);
+        symbols.is_spot_trading_allowed(BoolEnum::True);
+        symbols.is_margin_trading_allowed(BoolEnum::False);
+        symbols.default_self_trade_prevention_mode(SelfTradePreventionMode::ExpireMaker);
+        let mut modes = AllowedSelfTradePreventionModes::default();
+        modes
+            .set_expire_taker(true)
+            .set_expire_maker(true)
+            .set_expire_both(true);
+        symbols.allowed_self_trade_prevention_modes(modes);
+        symbols.peg_instructions_allowed(BoolEnum::False);
+
+        let mut filters = symbols.filters_encoder(2, FiltersEncoder::default());
+        filters.advance().unwrap();
+        filters.filter(&price_filter);
+        filters.advance().unwrap();
+        filters.filter(&lot_size_filter);
+        symbols = filters.parent().unwrap();
+
+        let mut permission_sets =
+            symbols.permission_sets_encoder(1, PermissionSetsEncoder::default());
+        permission_sets.advance().unwrap();
+        let mut permissions = permission_sets.permissions_encoder(1, PermissionsEncoder::default());
+        permissions.advance().unwrap();
+        permissions.permission("SPOT");
+        let mut permission_sets = permissions.parent().unwrap();
+        symbols = permission_sets.parent().unwrap();
+
+        let base_asset = format!("A{i:04}");
+        symbols.symbol(&format!("{base_asset}USDT"));
+        symbols.base_asset(&base_asset);
+        symbols.quote_asset("USDT");
+    }
+    let encoder = symbols.parent().unwrap();
+    let mut sors = encoder.sors_encoder(0, SorsEncoder::default());
+    let encoder = sors.parent().unwrap();
+    let limit = encoder.get_limit();
+    buf.truncate(limit);
+    buf
+}
+
+/// A decimal the JSON API sends as a string, parsed as the owned models hold
+/// it.
+struct JsonDecimal(Decimal);
+
+impl<'de> Deserialize<'de> for JsonDecimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = JsonDecimal;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a decimal string")
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<JsonDecimal, E> {
+                s.parse().map(JsonDecimal).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
+
+/// The JSON API's executionReport event, with the strings the owned model
+/// holds as `String` owned, its decimals parsed and its enums borrowed by
+/// name. The fields are only read to be decoded.
+#[allow(dead_code)]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonExecutionReport<'a> {
+    subscription_id: Option<u16>,
+    event_time: i64,
+    transact_time: i64,
+    symbol: String,
+    client_order_id: String,
+    orig_client_order_id: String,
+    order_id: i64,
+    order_list_id: Option<i64>,
+    order_creation_time: Option<i64>,
+    working_time: Option<i64>,
+    #[serde(borrow)]
+    execution_type: Option<&'a str>,
+    #[serde(borrow)]
+    order_status: Option<&'a str>,
+    reject_reason: String,
+    #[serde(rename = "type", borrow)]
+    order_type: Option<&'a str>,
+    #[serde(borrow)]
+    side: Option<&'a str>,
+    #[serde(borrow)]
+    time_in_force: Option<&'a str>,
+    orig_qty: JsonDecimal,
+    price: JsonDecimal,
+    orig_quote_order_qty: JsonDecimal,
+    iceberg_qty: JsonDecimal,
+    stop_price: JsonDecimal,
+    trade_id: Option<i64>,
+    execution_id: i64,
+    executed_qty: JsonDecimal,
+    cummulative_quote_qty: JsonDecimal,
+    last_qty: JsonDecimal,
+    last_price: JsonDecimal,
+    quote_qty: JsonDecimal,
+    commission: JsonDecimal,
+    commission_asset: String,
+    is_working: bool,
+    is_maker: bool,
+    is_best_match: bool,
+    #[serde(borrow)]
+    match_type: Option<&'a str>,
+    #[serde(borrow)]
+    self_trade_prevention_mode: Option<&'a str>,
+    #[serde(borrow)]
+    order_capacity: Option<&'a str>,
+    #[serde(borrow)]
+    working_floor: Option<&'a str>,
+    used_sor: bool,
+    alloc_id: Option<i64>,
+    trailing_delta: Option<u64>,
+    trailing_time: Option<i64>,
+    trade_group_id: Option<i64>,
+    counter_symbol: String,
+    prevented_qty: JsonDecimal,
+    last_prevented_qty: Option<JsonDecimal>,
+    prevented_match_id: Option<i64>,
+    prevented_execution_qty: Option<JsonDecimal>,
+    prevented_execution_price: Option<JsonDecimal>,
+    prevented_execution_quote_qty: Option<JsonDecimal>,
+    strategy_type: Option<i32>,
+    strategy_id: Option<i64>,
+    counter_order_id: Option<i64>,
+    #[serde(borrow)]
+    peg_price_type: Option<&'a str>,
+    #[serde(borrow)]
+    peg_offset_type: Option<&'a str>,
+    peg_offset_value: Option<u8>,
+    pegged_price: Option<JsonDecimal>,
+    #[serde(borrow)]
+    expiry_reason: Option<&'a str>,
+}
+
+fn bench_execution_report(c: &mut Criterion) {
+    let report = execution_report();
+    let payload = encode_execution_report(&report).unwrap();
+    let json = serde_json::to_vec(&report).unwrap();
+
+    let mut group = c.benchmark_group("execution_report");
+    group.throughput(Throughput::Elements(1));
+    bench(&mut group, "execution_report", "zero_copy", || {
+        let Ok(SpotMessage::ExecutionReportEvent(mut decoder)) = decode_any(&payload) else {
+            panic!("Expected an ExecutionReportEvent");
+        };
+        black_box((
+            decoder.event_time(),
+            decoder.order_id(),
+            decoder.side(),
+            decoder.order_status(),
+            decoder.price(),
+            decoder.price_exponent(),
+            decoder.last_qty(),
+            decoder.last_price(),
+            decoder.executed_qty(),
+            decoder.qty_exponent(),
+        ));
+        let coordinates = decoder.try_symbol_decoder().unwrap();
+        black_box(decoder.symbol_slice(coordinates));
+    });
+    bench(&mut group, "execution_report", "owned", || {
+        let Ok(SpotMessage::ExecutionReportEvent(decoder)) = decode_any(&payload) else {
+            panic!("Expected an ExecutionReportEvent");
+        };
+        decode_execution_report(decoder).unwrap()
+    });
+    bench(&mut group, "execution_report", "json", || {
+        serde_json::from_slice::<JsonExecutionReport>(&json).unwrap()
+    });
+    group.finish();
+}
+
+/// The JSON API's depth response, read without copying its strings.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonDepth<'a> {
+    last_update_id: i64,
+    #[serde(borrow)]
+    bids: Vec<(&'a str, &'a str)>,
+    #[serde(borrow)]
+    asks: Vec<(&'a str, &'a str)>,
+}
+
+fn from_json_levels(levels: &[(&str, &str)]) -> Vec<PriceLevel> {
+    levels
+        .iter()
+        .map(|(price, qty)| PriceLevel {
+            price: price.parse().unwrap(),
+            qty: qty.parse().unwrap(),
+        })
+        .collect()
+}
+
+fn bench_depth(c: &mut Criterion) {
+    let depth = depth();
+    let payload = encode_depth(&depth).unwrap();
+    let json = serde_json::to_vec(&depth).unwrap();
+
+    let mut group = c.benchmark_group("depth_5000");
+    group.throughput(Throughput::Elements(1));
+    bench(&mut group, "depth_5000", "zero_copy", || {
+        let Ok(SpotMessage::DepthResponse(decoder)) = decode_any(&payload) else {
+            panic!("Expected a DepthResponse");
+        };
+        let mut bids = decoder.try_bids_decoder().unwrap();
+        for _ in 0..bids.count() {
+            bids.try_advance().unwrap();
+            black_box((bids.price(), bids.qty()));
+        }
+        let mut asks = bids.parent().unwrap().try_asks_decoder().unwrap();
+        for _ in 0..asks.count() {
+            asks.try_advance().unwrap();
+            black_box((asks.price(), asks.qty()));
+        }
+    });
+    bench(&mut group, "depth_5000", "owned", || {
+        let Ok(SpotMessage::DepthResponse(decoder)) = decode_any(&payload) else {
+            panic!("Expected a DepthResponse");
+        };
+        decode_depth(decoder).unwrap()
+    });
+    bench(&mut group, "depth_5000", "json", || {
+        let depth: JsonDepth = serde_json::from_slice(&json).unwrap();
+        Depth {
+            last_update_id: depth.last_update_id,
+            bids: from_json_levels(&depth.bids),
+            asks: from_json_levels(&depth.asks),
+        }
+    });
+    group.finish();
+}
+
+/// The JSON API's exchangeInfo response, typed as `JsonExecutionReport` is.
+#[allow(dead_code)]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonExchangeInfo<'a> {
+    #[serde(borrow)]
+    rate_limits: Vec<JsonRateLimit<'a>>,
+    exchange_filters: Vec<JsonExchangeFilter>,
+    #[serde(borrow)]
+    symbols: Vec<JsonSymbolInfo<'a>>,
+    sors: Vec<JsonSor>,
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonRateLimit<'a> {
+    #[serde(borrow)]
+    rate_limit_type: Option<&'a str>,
+    #[serde(borrow)]
+    interval: Option<&'a str>,
+    interval_num: u8,
+    limit: i64,
+    count: Option<i64>,
+}
+
+#[allow(dead_code, clippy::enum_variant_names)]
+#[derive(Deserialize)]
+#[serde(tag = "filterType", rename_all_fields = "camelCase")]
+enum JsonExchangeFilter {
+    #[serde(rename = "EXCHANGE_MAX_NUM_ORDERS")]
+    MaxNumOrders { max_num_orders: i64 },
+    #[serde(rename = "EXCHANGE_MAX_NUM_ALGO_ORDERS")]
+    MaxNumAlgoOrders { max_num_algo_orders: i64 },
+    #[serde(rename = "EXCHANGE_MAX_NUM_ICEBERG_ORDERS")]
+    MaxNumIcebergOrders { max_num_iceberg_orders: i64 },
+    #[serde(rename = "EXCHANGE_MAX_NUM_ORDER_LISTS")]
+    MaxNumOrderLists { max_num_order_lists: i64 },
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+#[serde(
+    tag = "filterType",
+    rename_all = "SCREAMING_SNAKE_CASE",
+    rename_all_fields = "camelCase"
+)]
+enum JsonSymbolFilter {
+    #[serde(rename = "PRICE_FILTER")]
+    Price {
+        min_price: JsonDecimal,
+        max_price: JsonDecimal,
+        tick_size: JsonDecimal,
+    },
+    PercentPrice {
+        multiplier_up: JsonDecimal,
+        multiplier_down: JsonDecimal,
+        avg_price_mins: i32,
+    },
+    PercentPriceBySide {
+        bid_multiplier_up: JsonDecimal,
+        bid_multiplier_down: JsonDecimal,
+        ask_multiplier_up: JsonDecimal,
+        ask_multiplier_down: JsonDecimal,
+        avg_price_mins: i32,
+    },
+    LotSize {
+        min_qty: JsonDecimal,
+        max_qty: JsonDecimal,
+        step_size: JsonDecimal,
+    },
+    MinNotional {
+        min_notional: JsonDecimal,
+        apply_to_market: bool,
+        avg_price_mins: i32,
+    },
+    Notional {
+        min_notional: JsonDecimal,
+        apply_min_to_market: bool,
+        max_notional: JsonDecimal,
+        apply_max_to_market: bool,
+        avg_price_mins: i32,
+    },
+    IcebergParts {
+        #[serde(rename = "limit")]
+        filter_limit: i64,
+    },
+    MarketLotSize {
+        min_qty: JsonDecimal,
+        max_qty: JsonDecimal,
+        step_size: JsonDecimal,
+    },
+    MaxNumOrders {
+        max_num_orders: i64,
+    },
+    MaxNumAlgoOrders {
+        max_num_algo_orders: i64,
+    },
+    MaxNumIcebergOrders {
+        max_num_iceberg_orders: i64,
+    },
+    MaxNumOrderLists {
+        max_num_order_lists: i64,
+    },
+    MaxNumOrderAmends {
+        max_num_order_amends: i64,
+    },
+    MaxPosition {
+        max_position: JsonDecimal,
+    },
+    TrailingDelta {
+        min_trailing_above_delta: i64,
+        max_trailing_above_delta: i64,
+        min_trailing_below_delta: i64,
+        max_trailing_below_delta: i64,
+    },
+    TPlusSell {
+        end_time: Option<i64>,
+    },
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSymbolInfo<'a> {
+    #[serde(borrow)]
+    status: Option<&'a str>,
+    base_asset_precision: u8,
+    quote_asset_precision: u8,
+    base_commission_precision: u8,
+    quote_commission_precision: u8,
+    #[serde(borrow)]
+    order_types: Vec<&'a str>,
+    iceberg_allowed: bool,
+    oco_allowed: bool,
+    oto_allowed: bool,
+    quote_order_qty_market_allowed: bool,
+    allow_trailing_stop: bool,
+    cancel_replace_allowed: bool,
+    amend_allowed: bool,
+    peg_instructions_allowed: Option<bool>,
+    is_spot_trading_allowed: bool,
+    is_margin_trading_allowed: bool,
+    #[serde(borrow)]
+    default_self_trade_prevention_mode: Option<&'a str>,
+    #[serde(borrow)]
+    allowed_self_trade_prevention_modes: Vec<&'a str>,
+    filters: Vec<JsonSymbolFilter>,
+    permission_sets: Vec<Vec<String>>,
+    symbol: String,
+    base_asset: String,
+    quote_asset: String,
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSor {
+    symbols: Vec<String>,
+    base_asset: String,
+}
+
+fn bench_exchange_info(c: &mut Criterion) {
+    let payload = exchange_info();
+    let Ok(SpotMessage::ExchangeInfoResponse(decoder)) = decode_any(&payload) else {
+        panic!("Expected an ExchangeInfoResponse");
+    };
+    let json = serde_json::to_vec(&decode_exchange_info(decoder).unwrap()).unwrap();
+
+    let mut group = c.benchmark_group("exchange_info");
+    group.throughput(Throughput::Elements(1));
+    // The JSON baseline takes tens of milliseconds per message.
+    group.sample_size(20);
+    bench(&mut group, "exchange_info", "owned", || {
+        let Ok(SpotMessage::ExchangeInfoResponse(decoder)) = decode_any(&payload) else {
+            panic!("Expected an ExchangeInfoResponse");
+        };
+        decode_exchange_info(decoder).unwrap()
+    });
+    bench(&mut group, "exchange_info", "json", || {
+        serde_json::from_slice::<JsonExchangeInfo>(&json).unwrap()
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_execution_report,
+    bench_depth,
+    bench_exchange_info
+);
+criterion_main!(benches);
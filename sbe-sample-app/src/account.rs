@@ -0,0 +1,427 @@
+use crate::{
+    decimal::Decimal,
+    decode::{into_bool, preallocated},
+    rate_limit::RateLimit,
+};
+use serde::{Serialize, Serializer};
+use spot_sbe::{
+    account_allocations_response_codec::AccountAllocationsResponseDecoder,
+    account_commission_response_codec::AccountCommissionResponseDecoder,
+    account_order_rate_limit_response_codec::AccountOrderRateLimitResponseDecoder,
+    account_prevented_matches_response_codec::AccountPreventedMatchesResponseDecoder,
+    account_response_codec::AccountResponseDecoder,
+    account_trades_response_codec::AccountTradesResponseDecoder, account_type::AccountType,
+    allocation_type::AllocationType,
+};
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommissionRates {
+    pub maker: Decimal,
+    pub taker: Decimal,
+    pub buyer: Decimal,
+    pub seller: Decimal,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Balance {
+    pub asset: String,
+    pub free: Decimal,
+    pub locked: Decimal,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    pub commission_rates: CommissionRates,
+    pub can_trade: bool,
+    pub can_withdraw: bool,
+    pub can_deposit: bool,
+    pub brokered: bool,
+    pub require_self_trade_prevention: bool,
+    pub prevent_sor: bool,
+    pub update_time: i64,
+    #[serde(serialize_with = "serialize_account_type")]
+    pub account_type: AccountType,
+    pub trade_group_id: Option<i64>,
+    pub uid: i64,
+    pub balances: Vec<Balance>,
+    pub permissions: Vec<String>,
+    pub reduce_only_assets: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountTrade {
+    pub symbol: String,
+    pub id: i64,
+    pub order_id: i64,
+    pub order_list_id: Option<i64>,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub quote_qty: Decimal,
+    pub commission: Decimal,
+    pub commission_asset: String,
+    pub time: i64,
+    pub is_buyer: bool,
+    pub is_maker: bool,
+    pub is_best_match: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Discount {
+    pub enabled_for_account: bool,
+    pub enabled_for_symbol: bool,
+    pub discount_asset: String,
+    pub discount: Decimal,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountCommission {
+    pub symbol: String,
+    pub standard_commission: CommissionRates,
+    /// Only present from schema version 1 onwards.
+    pub special_commission: Option<CommissionRates>,
+    pub tax_commission: CommissionRates,
+    pub discount: Discount,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreventedMatch {
+    pub symbol: String,
+    pub prevented_match_id: i64,
+    pub taker_order_id: i64,
+    pub maker_symbol: String,
+    pub maker_order_id: i64,
+    pub trade_group_id: i64,
+    pub price: Decimal,
+    pub taker_prevented_quantity: Option<Decimal>,
+    pub maker_prevented_quantity: Option<Decimal>,
+    pub transact_time: i64,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Allocation {
+    pub symbol: String,
+    pub allocation_id: i64,
+    #[serde(serialize_with = "serialize_allocation_type")]
+    pub allocation_type: AllocationType,
+    pub order_id: i64,
+    pub order_list_id: Option<i64>,
+    pub source_trade_id: Option<i64>,
+    pub source_allocation_id: Option<i64>,
+    pub source_symbol: String,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub quote_qty: Decimal,
+    pub commission: Option<Decimal>,
+    pub commission_asset: String,
+    pub time: i64,
+    pub is_buyer: bool,
+    pub is_maker: bool,
+    pub is_allocator: bool,
+}
+
+pub fn decode_account(decoder: AccountResponseDecoder<'_>) -> anyhow::Result<Account> {
+    let commission_exponent = decoder.commission_exponent();
+    let commission_rates = CommissionRates {
+        maker: Decimal::new(decoder.commission_rate_maker(), commission_exponent),
+        taker: Decimal::new(decoder.commission_rate_taker(), commission_exponent),
+        buyer: Decimal::new(decoder.commission_rate_buyer(), commission_exponent),
+        seller: Decimal::new(decoder.commission_rate_seller(), commission_exponent),
+    };
+    let can_trade = into_bool(decoder.can_trade())?;
+    let can_withdraw = into_bool(decoder.can_withdraw())?;
+    let can_deposit = into_bool(decoder.can_deposit())?;
+    let brokered = into_bool(decoder.brokered())?;
+    let require_self_trade_prevention = into_bool(decoder.require_self_trade_prevention())?;
+    let prevent_sor = into_bool(decoder.prevent_sor())?;
+    let update_time = decoder.update_time();
+    let account_type = decoder.account_type();
+    let trade_group_id = decoder.trade_group_id();
+    let uid = decoder.uid();
+
+    let mut decoder = decoder.try_balances_decoder()?;
+    let count = decoder.count();
+    let mut balances = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        let exponent = decoder.exponent();
+        let free = Decimal::new(decoder.free(), exponent);
+        let locked = Decimal::new(decoder.locked(), exponent);
+        let coordinates = decoder.try_asset_decoder()?;
+        let asset = String::from_utf8(decoder.asset_slice(coordinates).into())?;
+        balances.push(Balance {
+            asset,
+            free,
+            locked,
+        });
+    }
+    let decoder = decoder.parent()?;
+
+    let mut decoder = decoder.try_permissions_decoder()?;
+    let count = decoder.count();
+    let mut permissions = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        let coordinates = decoder.try_permission_decoder()?;
+        let slice = decoder.permission_slice(coordinates);
+        permissions.push(String::from_utf8(slice.into())?);
+    }
+    let decoder = decoder.parent()?;
+
+    let mut decoder = decoder.try_reduce_only_assets_decoder()?;
+    let count = decoder.count();
+    let mut reduce_only_assets = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        let coordinates = decoder.try_asset_decoder()?;
+        let slice = decoder.asset_slice(coordinates);
+        reduce_only_assets.push(String::from_utf8(slice.into())?);
+    }
+
+    Ok(Account {
+        commission_rates,
+        can_trade,
+        can_withdraw,
+        can_deposit,
+        brokered,
+        require_self_trade_prevention,
+        prevent_sor,
+        update_time,
+        account_type,
+        trade_group_id,
+        uid,
+        balances,
+        permissions,
+        reduce_only_assets,
+    })
+}
+
+pub fn decode_account_trades(
+    decoder: AccountTradesResponseDecoder<'_>,
+) -> anyhow::Result<Vec<AccountTrade>> {
+    let mut decoder = decoder.try_trades_decoder()?;
+    let count = decoder.count();
+    let mut trades = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        let price_exponent = decoder.price_exponent();
+        let qty_exponent = decoder.qty_exponent();
+        let commission_exponent = decoder.commission_exponent();
+        trades.push(AccountTrade {
+            id: decoder.id(),
+            order_id: decoder.order_id(),
+            order_list_id: decoder.order_list_id(),
+            price: Decimal::new(decoder.price(), price_exponent),
+            qty: Decimal::new(decoder.qty(), qty_exponent),
+            quote_qty: Decimal::new(decoder.quote_qty(), price_exponent),
+            commission: Decimal::new(decoder.commission(), commission_exponent),
+            time: decoder.time(),
+            is_buyer: into_bool(decoder.is_buyer())?,
+            is_maker: into_bool(decoder.is_maker())?,
+            is_best_match: into_bool(decoder.is_best_match())?,
+            symbol: {
+                let coordinates = decoder.try_symbol_decoder()?;
+                let slice = decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            commission_asset: {
+                let coordinates = decoder.try_commission_asset_decoder()?;
+                let slice = decoder.commission_asset_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        });
+    }
+    Ok(trades)
+}
+
+pub fn decode_account_commission(
+    mut decoder: AccountCommissionResponseDecoder<'_>,
+) -> anyhow::Result<AccountCommission> {
+    let commission_exponent = decoder.commission_exponent();
+    let commission = |mantissa: i64| Decimal::new(mantissa, commission_exponent);
+    let standard_commission = CommissionRates {
+        maker: commission(decoder.standard_commission_maker()),
+        taker: commission(decoder.standard_commission_taker()),
+        buyer: commission(decoder.standard_commission_buyer()),
+        seller: commission(decoder.standard_commission_seller()),
+    };
+    let special_commission = match (
+        decoder.special_commission_maker(),
+        decoder.special_commission_taker(),
+        decoder.special_commission_buyer(),
+        decoder.special_commission_seller(),
+    ) {
+        (Some(maker), Some(taker), Some(buyer), Some(seller)) => Some(CommissionRates {
+            maker: commission(maker),
+            taker: commission(taker),
+            buyer: commission(buyer),
+            seller: commission(seller),
+        }),
+        _ => None,
+    };
+    let tax_commission = CommissionRates {
+        maker: commission(decoder.tax_commission_maker()),
+        taker: commission(decoder.tax_commission_taker()),
+        buyer: commission(decoder.tax_commission_buyer()),
+        seller: commission(decoder.tax_commission_seller()),
+    };
+    let enabled_for_account = into_bool(decoder.discount_enabled_for_account())?;
+    let enabled_for_symbol = into_bool(decoder.discount_enabled_for_symbol())?;
+    let discount = Decimal::new(decoder.discount(), decoder.discount_exponent());
+
+    let coordinates = decoder.try_symbol_decoder()?;
+    let symbol = String::from_utf8(decoder.symbol_slice(coordinates).into())?;
+    let coordinates = decoder.try_discount_asset_decoder()?;
+    let discount_asset = String::from_utf8(decoder.discount_asset_slice(coordinates).into())?;
+
+    Ok(AccountCommission {
+        symbol,
+        standard_commission,
+        special_commission,
+        tax_commission,
+        discount: Discount {
+            enabled_for_account,
+            enabled_for_symbol,
+            discount_asset,
+            discount,
+        },
+    })
+}
+
+pub fn decode_account_order_rate_limits(
+    decoder: AccountOrderRateLimitResponseDecoder<'_>,
+) -> anyhow::Result<Vec<RateLimit>> {
+    let mut decoder = decoder.try_rate_limits_decoder()?;
+    let count = decoder.count();
+    let mut rate_limits = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        rate_limits.push(RateLimit {
+            rate_limit_type: decoder.rate_limit_type(),
+            interval: decoder.interval(),
+            interval_num: decoder.interval_num(),
+            limit: decoder.rate_limit(),
+            count: Some(decoder.num_orders()),
+        });
+    }
+    Ok(rate_limits)
+}
+
+pub fn decode_account_prevented_matches(
+    decoder: AccountPreventedMatchesResponseDecoder<'_>,
+) -> anyhow::Result<Vec<PreventedMatch>> {
+    let mut decoder = decoder.try_prevented_matches_decoder()?;
+    let count = decoder.count();
+    let mut prevented_matches = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        let price_exponent = decoder.price_exponent();
+        let qty_exponent = decoder.qty_exponent();
+        let qty = |mantissa: i64| Decimal::new(mantissa, qty_exponent);
+        prevented_matches.push(PreventedMatch {
+            prevented_match_id: decoder.prevented_match_id(),
+            taker_order_id: decoder.taker_order_id(),
+            maker_order_id: decoder.maker_order_id(),
+            trade_group_id: decoder.trade_group_id(),
+            price: Decimal::new(decoder.price(), price_exponent),
+            taker_prevented_quantity: decoder.taker_prevented_quantity().map(qty),
+            maker_prevented_quantity: decoder.maker_prevented_quantity().map(qty),
+            transact_time: decoder.transact_time(),
+            symbol: {
+                let coordinates = decoder.try_symbol_decoder()?;
+                let slice = decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            maker_symbol: {
+                let coordinates = decoder.try_maker_symbol_decoder()?;
+                let slice = decoder.maker_symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        });
+    }
+    Ok(prevented_matches)
+}
+
+pub fn decode_account_allocations(
+    decoder: AccountAllocationsResponseDecoder<'_>,
+) -> anyhow::Result<Vec<Allocation>> {
+    let mut decoder = decoder.try_allocations_decoder()?;
+    let count = decoder.count();
+    let mut allocations = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        let price_exponent = decoder.price_exponent();
+        let qty_exponent = decoder.qty_exponent();
+        let commission = match (decoder.commission(), decoder.commission_exponent()) {
+            (Some(mantissa), Some(exponent)) => Some(Decimal::new(mantissa, exponent)),
+            _ => None,
+        };
+        allocations.push(Allocation {
+            allocation_id: decoder.allocation_id(),
+            allocation_type: decoder.allocation_type(),
+            order_id: decoder.order_id(),
+            order_list_id: decoder.order_list_id(),
+            source_trade_id: decoder.source_trade_id(),
+            source_allocation_id: decoder.source_allocation_id(),
+            price: Decimal::new(decoder.price(), price_exponent),
+            qty: Decimal::new(decoder.qty(), qty_exponent),
+            quote_qty: Decimal::new(decoder.quote_qty(), price_exponent),
+            commission,
+            time: decoder.time(),
+            is_buyer: into_bool(decoder.is_buyer())?,
+            is_maker: into_bool(decoder.is_maker())?,
+            is_allocator: into_bool(decoder.is_allocator())?,
+            symbol: {
+                let coordinates = decoder.try_symbol_decoder()?;
+                let slice = decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            commission_asset: {
+                let coordinates = decoder.try_commission_asset_decoder()?;
+                let slice = decoder.commission_asset_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            source_symbol: {
+                let coordinates = decoder.try_source_symbol_decoder()?;
+                let slice = decoder.source_symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        });
+    }
+    Ok(allocations)
+}
+
+fn serialize_account_type<S: Serializer>(
+    val: &AccountType,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use AccountType::*;
+    let str_val = match val {
+        Spot => "SPOT",
+        Unknown => "UNKNOWN",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+fn serialize_allocation_type<S: Serializer>(
+    val: &AllocationType,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use AllocationType::*;
+    let str_val = match val {
+        Unknown => "UNKNOWN",
+        Sor => "SOR",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
@@ -0,0 +1,273 @@
+use crate::{
+    account::Account,
+    decimal::Decimal,
+    user_data::{BalanceUpdate, ExternalLockUpdate, OutboundAccountPosition, UserDataEvent},
+};
+use std::{collections::BTreeMap, fmt};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AssetBalance {
+    pub free: Decimal,
+    pub locked: Decimal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BalanceChangeCause {
+    OutboundAccountPosition,
+    BalanceUpdate,
+    ExternalLockUpdate,
+}
+
+/// An asset's balance after an event changed it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BalanceChange {
+    pub event_time: i64,
+    pub cause: BalanceChangeCause,
+    pub asset: String,
+    pub balance: AssetBalance,
+}
+
+/// An event older than one already applied. The tracker should be seeded again
+/// from a fresh `account` response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutOfOrderEvent {
+    pub last_event_time: i64,
+    pub event_time: i64,
+}
+
+impl fmt::Display for OutOfOrderEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Out of order account event: event time {} is before {}",
+            self.event_time, self.last_event_time
+        )
+    }
+}
+
+impl std::error::Error for OutOfOrderEvent {}
+
+/// A delta that takes a balance out of the range of [`Decimal`], such as one
+/// whose exponent is far from the balance's.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BalanceOverflow {
+    pub event_time: i64,
+    pub asset: String,
+}
+
+impl fmt::Display for BalanceOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Balance overflow applying the {} event at {}",
+            self.asset, self.event_time
+        )
+    }
+}
+
+impl std::error::Error for BalanceOverflow {}
+
+/// An account event the tracker could not apply.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccountEventError {
+    OutOfOrder(OutOfOrderEvent),
+    Overflow(BalanceOverflow),
+}
+
+impl fmt::Display for AccountEventError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfOrder(error) => error.fmt(f),
+            Self::Overflow(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for AccountEventError {}
+
+impl From<OutOfOrderEvent> for AccountEventError {
+    fn from(error: OutOfOrderEvent) -> Self {
+        Self::OutOfOrder(error)
+    }
+}
+
+impl From<BalanceOverflow> for AccountEventError {
+    fn from(error: BalanceOverflow) -> Self {
+        Self::Overflow(error)
+    }
+}
+
+/// Per-asset balances, seeded from an `account` response and kept current with
+/// user data stream events.
+#[derive(Clone, Debug)]
+pub struct AccountTracker {
+    balances: BTreeMap<String, AssetBalance>,
+    /// The time of the last account update reflected in the balances.
+    update_time: i64,
+    /// The time of the last event that changed the balances.
+    last_event_time: i64,
+    history: Vec<BalanceChange>,
+}
+
+impl AccountTracker {
+    pub fn from_account(account: &Account) -> Self {
+        let balances = account
+            .balances
+            .iter()
+            .map(|balance| {
+                let asset_balance = AssetBalance {
+                    free: balance.free,
+                    locked: balance.locked,
+                };
+                (balance.asset.clone(), asset_balance)
+            })
+            .collect();
+        Self {
+            balances,
+            update_time: account.update_time,
+            last_event_time: i64::MIN,
+            history: Vec::new(),
+        }
+    }
+
+    /// Zero for assets the account has never held.
+    pub fn balance(&self, asset: &str) -> AssetBalance {
+        self.balances.get(asset).copied().unwrap_or_default()
+    }
+
+    pub fn balances(&self) -> impl Iterator<Item = (&str, AssetBalance)> {
+        self.balances
+            .iter()
+            .map(|(asset, balance)| (asset.as_str(), *balance))
+    }
+
+    pub fn update_time(&self) -> i64 {
+        self.update_time
+    }
+
+    /// Every balance change applied since the tracker was seeded, oldest first.
+    pub fn history(&self) -> &[BalanceChange] {
+        &self.history
+    }
+
+    fn check_order(&self, event_time: i64) -> Result<(), OutOfOrderEvent> {
+        if event_time < self.last_event_time {
+            return Err(OutOfOrderEvent {
+                last_event_time: self.last_event_time,
+                event_time,
+            });
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, event_time: i64, cause: BalanceChangeCause, asset: &str) {
+        let balance = self.balance(asset);
+        self.history.push(BalanceChange {
+            event_time,
+            cause,
+            asset: asset.to_owned(),
+            balance,
+        });
+    }
+
+    /// Applies the account events among user data stream events; other events
+    /// are ignored.
+    ///
+    /// Returns `Ok(false)` for events already reflected in the balances.
+    /// Events that would overflow a balance leave it unchanged, and are not
+    /// considered when ordering later events.
+    pub fn apply(&mut self, event: &UserDataEvent) -> Result<bool, AccountEventError> {
+        match event {
+            UserDataEvent::OutboundAccountPosition(event) => Ok(self.apply_position(event)?),
+            UserDataEvent::BalanceUpdate(event) => self.apply_balance_update(event),
+            UserDataEvent::ExternalLockUpdate(event) => self.apply_external_lock_update(event),
+            _ => Ok(false),
+        }
+    }
+
+    /// Sets the balances of the assets in the event.
+    pub fn apply_position(
+        &mut self,
+        event: &OutboundAccountPosition,
+    ) -> Result<bool, OutOfOrderEvent> {
+        self.check_order(event.event_time)?;
+        if event.update_time < self.update_time {
+            return Ok(false);
+        }
+        for position in &event.balances {
+            let balance = AssetBalance {
+                free: position.free,
+                locked: position.locked,
+            };
+            self.balances.insert(position.asset.clone(), balance);
+            self.record(
+                event.event_time,
+                BalanceChangeCause::OutboundAccountPosition,
+                &position.asset,
+            );
+        }
+        self.update_time = event.update_time;
+        self.last_event_time = event.event_time;
+        Ok(true)
+    }
+
+    fn overflow(event_time: i64, asset: &str) -> BalanceOverflow {
+        BalanceOverflow {
+            event_time,
+            asset: asset.to_owned(),
+        }
+    }
+
+    /// Adds the delta to the free balance, as for deposits and withdrawals.
+    pub fn apply_balance_update(
+        &mut self,
+        event: &BalanceUpdate,
+    ) -> Result<bool, AccountEventError> {
+        self.check_order(event.event_time)?;
+        if event.event_time <= self.update_time {
+            return Ok(false);
+        }
+        let mut balance = self.balance(&event.asset);
+        balance.free = balance
+            .free
+            .checked_add(event.free_qty_delta)
+            .ok_or_else(|| Self::overflow(event.event_time, &event.asset))?;
+        self.balances.insert(event.asset.clone(), balance);
+        self.record(
+            event.event_time,
+            BalanceChangeCause::BalanceUpdate,
+            &event.asset,
+        );
+        self.last_event_time = event.event_time;
+        Ok(true)
+    }
+
+    /// Moves the delta from the free to the locked balance.
+    pub fn apply_external_lock_update(
+        &mut self,
+        event: &ExternalLockUpdate,
+    ) -> Result<bool, AccountEventError> {
+        self.check_order(event.event_time)?;
+        if event.event_time <= self.update_time {
+            return Ok(false);
+        }
+        let mut balance = self.balance(&event.asset);
+        let overflow = || Self::overflow(event.event_time, &event.asset);
+        balance.locked = balance
+            .locked
+            .checked_add(event.locked_qty_delta)
+            .ok_or_else(overflow)?;
+        balance.free = balance
+            .free
+            .checked_sub(event.locked_qty_delta)
+            .ok_or_else(overflow)?;
+        self.balances.insert(event.asset.clone(), balance);
+        self.record(
+            event.event_time,
+            BalanceChangeCause::ExternalLockUpdate,
+            &event.asset,
+        );
+        self.last_event_time = event.event_time;
+        Ok(true)
+    }
+}
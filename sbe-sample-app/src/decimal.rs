@@ -0,0 +1,389 @@
+use anyhow::{anyhow, bail};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Add, Div, Mul, Neg, Rem, Sub},
+    str::FromStr,
+};
+
+/// An exact fixed-point decimal, `mantissa * 10^exponent`, as carried by SBE
+/// price and quantity fields.
+///
+/// Equality, ordering and hashing compare numeric values, so `1.0 == 1.00`.
+/// Arithmetic is exact and panics on overflow like the integer operators; use
+/// the `checked_*` methods to handle overflow instead.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Decimal {
+    mantissa: i128,
+    exponent: i8,
+}
+
+/// How [`Decimal`] values are serialized.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DecimalFormat {
+    /// A string such as `"0.00100000"`, as the JSON API renders decimals.
+    #[default]
+    String,
+    /// A `{mantissa, exponent}` pair, as carried on the wire.
+    MantissaExponent,
+}
+
+impl FromStr for DecimalFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Ok(match s {
+            "string" => Self::String,
+            "mantissa-exponent" => Self::MantissaExponent,
+            _ => bail!("Unknown decimal format {s:?}; expected string or mantissa-exponent"),
+        })
+    }
+}
+
+thread_local! {
+    // Serde offers no way to thread options through `Serialize`, so the format
+    // is set around each serialization by `with_decimal_format`.
+    static DECIMAL_FORMAT: Cell<DecimalFormat> = Cell::new(DecimalFormat::String);
+}
+
+/// Runs `serialize` with [`Decimal`] values serialized in `format` on this
+/// thread, restoring the previous format afterwards. Prefer passing the format
+/// to [`OutputFormat::render`](crate::output::OutputFormat::render).
+pub fn with_decimal_format<R>(format: DecimalFormat, serialize: impl FnOnce() -> R) -> R {
+    struct Restore(DecimalFormat);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            DECIMAL_FORMAT.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(DECIMAL_FORMAT.with(|current| current.replace(format)));
+    serialize()
+}
+
+fn pow10(exponent: u32) -> Option<i128> {
+    10i128.checked_pow(exponent)
+}
+
+/// Multiplies `mantissa` by `10^by`.
+fn scale_up(mantissa: i128, by: u32) -> Option<i128> {
+    pow10(by).and_then(|factor| mantissa.checked_mul(factor))
+}
+
+impl Decimal {
+    pub const ZERO: Decimal = Decimal {
+        mantissa: 0,
+        exponent: 0,
+    };
+
+    pub fn new(mantissa: i64, exponent: i8) -> Self {
+        Self {
+            mantissa: mantissa.into(),
+            exponent,
+        }
+    }
+
+    pub const fn from_parts(mantissa: i128, exponent: i8) -> Self {
+        Self { mantissa, exponent }
+    }
+
+    /// Builds a decimal from a 128-bit little-endian mantissa, as used by volume fields.
+    pub fn from_le_bytes(mantissa: [u8; 16], exponent: i8) -> Self {
+        Self {
+            mantissa: i128::from_le_bytes(mantissa),
+            exponent,
+        }
+    }
+
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    pub fn exponent(&self) -> i8 {
+        self.exponent
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.mantissa < 0
+    }
+
+    /// Re-expresses the value with the given exponent, truncating towards zero
+    /// when digits are dropped. Returns `None` if the mantissa overflows.
+    pub fn rescale(&self, exponent: i8) -> Option<Self> {
+        let mantissa = match exponent.cmp(&self.exponent) {
+            Ordering::Equal => self.mantissa,
+            Ordering::Less => scale_up(self.mantissa, self.exponent.abs_diff(exponent).into())?,
+            Ordering::Greater => match pow10(self.exponent.abs_diff(exponent).into()) {
+                Some(factor) => self.mantissa / factor,
+                // The divisor exceeds any mantissa.
+                None => 0,
+            },
+        };
+        Some(Self { mantissa, exponent })
+    }
+
+    /// The same value with trailing zeros removed from the mantissa.
+    pub fn normalize(&self) -> Self {
+        if self.mantissa == 0 {
+            return Self::ZERO;
+        }
+        let mut normalized = *self;
+        while normalized.mantissa % 10 == 0 && normalized.exponent < i8::MAX {
+            normalized.mantissa /= 10;
+            normalized.exponent += 1;
+        }
+        normalized
+    }
+
+    /// Both mantissas expressed with the smaller of the two exponents.
+    fn aligned(&self, other: &Self) -> Option<(i128, i128, i8)> {
+        let exponent = self.exponent.min(other.exponent);
+        let lhs = self.rescale(exponent)?.mantissa;
+        let rhs = other.rescale(exponent)?.mantissa;
+        Some((lhs, rhs, exponent))
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let (lhs, rhs, exponent) = self.aligned(&rhs)?;
+        Some(Self {
+            mantissa: lhs.checked_add(rhs)?,
+            exponent,
+        })
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        let (lhs, rhs, exponent) = self.aligned(&rhs)?;
+        Some(Self {
+            mantissa: lhs.checked_sub(rhs)?,
+            exponent,
+        })
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            mantissa: self.mantissa.checked_neg()?,
+            exponent: self.exponent,
+        })
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Some(Self {
+            mantissa: self.mantissa.checked_mul(rhs.mantissa)?,
+            exponent: self.exponent.checked_add(rhs.exponent)?,
+        })
+    }
+
+    /// Divides by `rhs`, producing a result with the given exponent and
+    /// truncating towards zero. Returns `None` on division by zero or overflow.
+    pub fn checked_div_with_exponent(self, rhs: Self, exponent: i8) -> Option<Self> {
+        if rhs.mantissa == 0 {
+            return None;
+        }
+        // self / rhs = (m1 / m2) * 10^(e1 - e2), re-expressed as m * 10^exponent.
+        let shift = i32::from(self.exponent) - i32::from(rhs.exponent) - i32::from(exponent);
+        let mantissa = if shift >= 0 {
+            scale_up(self.mantissa, shift.unsigned_abs())? / rhs.mantissa
+        } else {
+            match scale_up(rhs.mantissa, shift.unsigned_abs()) {
+                Some(divisor) => self.mantissa / divisor,
+                // The divisor exceeds any mantissa.
+                None => 0,
+            }
+        };
+        Some(Self { mantissa, exponent })
+    }
+
+    /// Divides by `rhs`, keeping the exponent of `self`.
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        self.checked_div_with_exponent(rhs, self.exponent)
+    }
+
+    /// The exact remainder of dividing by `rhs`, with the sign of `self`.
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        let (lhs, rhs, exponent) = self.aligned(&rhs)?;
+        Some(Self {
+            mantissa: lhs.checked_rem(rhs)?,
+            exponent,
+        })
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Signs order values of any magnitude, and zeros are equal whatever their
+        // exponents, even where aligning them would overflow.
+        match self.mantissa.signum().cmp(&other.mantissa.signum()) {
+            Ordering::Equal if self.mantissa == 0 => return Ordering::Equal,
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
+        match self.aligned(other) {
+            Some((lhs, rhs, _)) => lhs.cmp(&rhs),
+            // Aligning only scales up the operand with the larger exponent, so
+            // if that overflows its magnitude exceeds the other operand's.
+            None if self.exponent > other.exponent => self.mantissa.cmp(&0),
+            None => 0.cmp(&other.mantissa),
+        }
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalize();
+        normalized.mantissa.hash(state);
+        normalized.exponent.hash(state);
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.checked_neg().expect("attempt to negate with overflow")
+    }
+}
+
+impl Add for Decimal {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).expect("attempt to add with overflow")
+    }
+}
+
+impl Sub for Decimal {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul for Decimal {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs)
+            .expect("attempt to multiply with overflow")
+    }
+}
+
+impl Div for Decimal {
+    type Output = Self;
+
+    /// Keeps the exponent of the dividend; see [`Decimal::checked_div_with_exponent`]
+    /// to choose the precision of the result.
+    fn div(self, rhs: Self) -> Self {
+        assert!(rhs.mantissa != 0, "attempt to divide by zero");
+        self.checked_div(rhs)
+            .expect("attempt to divide with overflow")
+    }
+}
+
+impl Rem for Decimal {
+    type Output = Self;
+
+    fn rem(self, rhs: Self) -> Self {
+        assert!(
+            rhs.mantissa != 0,
+            "attempt to calculate the remainder with a divisor of zero"
+        );
+        self.checked_rem(rhs)
+            .expect("attempt to calculate the remainder with overflow")
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let digits = self.mantissa.unsigned_abs().to_string();
+        if self.exponent >= 0 {
+            let zeros = if self.mantissa == 0 {
+                0
+            } else {
+                self.exponent as usize
+            };
+            return write!(f, "{sign}{digits}{:0<zeros$}", "");
+        }
+        let scale = self.exponent.unsigned_abs() as usize;
+        if digits.len() > scale {
+            let (integer, fraction) = digits.split_at(digits.len() - scale);
+            write!(f, "{sign}{integer}.{fraction}")
+        } else {
+            write!(f, "{sign}0.{digits:0>scale$}")
+        }
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = anyhow::Error;
+
+    /// Parses plain decimal notation such as `-12.3400`; the number of
+    /// fractional digits determines the exponent.
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let (negative, unsigned) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let all_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+        if integer.is_empty() && fraction.is_empty()
+            || !all_digits(integer)
+            || !all_digits(fraction)
+        {
+            bail!("Invalid decimal {s:?}");
+        }
+        let exponent = i8::try_from(fraction.len())
+            .map(|scale| -scale)
+            .map_err(|_| anyhow!("Too many fractional digits in {s:?}"))?;
+        let mut mantissa: i128 = 0;
+        for byte in integer.bytes().chain(fraction.bytes()) {
+            mantissa = mantissa
+                .checked_mul(10)
+                .and_then(|mantissa| mantissa.checked_add(i128::from(byte - b'0')))
+                .ok_or_else(|| anyhow!("Decimal {s:?} out of range"))?;
+        }
+        if negative {
+            mantissa = -mantissa;
+        }
+        Ok(Self { mantissa, exponent })
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match DECIMAL_FORMAT.with(Cell::get) {
+            DecimalFormat::String => serializer.collect_str(self),
+            DecimalFormat::MantissaExponent => {
+                let mut state = serializer.serialize_struct("Decimal", 2)?;
+                state.serialize_field("mantissa", &self.mantissa)?;
+                state.serialize_field("exponent", &self.exponent)?;
+                state.end()
+            }
+        }
+    }
+}
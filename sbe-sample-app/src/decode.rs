@@ -0,0 +1,30 @@
+use anyhow::bail;
+use spot_sbe::bool_enum::BoolEnum;
+
+const MAX_PREALLOCATED_ENTRIES: usize = 4096;
+
+// Group counts are read from the payload itself, so a corrupted count must not
+// be able to trigger a huge allocation before the group is walked.
+pub(crate) fn preallocated(count: usize) -> usize {
+    count.min(MAX_PREALLOCATED_ENTRIES)
+}
+
+pub(crate) fn into_bool(value: BoolEnum) -> anyhow::Result<bool> {
+    Ok(match value {
+        BoolEnum::True => true,
+        BoolEnum::False => false,
+        BoolEnum::NullVal => {
+            bail!("Bool value unexpectedly set to NullVal");
+        }
+    })
+}
+
+/// A bool added after the first version, which decoders read as `NullVal` from
+/// the versions before it.
+pub(crate) fn into_optional_bool(value: BoolEnum) -> Option<bool> {
+    match value {
+        BoolEnum::True => Some(true),
+        BoolEnum::False => Some(false),
+        BoolEnum::NullVal => None,
+    }
+}
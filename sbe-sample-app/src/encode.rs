@@ -0,0 +1,396 @@
+//! Serializes owned models back into complete SBE messages, header included,
+//! each in a buffer of exactly its encoded length.
+//!
+//! Decimals sharing an exponent on the wire are written with the smallest
+//! exponent among them, so re-encoding a decoded model reproduces the original
+//! bytes, save for the exponents of a message with no decimals to encode.
+
+use crate::{
+    decimal::Decimal, exchange_info::ErrorResponse, market_data::Depth, rate_limit::RateLimit,
+    user_data::ExecutionReport,
+};
+use anyhow::{anyhow, bail};
+use spot_sbe::{
+    bool_enum::BoolEnum,
+    depth_response_codec::{
+        self,
+        encoder::{AsksEncoder, BidsEncoder},
+        DepthResponseEncoder,
+    },
+    error_response_codec::{self, ErrorResponseEncoder},
+    execution_report_event_codec::{self, ExecutionReportEventEncoder},
+    group_size_16_encoding_codec, group_size_encoding_codec, message_header_codec,
+    web_socket_response_codec::{self, encoder::RateLimitsEncoder, WebSocketResponseEncoder},
+    Encoder, WriteBuf,
+};
+use std::fmt::Display;
+
+/// The length prefix of a var-data encoding.
+struct VarData {
+    prefix: usize,
+    max: usize,
+}
+
+const VAR_STRING8: VarData = VarData {
+    prefix: 1,
+    max: u8::MAX as usize,
+};
+
+const VAR_STRING: VarData = VarData {
+    prefix: 2,
+    max: u16::MAX as usize,
+};
+
+const MESSAGE_DATA: VarData = VarData {
+    prefix: 4,
+    max: u32::MAX as usize,
+};
+
+impl VarData {
+    /// The encoded length of `value`, prefix included. The generated setters
+    /// truncate the length silently, so overlong values are refused here.
+    fn length(&self, field: &str, value: &[u8]) -> anyhow::Result<usize> {
+        if value.len() > self.max {
+            bail!(
+                "{field} is {} bytes long; at most {} fit",
+                value.len(),
+                self.max
+            );
+        }
+        Ok(self.prefix + value.len())
+    }
+}
+
+/// The encoded length of a group, dimension included.
+fn group_length(dimension: usize, count: usize, block_length: u16) -> usize {
+    dimension + count * usize::from(block_length)
+}
+
+fn group_count<T: TryFrom<usize>>(field: &str, count: usize) -> anyhow::Result<T> {
+    T::try_from(count)
+        .map_err(|_| anyhow!("{field} has {count} entries, more than its group holds"))
+}
+
+/// The smallest exponent among `values`, which expresses all of them exactly.
+fn common_exponent<'a>(values: impl IntoIterator<Item = &'a Decimal>) -> i8 {
+    values.into_iter().map(Decimal::exponent).min().unwrap_or(0)
+}
+
+fn mantissa(field: &str, value: &Decimal, exponent: i8) -> anyhow::Result<i64> {
+    value
+        .rescale(exponent)
+        .and_then(|value| i64::try_from(value.mantissa()).ok())
+        .ok_or_else(|| {
+            anyhow!("{field} of {value} overflows an Int64 mantissa at exponent {exponent}")
+        })
+}
+
+/// The value of an optional field, refusing a present value that would read
+/// back as null.
+fn optional<T: Copy + PartialEq + Display>(
+    field: &str,
+    value: Option<T>,
+    null: T,
+) -> anyhow::Result<T> {
+    match value {
+        Some(value) if value == null => bail!("{field} of {value} collides with its null value"),
+        value => Ok(value.unwrap_or(null)),
+    }
+}
+
+fn optional_mantissa(field: &str, value: Option<Decimal>, exponent: i8) -> anyhow::Result<i64> {
+    let value = value
+        .map(|value| mantissa(field, &value, exponent))
+        .transpose()?;
+    optional(field, value, i64::MIN)
+}
+
+fn from_bool(value: bool) -> BoolEnum {
+    if value {
+        BoolEnum::True
+    } else {
+        BoolEnum::False
+    }
+}
+
+pub fn encode_depth(depth: &Depth) -> anyhow::Result<Vec<u8>> {
+    let levels = depth.bids.iter().chain(&depth.asks);
+    let price_exponent = common_exponent(levels.clone().map(|level| &level.price));
+    let qty_exponent = common_exponent(levels.map(|level| &level.qty));
+    let bid_count: u32 = group_count("bids", depth.bids.len())?;
+    let ask_count: u32 = group_count("asks", depth.asks.len())?;
+    let length = message_header_codec::ENCODED_LENGTH
+        + usize::from(depth_response_codec::SBE_BLOCK_LENGTH)
+        + group_length(
+            group_size_encoding_codec::ENCODED_LENGTH,
+            depth.bids.len(),
+            BidsEncoder::<DepthResponseEncoder>::block_length(),
+        )
+        + group_length(
+            group_size_encoding_codec::ENCODED_LENGTH,
+            depth.asks.len(),
+            AsksEncoder::<DepthResponseEncoder>::block_length(),
+        );
+
+    let mut buf = vec![0; length];
+    let encoder = DepthResponseEncoder::default().wrap(
+        WriteBuf::new(&mut buf),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let mut header = encoder.header(0);
+    let mut encoder = header.parent()?;
+    encoder.last_update_id(depth.last_update_id);
+    encoder.price_exponent(price_exponent);
+    encoder.qty_exponent(qty_exponent);
+
+    let mut bids = encoder.bids_encoder(bid_count, BidsEncoder::default());
+    for level in &depth.bids {
+        bids.advance()?;
+        bids.price(mantissa("bid price", &level.price, price_exponent)?);
+        bids.qty(mantissa("bid qty", &level.qty, qty_exponent)?);
+    }
+    let encoder = bids.parent()?;
+
+    let mut asks = encoder.asks_encoder(ask_count, AsksEncoder::default());
+    for level in &depth.asks {
+        asks.advance()?;
+        asks.price(mantissa("ask price", &level.price, price_exponent)?);
+        asks.qty(mantissa("ask qty", &level.qty, qty_exponent)?);
+    }
+    let encoder = asks.parent()?;
+    debug_assert_eq!(encoder.get_limit(), length);
+    Ok(buf)
+}
+
+pub fn encode_execution_report(report: &ExecutionReport) -> anyhow::Result<Vec<u8>> {
+    let price_exponent = common_exponent(
+        [
+            &report.price,
+            &report.orig_quote_order_qty,
+            &report.stop_price,
+            &report.cummulative_quote_qty,
+            &report.last_price,
+            &report.quote_qty,
+        ]
+        .into_iter()
+        .chain(&report.prevented_execution_price)
+        .chain(&report.prevented_execution_quote_qty)
+        .chain(&report.pegged_price),
+    );
+    let qty_exponent = common_exponent(
+        [
+            &report.orig_qty,
+            &report.iceberg_qty,
+            &report.executed_qty,
+            &report.last_qty,
+            &report.prevented_qty,
+        ]
+        .into_iter()
+        .chain(&report.last_prevented_qty)
+        .chain(&report.prevented_execution_qty),
+    );
+    let commission_exponent = report.commission.exponent();
+    let price = |field, value| mantissa(field, value, price_exponent);
+    let qty = |field, value| mantissa(field, value, qty_exponent);
+
+    let strings = [
+        ("symbol", &report.symbol),
+        ("clientOrderId", &report.client_order_id),
+        ("origClientOrderId", &report.orig_client_order_id),
+        ("commissionAsset", &report.commission_asset),
+        ("rejectReason", &report.reject_reason),
+        ("counterSymbol", &report.counter_symbol),
+    ];
+    let mut length = message_header_codec::ENCODED_LENGTH
+        + usize::from(execution_report_event_codec::SBE_BLOCK_LENGTH);
+    for (field, value) in strings {
+        length += VAR_STRING8.length(field, value.as_bytes())?;
+    }
+
+    let mut buf = vec![0; length];
+    let encoder = ExecutionReportEventEncoder::default().wrap(
+        WriteBuf::new(&mut buf),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let mut header = encoder.header(0);
+    let mut encoder = header.parent()?;
+    encoder.event_time(report.event_time);
+    encoder.transact_time(report.transact_time);
+    encoder.price_exponent(price_exponent);
+    encoder.qty_exponent(qty_exponent);
+    encoder.commission_exponent(commission_exponent);
+    encoder.order_creation_time(optional(
+        "orderCreationTime",
+        report.order_creation_time,
+        i64::MIN,
+    )?);
+    encoder.working_time(optional("workingTime", report.working_time, i64::MIN)?);
+    encoder.order_id(report.order_id);
+    encoder.order_list_id(optional("orderListId", report.order_list_id, i64::MIN)?);
+    encoder.orig_qty(qty("origQty", &report.orig_qty)?);
+    encoder.price(price("price", &report.price)?);
+    encoder.orig_quote_order_qty(price("origQuoteOrderQty", &report.orig_quote_order_qty)?);
+    encoder.iceberg_qty(qty("icebergQty", &report.iceberg_qty)?);
+    encoder.stop_price(price("stopPrice", &report.stop_price)?);
+    encoder.order_type(report.order_type);
+    encoder.side(report.side);
+    encoder.time_in_force(report.time_in_force);
+    encoder.execution_type(report.execution_type);
+    encoder.order_status(report.order_status);
+    encoder.trade_id(optional("tradeId", report.trade_id, i64::MIN)?);
+    encoder.execution_id(report.execution_id);
+    encoder.executed_qty(qty("executedQty", &report.executed_qty)?);
+    encoder.cummulative_quote_qty(price("cummulativeQuoteQty", &report.cummulative_quote_qty)?);
+    encoder.last_qty(qty("lastQty", &report.last_qty)?);
+    encoder.last_price(price("lastPrice", &report.last_price)?);
+    encoder.quote_qty(price("quoteQty", &report.quote_qty)?);
+    encoder.commission(mantissa(
+        "commission",
+        &report.commission,
+        commission_exponent,
+    )?);
+    encoder.is_working(from_bool(report.is_working));
+    encoder.is_maker(from_bool(report.is_maker));
+    encoder.is_best_match(from_bool(report.is_best_match));
+    encoder.match_type(report.match_type);
+    encoder.self_trade_prevention_mode(report.self_trade_prevention_mode);
+    encoder.order_capacity(report.order_capacity);
+    encoder.working_floor(report.working_floor);
+    encoder.used_sor(from_bool(report.used_sor));
+    encoder.alloc_id(optional("allocId", report.alloc_id, i64::MIN)?);
+    encoder.trailing_delta(optional("trailingDelta", report.trailing_delta, u64::MAX)?);
+    encoder.trailing_time(optional("trailingTime", report.trailing_time, i64::MIN)?);
+    encoder.trade_group_id(optional("tradeGroupId", report.trade_group_id, i64::MIN)?);
+    encoder.prevented_qty(qty("preventedQty", &report.prevented_qty)?);
+    encoder.last_prevented_qty(optional_mantissa(
+        "lastPreventedQty",
+        report.last_prevented_qty,
+        qty_exponent,
+    )?);
+    encoder.prevented_match_id(optional(
+        "preventedMatchId",
+        report.prevented_match_id,
+        i64::MIN,
+    )?);
+    encoder.prevented_execution_qty(optional_mantissa(
+        "preventedExecutionQty",
+        report.prevented_execution_qty,
+        qty_exponent,
+    )?);
+    encoder.prevented_execution_price(optional_mantissa(
+        "preventedExecutionPrice",
+        report.prevented_execution_price,
+        price_exponent,
+    )?);
+    encoder.prevented_execution_quote_qty(optional_mantissa(
+        "preventedExecutionQuoteQty",
+        report.prevented_execution_quote_qty,
+        price_exponent,
+    )?);
+    encoder.strategy_type(optional("strategyType", report.strategy_type, i32::MIN)?);
+    encoder.strategy_id(optional("strategyId", report.strategy_id, i64::MIN)?);
+    encoder.counter_order_id(optional(
+        "counterOrderId",
+        report.counter_order_id,
+        i64::MIN,
+    )?);
+    encoder.subscription_id(optional(
+        "subscriptionId",
+        report.subscription_id,
+        u16::MAX,
+    )?);
+    encoder.peg_price_type(report.peg_price_type);
+    encoder.peg_offset_type(report.peg_offset_type);
+    encoder.peg_offset_value(optional(
+        "pegOffsetValue",
+        report.peg_offset_value,
+        u8::MAX,
+    )?);
+    encoder.pegged_price(optional_mantissa(
+        "peggedPrice",
+        report.pegged_price,
+        price_exponent,
+    )?);
+    encoder.expiry_reason(report.expiry_reason);
+    encoder.symbol(&report.symbol);
+    encoder.client_order_id(&report.client_order_id);
+    encoder.orig_client_order_id(&report.orig_client_order_id);
+    encoder.commission_asset(&report.commission_asset);
+    encoder.reject_reason(&report.reject_reason);
+    encoder.counter_symbol(&report.counter_symbol);
+    debug_assert_eq!(encoder.get_limit(), length);
+    Ok(buf)
+}
+
+/// Encodes an error response with an empty `data` field.
+pub fn encode_error(error: &ErrorResponse) -> anyhow::Result<Vec<u8>> {
+    let length = message_header_codec::ENCODED_LENGTH
+        + usize::from(error_response_codec::SBE_BLOCK_LENGTH)
+        + VAR_STRING.length("msg", error.msg.as_bytes())?
+        + MESSAGE_DATA.length("data", &[])?;
+
+    let mut buf = vec![0; length];
+    let encoder = ErrorResponseEncoder::default().wrap(
+        WriteBuf::new(&mut buf),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let mut header = encoder.header(0);
+    let mut encoder = header.parent()?;
+    encoder.code(error.code);
+    encoder.server_time(optional("serverTime", error.server_time, i64::MIN)?);
+    encoder.retry_after(optional("retryAfter", error.retry_after, i64::MIN)?);
+    encoder.msg(&error.msg);
+    encoder.data(&[]);
+    debug_assert_eq!(encoder.get_limit(), length);
+    Ok(buf)
+}
+
+/// Wraps an encoded response or error into a WebSocket API response envelope.
+/// Each rate limit needs its current `count`.
+pub fn encode_websocket_response(
+    id: &str,
+    status: u16,
+    rate_limits: &[RateLimit],
+    result: &[u8],
+) -> anyhow::Result<Vec<u8>> {
+    let rate_limit_count: u16 = group_count("rateLimits", rate_limits.len())?;
+    let length = message_header_codec::ENCODED_LENGTH
+        + usize::from(web_socket_response_codec::SBE_BLOCK_LENGTH)
+        + group_length(
+            group_size_16_encoding_codec::ENCODED_LENGTH,
+            rate_limits.len(),
+            RateLimitsEncoder::<WebSocketResponseEncoder>::block_length(),
+        )
+        + VAR_STRING8.length("id", id.as_bytes())?
+        + MESSAGE_DATA.length("result", result)?;
+
+    let mut buf = vec![0; length];
+    let encoder = WebSocketResponseEncoder::default().wrap(
+        WriteBuf::new(&mut buf),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let mut header = encoder.header(0);
+    let mut encoder = header.parent()?;
+    encoder.sbe_schema_id_version_deprecated(BoolEnum::False);
+    encoder.status(status);
+    let mut limits = encoder.rate_limits_encoder(rate_limit_count, RateLimitsEncoder::default());
+    for rate_limit in rate_limits {
+        let Some(count) = rate_limit.count else {
+            bail!(
+                "Rate limit {:?} has no current count",
+                rate_limit.rate_limit_type
+            );
+        };
+        limits.advance()?;
+        limits.rate_limit_type(rate_limit.rate_limit_type);
+        limits.interval(rate_limit.interval);
+        limits.interval_num(rate_limit.interval_num);
+        limits.rate_limit(rate_limit.limit);
+        limits.current(count);
+    }
+    let mut encoder = limits.parent()?;
+    encoder.id(id);
+    encoder.result(result);
+    debug_assert_eq!(encoder.get_limit(), length);
+    Ok(buf)
+}
@@ -1,32 +1,29 @@
-use crate::rate_limit::RateLimit;
+use crate::{
+    decimal::Decimal,
+    decode::{into_bool, into_optional_bool, preallocated},
+    message::{decode_any, upgrade, SpotMessage},
+    rate_limit::RateLimit,
+};
+use anyhow::bail;
 use serde::{ser::SerializeSeq, Serialize, Serializer};
 use spot_sbe::{
-    allowed_self_trade_prevention_modes::AllowedSelfTradePreventionModes, order_types::OrderTypes,
+    allowed_self_trade_prevention_modes::AllowedSelfTradePreventionModes,
+    error_response_codec::ErrorResponseDecoder,
+    exchange_info_response_codec::ExchangeInfoResponseDecoder, order_types::OrderTypes,
     self_trade_prevention_mode::SelfTradePreventionMode, symbol_status::SymbolStatus,
 };
 
-#[derive(Serialize)]
+#[derive(Debug, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ErrorResponse {
     pub code: i16,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub server_time: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub retry_after: Option<i64>,
     pub msg: String,
 }
 
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Decimal {
-    mantissa: i64,
-    exponent: i8,
-}
-
-impl Decimal {
-    pub fn new(mantissa: i64, exponent: i8) -> Self {
-        Self { mantissa, exponent }
-    }
-}
-
 #[allow(clippy::enum_variant_names)]
 #[derive(Serialize)]
 #[serde(
@@ -35,9 +32,13 @@ impl Decimal {
     rename_all_fields = "camelCase"
 )]
 pub enum ExchangeFilter {
+    #[serde(rename = "EXCHANGE_MAX_NUM_ORDERS")]
     MaxNumOrders { max_num_orders: i64 },
+    #[serde(rename = "EXCHANGE_MAX_NUM_ALGO_ORDERS")]
     MaxNumAlgoOrders { max_num_algo_orders: i64 },
+    #[serde(rename = "EXCHANGE_MAX_NUM_ICEBERG_ORDERS")]
     MaxNumIcebergOrders { max_num_iceberg_orders: i64 },
+    #[serde(rename = "EXCHANGE_MAX_NUM_ORDER_LISTS")]
     MaxNumOrderLists { max_num_order_lists: i64 },
 }
 
@@ -48,6 +49,7 @@ pub enum ExchangeFilter {
     rename_all_fields = "camelCase"
 )]
 pub enum SymbolFilter {
+    #[serde(rename = "PRICE_FILTER")]
     Price {
         min_price: Decimal,
         max_price: Decimal,
@@ -83,6 +85,7 @@ pub enum SymbolFilter {
         avg_price_mins: i32,
     },
     IcebergParts {
+        #[serde(rename = "limit")]
         filter_limit: i64,
     },
     MarketLotSize {
@@ -216,7 +219,7 @@ fn serialize_order_types<S: Serializer>(
     seq.end()
 }
 
-fn serialize_self_trade_prevention_mode<S: Serializer>(
+pub(crate) fn serialize_self_trade_prevention_mode<S: Serializer>(
     val: &SelfTradePreventionMode,
     serializer: S,
 ) -> Result<S::Ok, S::Error> {
@@ -227,6 +230,7 @@ fn serialize_self_trade_prevention_mode<S: Serializer>(
         ExpireMaker => "EXPIRE_MAKER",
         ExpireBoth => "EXPIRE_BOTH",
         Decrement => "DECREMENT",
+        Transfer => "TRANSFER",
         NonRepresentable => "NON_REPRESENTABLE",
         NullVal => return serializer.serialize_none(),
     };
@@ -237,7 +241,7 @@ fn serialize_allowed_self_trade_prevention_modes<S: Serializer>(
     val: &AllowedSelfTradePreventionModes,
     serializer: S,
 ) -> Result<S::Ok, S::Error> {
-    let mut strings = Vec::with_capacity(4);
+    let mut strings = Vec::with_capacity(7);
     if val.get_none() {
         strings.push("NONE");
     }
@@ -253,6 +257,9 @@ fn serialize_allowed_self_trade_prevention_modes<S: Serializer>(
     if val.get_decrement() {
         strings.push("DECREMENT");
     }
+    if val.get_transfer() {
+        strings.push("TRANSFER");
+    }
     if val.get_non_representable() {
         strings.push("NON_REPRESENTABLE");
     }
@@ -262,3 +269,299 @@ fn serialize_allowed_self_trade_prevention_modes<S: Serializer>(
     }
     seq.end()
 }
+
+pub fn decode_error(mut decoder: ErrorResponseDecoder<'_>) -> anyhow::Result<ErrorResponse> {
+    let response = ErrorResponse {
+        code: decoder.code(),
+        server_time: decoder.server_time(),
+        retry_after: decoder.retry_after(),
+        msg: {
+            let (offset, length) = decoder.try_msg_decoder()?;
+            let slice = decoder.msg_slice((offset, length));
+            String::from_utf8(slice.into())?
+        },
+    };
+    Ok(response)
+}
+
+/// Decodes an exchange filter from the complete SBE message nested in an
+/// `exchangeFilters` entry.
+pub fn decode_exchange_filter(payload: &[u8]) -> anyhow::Result<ExchangeFilter> {
+    Ok(match decode_any(&upgrade(payload)?)? {
+        SpotMessage::ExchangeMaxNumOrdersFilter(decoder) => ExchangeFilter::MaxNumOrders {
+            max_num_orders: decoder.max_num_orders(),
+        },
+        SpotMessage::ExchangeMaxNumAlgoOrdersFilter(decoder) => ExchangeFilter::MaxNumAlgoOrders {
+            max_num_algo_orders: decoder.max_num_algo_orders(),
+        },
+        SpotMessage::ExchangeMaxNumIcebergOrdersFilter(decoder) => {
+            ExchangeFilter::MaxNumIcebergOrders {
+                max_num_iceberg_orders: decoder.max_num_iceberg_orders(),
+            }
+        }
+        SpotMessage::ExchangeMaxNumOrderListsFilter(decoder) => ExchangeFilter::MaxNumOrderLists {
+            max_num_order_lists: decoder.max_num_order_lists(),
+        },
+        message => {
+            bail!(
+                "Unexpected exchange filter template ID: {}",
+                message.template_id()
+            );
+        }
+    })
+}
+
+/// Decodes a symbol filter from the complete SBE message nested in a symbol's
+/// `filters` entry.
+pub fn decode_symbol_filter(payload: &[u8]) -> anyhow::Result<SymbolFilter> {
+    Ok(match decode_any(&upgrade(payload)?)? {
+        SpotMessage::PriceFilter(filter) => {
+            let exponent = filter.price_exponent();
+            SymbolFilter::Price {
+                min_price: Decimal::new(filter.min_price(), exponent),
+                max_price: Decimal::new(filter.max_price(), exponent),
+                tick_size: Decimal::new(filter.tick_size(), exponent),
+            }
+        }
+        SpotMessage::PercentPriceFilter(filter) => {
+            let exponent = filter.multiplier_exponent();
+            SymbolFilter::PercentPrice {
+                multiplier_up: Decimal::new(filter.multiplier_up(), exponent),
+                multiplier_down: Decimal::new(filter.multiplier_down(), exponent),
+                avg_price_mins: filter.avg_price_mins(),
+            }
+        }
+        SpotMessage::PercentPriceBySideFilter(filter) => {
+            let exponent = filter.multiplier_exponent();
+            SymbolFilter::PercentPriceBySide {
+                bid_multiplier_up: Decimal::new(filter.bid_multiplier_up(), exponent),
+                bid_multiplier_down: Decimal::new(filter.bid_multiplier_down(), exponent),
+                ask_multiplier_up: Decimal::new(filter.ask_multiplier_up(), exponent),
+                ask_multiplier_down: Decimal::new(filter.ask_multiplier_down(), exponent),
+                avg_price_mins: filter.avg_price_mins(),
+            }
+        }
+        SpotMessage::LotSizeFilter(filter) => {
+            let exponent = filter.qty_exponent();
+            SymbolFilter::LotSize {
+                min_qty: Decimal::new(filter.min_qty(), exponent),
+                max_qty: Decimal::new(filter.max_qty(), exponent),
+                step_size: Decimal::new(filter.step_size(), exponent),
+            }
+        }
+        SpotMessage::MinNotionalFilter(filter) => {
+            let exponent = filter.price_exponent();
+            SymbolFilter::MinNotional {
+                min_notional: Decimal::new(filter.min_notional(), exponent),
+                apply_to_market: into_bool(filter.apply_to_market())?,
+                avg_price_mins: filter.avg_price_mins(),
+            }
+        }
+        SpotMessage::NotionalFilter(filter) => {
+            let exponent = filter.price_exponent();
+            SymbolFilter::Notional {
+                min_notional: Decimal::new(filter.min_notional(), exponent),
+                apply_min_to_market: into_bool(filter.apply_min_to_market())?,
+                max_notional: Decimal::new(filter.max_notional(), exponent),
+                apply_max_to_market: into_bool(filter.apply_max_to_market())?,
+                avg_price_mins: filter.avg_price_mins(),
+            }
+        }
+        SpotMessage::IcebergPartsFilter(filter) => SymbolFilter::IcebergParts {
+            filter_limit: filter.filter_limit(),
+        },
+        SpotMessage::MarketLotSizeFilter(filter) => {
+            let exponent = filter.qty_exponent();
+            SymbolFilter::MarketLotSize {
+                min_qty: Decimal::new(filter.min_qty(), exponent),
+                max_qty: Decimal::new(filter.max_qty(), exponent),
+                step_size: Decimal::new(filter.step_size(), exponent),
+            }
+        }
+        SpotMessage::MaxNumOrdersFilter(filter) => SymbolFilter::MaxNumOrders {
+            max_num_orders: filter.max_num_orders(),
+        },
+        SpotMessage::MaxNumAlgoOrdersFilter(filter) => SymbolFilter::MaxNumAlgoOrders {
+            max_num_algo_orders: filter.max_num_algo_orders(),
+        },
+        SpotMessage::MaxNumIcebergOrdersFilter(filter) => SymbolFilter::MaxNumIcebergOrders {
+            max_num_iceberg_orders: filter.max_num_iceberg_orders(),
+        },
+        SpotMessage::MaxNumOrderAmendsFilter(filter) => SymbolFilter::MaxNumOrderAmends {
+            max_num_order_amends: filter.max_num_order_amends(),
+        },
+        SpotMessage::MaxNumOrderListsFilter(filter) => SymbolFilter::MaxNumOrderLists {
+            max_num_order_lists: filter.max_num_order_lists(),
+        },
+        SpotMessage::MaxPositionFilter(filter) => {
+            let exponent = filter.qty_exponent();
+            SymbolFilter::MaxPosition {
+                max_position: Decimal::new(filter.max_position(), exponent),
+            }
+        }
+        SpotMessage::TrailingDeltaFilter(filter) => SymbolFilter::TrailingDelta {
+            min_trailing_above_delta: filter.min_trailing_above_delta(),
+            max_trailing_above_delta: filter.max_trailing_above_delta(),
+            min_trailing_below_delta: filter.min_trailing_below_delta(),
+            max_trailing_below_delta: filter.max_trailing_below_delta(),
+        },
+        SpotMessage::TPlusSellFilter(filter) => SymbolFilter::TPlusSell {
+            end_time: filter.end_time(),
+        },
+        message => {
+            bail!(
+                "Unexpected symbol filter message ID: {}",
+                message.template_id()
+            );
+        }
+    })
+}
+
+pub fn decode_exchange_info(
+    decoder: ExchangeInfoResponseDecoder<'_>,
+) -> anyhow::Result<ExchangeInfo> {
+    let mut decoder = decoder.try_rate_limits_decoder()?;
+    let count = decoder.count();
+    let mut rate_limits = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        let rate_limit = RateLimit {
+            rate_limit_type: decoder.rate_limit_type(),
+            interval: decoder.interval(),
+            interval_num: decoder.interval_num(),
+            limit: decoder.rate_limit(),
+            count: None,
+        };
+        rate_limits.push(rate_limit);
+    }
+    let decoder = decoder.parent()?;
+
+    let mut decoder = decoder.try_exchange_filters_decoder()?;
+    let count = decoder.count();
+    let mut exchange_filters = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        let coordinates = decoder.try_filter_decoder()?;
+        let slice = decoder.filter_slice(coordinates);
+        let filter = decode_exchange_filter(slice)?;
+        exchange_filters.push(filter);
+    }
+    let decoder = decoder.parent()?;
+
+    let mut decoder = decoder.try_symbols_decoder()?;
+    let count = decoder.count().try_into()?;
+    let mut symbols = Vec::with_capacity(preallocated(count));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        symbols.push(SymbolInfo {
+            status: decoder.status(),
+            base_asset_precision: decoder.base_asset_precision(),
+            quote_asset_precision: decoder.quote_asset_precision(),
+            base_commission_precision: decoder.base_commission_precision(),
+            quote_commission_precision: decoder.quote_commission_precision(),
+            order_types: decoder.order_types(),
+            iceberg_allowed: into_bool(decoder.iceberg_allowed())?,
+            oco_allowed: into_bool(decoder.oco_allowed())?,
+            oto_allowed: into_bool(decoder.oto_allowed())?,
+            quote_order_qty_market_allowed: into_bool(decoder.quote_order_qty_market_allowed())?,
+            allow_trailing_stop: into_bool(decoder.allow_trailing_stop())?,
+            cancel_replace_allowed: into_bool(decoder.cancel_replace_allowed())?,
+            amend_allowed: into_bool(decoder.amend_allowed())?,
+            is_spot_trading_allowed: into_bool(decoder.is_spot_trading_allowed())?,
+            is_margin_trading_allowed: into_bool(decoder.is_margin_trading_allowed())?,
+            default_self_trade_prevention_mode: decoder.default_self_trade_prevention_mode(),
+            allowed_self_trade_prevention_modes: decoder.allowed_self_trade_prevention_modes(),
+            peg_instructions_allowed: into_optional_bool(decoder.peg_instructions_allowed()),
+            filters: {
+                let mut filters_decoder = decoder.try_filters_decoder()?;
+                let count = filters_decoder.count().try_into()?;
+                let mut filters = Vec::with_capacity(preallocated(count));
+                for _ in 0..count {
+                    filters_decoder.try_advance()?;
+                    let coordinates = filters_decoder.try_filter_decoder()?;
+                    let slice = filters_decoder.filter_slice(coordinates);
+                    let filter = decode_symbol_filter(slice)?;
+                    filters.push(filter);
+                }
+                decoder = filters_decoder.parent()?;
+                filters
+            },
+            permission_sets: {
+                let mut permissions_sets_decoder = decoder.try_permission_sets_decoder()?;
+                let count = permissions_sets_decoder.count().try_into()?;
+                let mut all_permissions = Vec::with_capacity(preallocated(count));
+
+                for _ in 0..count {
+                    permissions_sets_decoder.try_advance()?;
+
+                    let mut permissions_decoder =
+                        permissions_sets_decoder.try_permissions_decoder()?;
+                    let count = permissions_decoder.count().try_into()?;
+                    let mut permission_sets = Vec::with_capacity(preallocated(count));
+
+                    for _ in 0..count {
+                        permissions_decoder.try_advance()?;
+                        let coordinates = permissions_decoder.try_permission_decoder()?;
+                        let slice = permissions_decoder.permission_slice(coordinates);
+                        permission_sets.push(String::from_utf8(slice.into())?);
+                    }
+
+                    // IMPORTANT: give back the parent so we can use it again
+                    permissions_sets_decoder = permissions_decoder.parent()?;
+
+                    all_permissions.push(permission_sets);
+                }
+
+                // now we can go back up another level
+                decoder = permissions_sets_decoder.parent()?;
+                all_permissions
+            },
+            symbol: {
+                let coordinates = decoder.try_symbol_decoder()?;
+                let slice = decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            base_asset: {
+                let coordinates = decoder.try_base_asset_decoder()?;
+                let slice = decoder.base_asset_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            quote_asset: {
+                let coordinates = decoder.try_quote_asset_decoder()?;
+                let slice = decoder.quote_asset_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        });
+    }
+    let decoder = decoder.parent()?;
+
+    let mut decoder = decoder.try_sors_decoder()?;
+    let count = decoder.count().try_into()?;
+    let mut sors = Vec::with_capacity(preallocated(count));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        let mut symbols_decoder = decoder.try_sor_symbols_decoder()?;
+        let count = symbols_decoder.count().try_into()?;
+        let mut symbols = Vec::with_capacity(preallocated(count));
+        for _ in 0..count {
+            symbols_decoder.try_advance()?;
+            let coordinates = symbols_decoder.try_symbol_decoder()?;
+            let slice = symbols_decoder.symbol_slice(coordinates);
+            symbols.push(String::from_utf8(slice.into())?);
+        }
+        decoder = symbols_decoder.parent()?;
+        let coordinates = decoder.try_base_asset_decoder()?;
+        let slice = decoder.base_asset_slice(coordinates);
+        let base_asset = String::from_utf8(slice.into())?;
+        sors.push(Sor {
+            symbols,
+            base_asset,
+        });
+    }
+    Ok(ExchangeInfo {
+        rate_limits,
+        exchange_filters,
+        symbols,
+        sors,
+    })
+}
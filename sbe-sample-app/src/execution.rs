@@ -0,0 +1,879 @@
+use crate::{
+    decimal::Decimal,
+    decode::{into_bool, preallocated},
+    exchange_info::{decode_error, ErrorResponse},
+    message::{decode_any, upgrade, SpotMessage},
+    order::{
+        decode_order_list_orders, serialize_contingency_type, serialize_expiry_reason,
+        serialize_floor, serialize_list_order_status, serialize_list_status_type,
+        serialize_order_capacity, serialize_order_side, serialize_order_status,
+        serialize_order_type, serialize_peg_offset_type, serialize_peg_price_type,
+        serialize_time_in_force, OrderListOrder,
+    },
+};
+use anyhow::bail;
+use serde::{Serialize, Serializer};
+use spot_sbe::{
+    cancel_open_orders_response_codec::CancelOpenOrdersResponseDecoder,
+    cancel_order_list_response_codec::CancelOrderListResponseDecoder,
+    cancel_order_response_codec::CancelOrderResponseDecoder,
+    cancel_replace_order_response_codec::CancelReplaceOrderResponseDecoder,
+    cancel_replace_status::CancelReplaceStatus, contingency_type::ContingencyType,
+    expiry_reason::ExpiryReason, floor::Floor, list_order_status::ListOrderStatus,
+    list_status_type::ListStatusType, match_type::MatchType,
+    new_order_ack_response_codec::NewOrderAckResponseDecoder,
+    new_order_full_response_codec::NewOrderFullResponseDecoder,
+    new_order_list_ack_response_codec::NewOrderListAckResponseDecoder,
+    new_order_list_full_response_codec::NewOrderListFullResponseDecoder,
+    new_order_list_result_response_codec::NewOrderListResultResponseDecoder,
+    new_order_result_response_codec::NewOrderResultResponseDecoder,
+    order_amend_keep_priority_response_codec::OrderAmendKeepPriorityResponseDecoder,
+    order_amendments_response_codec::OrderAmendmentsResponseDecoder, order_capacity::OrderCapacity,
+    order_side::OrderSide, order_status::OrderStatus, order_type::OrderType,
+    peg_offset_type::PegOffsetType, peg_price_type::PegPriceType, time_in_force::TimeInForce,
+};
+
+/// A new order, as returned with `newOrderRespType=ACK`.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewOrderAck {
+    pub symbol: String,
+    pub order_id: i64,
+    pub order_list_id: Option<i64>,
+    pub client_order_id: String,
+    pub transact_time: i64,
+}
+
+/// A new order, as returned with `newOrderRespType=RESULT`.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewOrderResult {
+    pub symbol: String,
+    pub order_id: i64,
+    pub order_list_id: Option<i64>,
+    pub client_order_id: String,
+    pub transact_time: i64,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub cummulative_quote_qty: Decimal,
+    #[serde(serialize_with = "serialize_order_status")]
+    pub status: OrderStatus,
+    #[serde(serialize_with = "serialize_time_in_force")]
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type", serialize_with = "serialize_order_type")]
+    pub order_type: OrderType,
+    #[serde(serialize_with = "serialize_order_side")]
+    pub side: OrderSide,
+    pub stop_price: Option<Decimal>,
+    pub trailing_delta: Option<i64>,
+    pub trailing_time: Option<i64>,
+    pub working_time: Option<i64>,
+    pub iceberg_qty: Option<Decimal>,
+    pub strategy_id: Option<i64>,
+    pub strategy_type: Option<i32>,
+    #[serde(serialize_with = "serialize_order_capacity")]
+    pub order_capacity: OrderCapacity,
+    #[serde(serialize_with = "serialize_floor")]
+    pub working_floor: Floor,
+    pub trade_group_id: Option<i64>,
+    pub prevented_quantity: Decimal,
+    pub used_sor: bool,
+    pub orig_quote_order_qty: Decimal,
+    #[serde(serialize_with = "serialize_peg_price_type")]
+    pub peg_price_type: PegPriceType,
+    #[serde(serialize_with = "serialize_peg_offset_type")]
+    pub peg_offset_type: PegOffsetType,
+    pub peg_offset_value: Option<u8>,
+    pub pegged_price: Option<Decimal>,
+    #[serde(serialize_with = "serialize_expiry_reason")]
+    pub expiry_reason: ExpiryReason,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fill {
+    #[serde(serialize_with = "serialize_match_type")]
+    pub match_type: MatchType,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub commission: Decimal,
+    pub commission_asset: String,
+    pub trade_id: Option<i64>,
+    pub alloc_id: Option<i64>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderPreventedMatch {
+    pub prevented_match_id: i64,
+    pub maker_symbol: String,
+    pub maker_order_id: Option<i64>,
+    pub price: Option<Decimal>,
+    pub taker_prevented_quantity: Option<Decimal>,
+    pub maker_prevented_quantity: Option<Decimal>,
+}
+
+/// A new order, as returned with `newOrderRespType=FULL`.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewOrderFull {
+    #[serde(flatten)]
+    pub order: NewOrderResult,
+    pub fills: Vec<Fill>,
+    pub prevented_matches: Vec<OrderPreventedMatch>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CanceledOrder {
+    pub symbol: String,
+    pub orig_client_order_id: String,
+    pub order_id: i64,
+    pub order_list_id: Option<i64>,
+    pub client_order_id: String,
+    pub transact_time: i64,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub cummulative_quote_qty: Decimal,
+    #[serde(serialize_with = "serialize_order_status")]
+    pub status: OrderStatus,
+    #[serde(serialize_with = "serialize_time_in_force")]
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type", serialize_with = "serialize_order_type")]
+    pub order_type: OrderType,
+    #[serde(serialize_with = "serialize_order_side")]
+    pub side: OrderSide,
+    pub stop_price: Option<Decimal>,
+    pub trailing_delta: Option<i64>,
+    pub trailing_time: Option<i64>,
+    pub iceberg_qty: Option<Decimal>,
+    pub strategy_id: Option<i64>,
+    pub strategy_type: Option<i32>,
+    #[serde(serialize_with = "serialize_order_capacity")]
+    pub order_capacity: OrderCapacity,
+    #[serde(serialize_with = "serialize_floor")]
+    pub working_floor: Floor,
+    pub prevented_quantity: Decimal,
+    pub used_sor: bool,
+    pub orig_quote_order_qty: Decimal,
+    #[serde(serialize_with = "serialize_peg_price_type")]
+    pub peg_price_type: PegPriceType,
+    #[serde(serialize_with = "serialize_peg_offset_type")]
+    pub peg_offset_type: PegOffsetType,
+    pub peg_offset_value: Option<u8>,
+    pub pegged_price: Option<Decimal>,
+}
+
+/// An order list placed or canceled in a single request, together with a
+/// report for each of its orders.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListReport<R> {
+    pub order_list_id: i64,
+    #[serde(serialize_with = "serialize_contingency_type")]
+    pub contingency_type: ContingencyType,
+    #[serde(serialize_with = "serialize_list_status_type")]
+    pub list_status_type: ListStatusType,
+    #[serde(serialize_with = "serialize_list_order_status")]
+    pub list_order_status: ListOrderStatus,
+    pub list_client_order_id: String,
+    pub transaction_time: i64,
+    pub symbol: String,
+    pub orders: Vec<OrderListOrder>,
+    pub order_reports: Vec<R>,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum CanceledOpenOrder {
+    Order(CanceledOrder),
+    OrderList(OrderListReport<CanceledOrder>),
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum CancelResponse {
+    Canceled(CanceledOrder),
+    Error(ErrorResponse),
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum NewOrderResponse {
+    Ack(NewOrderAck),
+    Result(NewOrderResult),
+    Full(NewOrderFull),
+    Error(ErrorResponse),
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelReplaceOrder {
+    #[serde(serialize_with = "serialize_cancel_replace_status")]
+    pub cancel_result: CancelReplaceStatus,
+    #[serde(serialize_with = "serialize_cancel_replace_status")]
+    pub new_order_result: CancelReplaceStatus,
+    pub cancel_response: Option<CancelResponse>,
+    pub new_order_response: Option<NewOrderResponse>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendedOrder {
+    pub symbol: String,
+    pub order_id: i64,
+    pub order_list_id: Option<i64>,
+    /// Only set on the amended order itself, not on the related orders of its list.
+    pub orig_client_order_id: Option<String>,
+    pub client_order_id: String,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub executed_qty: Decimal,
+    pub prevented_qty: Decimal,
+    pub cumulative_quote_qty: Decimal,
+    #[serde(serialize_with = "serialize_order_status")]
+    pub status: OrderStatus,
+    #[serde(serialize_with = "serialize_time_in_force")]
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type", serialize_with = "serialize_order_type")]
+    pub order_type: OrderType,
+    #[serde(serialize_with = "serialize_order_side")]
+    pub side: OrderSide,
+    pub stop_price: Option<Decimal>,
+    pub trailing_delta: Option<i64>,
+    pub trailing_time: Option<i64>,
+    pub iceberg_qty: Option<Decimal>,
+    pub working_time: Option<i64>,
+    pub strategy_id: Option<i64>,
+    pub strategy_type: Option<i32>,
+    #[serde(serialize_with = "serialize_order_capacity")]
+    pub order_capacity: OrderCapacity,
+    #[serde(serialize_with = "serialize_floor")]
+    pub working_floor: Floor,
+    pub used_sor: bool,
+    #[serde(serialize_with = "serialize_peg_price_type")]
+    pub peg_price_type: PegPriceType,
+    #[serde(serialize_with = "serialize_peg_offset_type")]
+    pub peg_offset_type: PegOffsetType,
+    pub peg_offset_value: Option<u8>,
+    pub pegged_price: Option<Decimal>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendListStatus {
+    pub order_list_id: i64,
+    #[serde(serialize_with = "serialize_contingency_type")]
+    pub contingency_type: ContingencyType,
+    #[serde(serialize_with = "serialize_list_order_status")]
+    pub list_order_status: ListOrderStatus,
+    pub list_client_order_id: String,
+    pub symbol: String,
+    pub orders: Vec<OrderListOrder>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAmendKeepPriority {
+    pub transact_time: i64,
+    pub execution_id: i64,
+    pub amended_order: AmendedOrder,
+    pub list_status: Option<AmendListStatus>,
+    pub related_orders: Vec<AmendedOrder>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderAmendment {
+    pub symbol: String,
+    pub order_id: i64,
+    pub execution_id: i64,
+    pub orig_client_order_id: String,
+    pub new_client_order_id: String,
+    pub orig_qty: Decimal,
+    pub new_qty: Decimal,
+    pub time: i64,
+}
+
+// Fixed fields are read at the block offset of the decoder, so the groups of a
+// message can be decoded before its fixed fields. The macros below rely on
+// that to read var-data fields in wire order while keeping one field mapping
+// per model, shared by the top-level messages and the order report groups
+// that embed the same fields.
+macro_rules! decode_new_order_ack {
+    ($decoder:ident) => {{
+        NewOrderAck {
+            order_id: $decoder.order_id(),
+            order_list_id: $decoder.order_list_id(),
+            transact_time: $decoder.transact_time(),
+            symbol: {
+                let coordinates = $decoder.try_symbol_decoder()?;
+                let slice = $decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            client_order_id: {
+                let coordinates = $decoder.try_client_order_id_decoder()?;
+                let slice = $decoder.client_order_id_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        }
+    }};
+}
+
+macro_rules! decode_new_order_result {
+    ($decoder:ident, $price_exponent:expr, $qty_exponent:expr) => {{
+        let price_exponent = $price_exponent;
+        let qty_exponent = $qty_exponent;
+        let price = |mantissa: i64| Decimal::new(mantissa, price_exponent);
+        let qty = |mantissa: i64| Decimal::new(mantissa, qty_exponent);
+        NewOrderResult {
+            order_id: $decoder.order_id(),
+            order_list_id: $decoder.order_list_id(),
+            transact_time: $decoder.transact_time(),
+            price: price($decoder.price()),
+            orig_qty: qty($decoder.orig_qty()),
+            executed_qty: qty($decoder.executed_qty()),
+            cummulative_quote_qty: price($decoder.cummulative_quote_qty()),
+            status: $decoder.status(),
+            time_in_force: $decoder.time_in_force(),
+            order_type: $decoder.order_type(),
+            side: $decoder.side(),
+            stop_price: $decoder.stop_price().map(price),
+            trailing_delta: $decoder.trailing_delta(),
+            trailing_time: $decoder.trailing_time(),
+            working_time: $decoder.working_time(),
+            iceberg_qty: $decoder.iceberg_qty().map(qty),
+            strategy_id: $decoder.strategy_id(),
+            strategy_type: $decoder.strategy_type(),
+            order_capacity: $decoder.order_capacity(),
+            working_floor: $decoder.working_floor(),
+            trade_group_id: $decoder.trade_group_id(),
+            prevented_quantity: qty($decoder.prevented_quantity()),
+            used_sor: into_bool($decoder.used_sor())?,
+            orig_quote_order_qty: price($decoder.orig_quote_order_qty()),
+            peg_price_type: $decoder.peg_price_type(),
+            peg_offset_type: $decoder.peg_offset_type(),
+            peg_offset_value: $decoder.peg_offset_value(),
+            pegged_price: $decoder.pegged_price().map(price),
+            expiry_reason: $decoder.expiry_reason(),
+            symbol: {
+                let coordinates = $decoder.try_symbol_decoder()?;
+                let slice = $decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            client_order_id: {
+                let coordinates = $decoder.try_client_order_id_decoder()?;
+                let slice = $decoder.client_order_id_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        }
+    }};
+}
+
+macro_rules! decode_new_order_full {
+    ($decoder:ident, $price_exponent:expr, $qty_exponent:expr) => {{
+        let price_exponent = $price_exponent;
+        let qty_exponent = $qty_exponent;
+
+        let mut fills_decoder = $decoder.try_fills_decoder()?;
+        let count = fills_decoder.count();
+        let mut fills = Vec::with_capacity(preallocated(count.try_into()?));
+        for _ in 0..count {
+            fills_decoder.try_advance()?;
+            fills.push(Fill {
+                match_type: fills_decoder.match_type(),
+                price: Decimal::new(fills_decoder.price(), price_exponent),
+                qty: Decimal::new(fills_decoder.qty(), qty_exponent),
+                commission: Decimal::new(
+                    fills_decoder.commission(),
+                    fills_decoder.commission_exponent(),
+                ),
+                trade_id: fills_decoder.trade_id(),
+                alloc_id: fills_decoder.alloc_id(),
+                commission_asset: {
+                    let coordinates = fills_decoder.try_commission_asset_decoder()?;
+                    let slice = fills_decoder.commission_asset_slice(coordinates);
+                    String::from_utf8(slice.into())?
+                },
+            });
+        }
+        $decoder = fills_decoder.parent()?;
+
+        let mut prevented_matches_decoder = $decoder.try_prevented_matches_decoder()?;
+        let count = prevented_matches_decoder.count();
+        let mut prevented_matches = Vec::with_capacity(preallocated(count.try_into()?));
+        for _ in 0..count {
+            prevented_matches_decoder.try_advance()?;
+            let qty = |mantissa: i64| Decimal::new(mantissa, qty_exponent);
+            prevented_matches.push(OrderPreventedMatch {
+                prevented_match_id: prevented_matches_decoder.prevented_match_id(),
+                maker_order_id: prevented_matches_decoder.maker_order_id(),
+                price: prevented_matches_decoder
+                    .price()
+                    .map(|mantissa| Decimal::new(mantissa, price_exponent)),
+                taker_prevented_quantity: prevented_matches_decoder
+                    .taker_prevented_quantity()
+                    .map(qty),
+                maker_prevented_quantity: prevented_matches_decoder
+                    .maker_prevented_quantity()
+                    .map(qty),
+                maker_symbol: {
+                    let coordinates = prevented_matches_decoder.try_maker_symbol_decoder()?;
+                    let slice = prevented_matches_decoder.maker_symbol_slice(coordinates);
+                    String::from_utf8(slice.into())?
+                },
+            });
+        }
+        $decoder = prevented_matches_decoder.parent()?;
+
+        NewOrderFull {
+            order: decode_new_order_result!($decoder, price_exponent, qty_exponent),
+            fills,
+            prevented_matches,
+        }
+    }};
+}
+
+macro_rules! decode_canceled_order {
+    ($decoder:ident, $price_exponent:expr, $qty_exponent:expr) => {{
+        let price_exponent = $price_exponent;
+        let qty_exponent = $qty_exponent;
+        let price = |mantissa: i64| Decimal::new(mantissa, price_exponent);
+        let qty = |mantissa: i64| Decimal::new(mantissa, qty_exponent);
+        CanceledOrder {
+            order_id: $decoder.order_id(),
+            order_list_id: $decoder.order_list_id(),
+            transact_time: $decoder.transact_time(),
+            price: price($decoder.price()),
+            orig_qty: qty($decoder.orig_qty()),
+            executed_qty: qty($decoder.executed_qty()),
+            cummulative_quote_qty: price($decoder.cummulative_quote_qty()),
+            status: $decoder.status(),
+            time_in_force: $decoder.time_in_force(),
+            order_type: $decoder.order_type(),
+            side: $decoder.side(),
+            stop_price: $decoder.stop_price().map(price),
+            trailing_delta: $decoder.trailing_delta(),
+            trailing_time: $decoder.trailing_time(),
+            iceberg_qty: $decoder.iceberg_qty().map(qty),
+            strategy_id: $decoder.strategy_id(),
+            strategy_type: $decoder.strategy_type(),
+            order_capacity: $decoder.order_capacity(),
+            working_floor: $decoder.working_floor(),
+            prevented_quantity: qty($decoder.prevented_quantity()),
+            used_sor: into_bool($decoder.used_sor())?,
+            orig_quote_order_qty: price($decoder.orig_quote_order_qty()),
+            peg_price_type: $decoder.peg_price_type(),
+            peg_offset_type: $decoder.peg_offset_type(),
+            peg_offset_value: $decoder.peg_offset_value(),
+            pegged_price: $decoder.pegged_price().map(price),
+            symbol: {
+                let coordinates = $decoder.try_symbol_decoder()?;
+                let slice = $decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            orig_client_order_id: {
+                let coordinates = $decoder.try_orig_client_order_id_decoder()?;
+                let slice = $decoder.orig_client_order_id_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            client_order_id: {
+                let coordinates = $decoder.try_client_order_id_decoder()?;
+                let slice = $decoder.client_order_id_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        }
+    }};
+}
+
+/// Decodes an order list message; `$report` decodes one entry of its
+/// `order_reports` group.
+macro_rules! decode_order_list_report {
+    ($decoder:ident, |$report_decoder:ident| $report:expr) => {{
+        let order_list_id = $decoder.order_list_id();
+        let contingency_type = $decoder.contingency_type();
+        let list_status_type = $decoder.list_status_type();
+        let list_order_status = $decoder.list_order_status();
+        let transaction_time = $decoder.transaction_time();
+        let orders = decode_order_list_orders!($decoder);
+
+        let mut $report_decoder = $decoder.try_order_reports_decoder()?;
+        let count = $report_decoder.count();
+        let mut order_reports = Vec::with_capacity(preallocated(count.try_into()?));
+        for _ in 0..count {
+            $report_decoder.try_advance()?;
+            order_reports.push($report);
+        }
+        $decoder = $report_decoder.parent()?;
+
+        OrderListReport {
+            order_list_id,
+            contingency_type,
+            list_status_type,
+            list_order_status,
+            transaction_time,
+            orders,
+            order_reports,
+            list_client_order_id: {
+                let coordinates = $decoder.try_list_client_order_id_decoder()?;
+                let slice = $decoder.list_client_order_id_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            symbol: {
+                let coordinates = $decoder.try_symbol_decoder()?;
+                let slice = $decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        }
+    }};
+}
+
+macro_rules! decode_amended_order {
+    ($decoder:ident, $price_exponent:expr, $qty_exponent:expr, $orig_client_order_id:expr) => {{
+        let price_exponent = $price_exponent;
+        let qty_exponent = $qty_exponent;
+        let price = |mantissa: i64| Decimal::new(mantissa, price_exponent);
+        let qty = |mantissa: i64| Decimal::new(mantissa, qty_exponent);
+        AmendedOrder {
+            order_id: $decoder.order_id(),
+            order_list_id: $decoder.order_list_id(),
+            price: price($decoder.price()),
+            qty: qty($decoder.qty()),
+            executed_qty: qty($decoder.executed_qty()),
+            prevented_qty: qty($decoder.prevented_qty()),
+            cumulative_quote_qty: price($decoder.cumulative_quote_qty()),
+            status: $decoder.status(),
+            time_in_force: $decoder.time_in_force(),
+            order_type: $decoder.order_type(),
+            side: $decoder.side(),
+            stop_price: $decoder.stop_price().map(price),
+            trailing_delta: $decoder.trailing_delta(),
+            trailing_time: $decoder.trailing_time(),
+            iceberg_qty: $decoder.iceberg_qty().map(qty),
+            working_time: $decoder.working_time(),
+            strategy_id: $decoder.strategy_id(),
+            strategy_type: $decoder.strategy_type(),
+            order_capacity: $decoder.order_capacity(),
+            working_floor: $decoder.working_floor(),
+            used_sor: into_bool($decoder.used_sor())?,
+            peg_price_type: $decoder.peg_price_type(),
+            peg_offset_type: $decoder.peg_offset_type(),
+            peg_offset_value: $decoder.peg_offset_value(),
+            pegged_price: $decoder.pegged_price().map(price),
+            symbol: {
+                let coordinates = $decoder.try_symbol_decoder()?;
+                let slice = $decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            orig_client_order_id: $orig_client_order_id,
+            client_order_id: {
+                let coordinates = $decoder.try_client_order_id_decoder()?;
+                let slice = $decoder.client_order_id_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        }
+    }};
+}
+
+pub fn decode_new_order_ack(
+    mut decoder: NewOrderAckResponseDecoder<'_>,
+) -> anyhow::Result<NewOrderAck> {
+    Ok(decode_new_order_ack!(decoder))
+}
+
+pub fn decode_new_order_result(
+    mut decoder: NewOrderResultResponseDecoder<'_>,
+) -> anyhow::Result<NewOrderResult> {
+    Ok(decode_new_order_result!(
+        decoder,
+        decoder.price_exponent(),
+        decoder.qty_exponent()
+    ))
+}
+
+pub fn decode_new_order_full(
+    mut decoder: NewOrderFullResponseDecoder<'_>,
+) -> anyhow::Result<NewOrderFull> {
+    Ok(decode_new_order_full!(
+        decoder,
+        decoder.price_exponent(),
+        decoder.qty_exponent()
+    ))
+}
+
+pub fn decode_new_order_list_ack(
+    mut decoder: NewOrderListAckResponseDecoder<'_>,
+) -> anyhow::Result<OrderListReport<NewOrderAck>> {
+    Ok(decode_order_list_report!(decoder, |report_decoder| {
+        decode_new_order_ack!(report_decoder)
+    }))
+}
+
+pub fn decode_new_order_list_result(
+    mut decoder: NewOrderListResultResponseDecoder<'_>,
+) -> anyhow::Result<OrderListReport<NewOrderResult>> {
+    let price_exponent = decoder.price_exponent();
+    let qty_exponent = decoder.qty_exponent();
+    Ok(decode_order_list_report!(decoder, |report_decoder| {
+        decode_new_order_result!(report_decoder, price_exponent, qty_exponent)
+    }))
+}
+
+pub fn decode_new_order_list_full(
+    mut decoder: NewOrderListFullResponseDecoder<'_>,
+) -> anyhow::Result<OrderListReport<NewOrderFull>> {
+    let price_exponent = decoder.price_exponent();
+    let qty_exponent = decoder.qty_exponent();
+    Ok(decode_order_list_report!(decoder, |report_decoder| {
+        decode_new_order_full!(report_decoder, price_exponent, qty_exponent)
+    }))
+}
+
+pub fn decode_cancel_order(
+    mut decoder: CancelOrderResponseDecoder<'_>,
+) -> anyhow::Result<CanceledOrder> {
+    Ok(decode_canceled_order!(
+        decoder,
+        decoder.price_exponent(),
+        decoder.qty_exponent()
+    ))
+}
+
+pub fn decode_cancel_order_list(
+    mut decoder: CancelOrderListResponseDecoder<'_>,
+) -> anyhow::Result<OrderListReport<CanceledOrder>> {
+    let price_exponent = decoder.price_exponent();
+    let qty_exponent = decoder.qty_exponent();
+    Ok(decode_order_list_report!(decoder, |report_decoder| {
+        decode_canceled_order!(report_decoder, price_exponent, qty_exponent)
+    }))
+}
+
+/// Each entry of the response embeds a complete `CancelOrderResponse` or
+/// `CancelOrderListResponse` message, header included.
+pub fn decode_cancel_open_orders(
+    decoder: CancelOpenOrdersResponseDecoder<'_>,
+) -> anyhow::Result<Vec<CanceledOpenOrder>> {
+    let mut decoder = decoder.try_responses_decoder()?;
+    let count = decoder.count();
+    let mut responses = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        let coordinates = decoder.try_response_decoder()?;
+        let slice = decoder.response_slice(coordinates);
+        let response = match decode_any(&upgrade(slice)?)? {
+            SpotMessage::CancelOrderResponse(decoder) => {
+                CanceledOpenOrder::Order(decode_cancel_order(decoder)?)
+            }
+            SpotMessage::CancelOrderListResponse(decoder) => {
+                CanceledOpenOrder::OrderList(decode_cancel_order_list(decoder)?)
+            }
+            message => {
+                bail!(
+                    "Unexpected template ID {} in cancel open orders response",
+                    message.template_id()
+                );
+            }
+        };
+        responses.push(response);
+    }
+    Ok(responses)
+}
+
+/// The `cancel_response` and `new_order_response` fields each embed a complete
+/// SBE message, header included, which is decoded in turn. Either is empty when
+/// the corresponding step was not attempted.
+pub fn decode_cancel_replace_order(
+    mut decoder: CancelReplaceOrderResponseDecoder<'_>,
+) -> anyhow::Result<CancelReplaceOrder> {
+    let cancel_result = decoder.cancel_result();
+    let new_order_result = decoder.new_order_result();
+
+    let coordinates = decoder.try_cancel_response_decoder()?;
+    let slice = decoder.cancel_response_slice(coordinates);
+    let cancel_response = if slice.is_empty() {
+        None
+    } else {
+        Some(match decode_any(&upgrade(slice)?)? {
+            SpotMessage::CancelOrderResponse(decoder) => {
+                CancelResponse::Canceled(decode_cancel_order(decoder)?)
+            }
+            SpotMessage::ErrorResponse(decoder) => CancelResponse::Error(decode_error(decoder)?),
+            message => {
+                bail!(
+                    "Unexpected template ID {} in cancel response",
+                    message.template_id()
+                );
+            }
+        })
+    };
+
+    let coordinates = decoder.try_new_order_response_decoder()?;
+    let slice = decoder.new_order_response_slice(coordinates);
+    let new_order_response = if slice.is_empty() {
+        None
+    } else {
+        Some(match decode_any(&upgrade(slice)?)? {
+            SpotMessage::NewOrderAckResponse(decoder) => {
+                NewOrderResponse::Ack(decode_new_order_ack(decoder)?)
+            }
+            SpotMessage::NewOrderResultResponse(decoder) => {
+                NewOrderResponse::Result(decode_new_order_result(decoder)?)
+            }
+            SpotMessage::NewOrderFullResponse(decoder) => {
+                NewOrderResponse::Full(decode_new_order_full(decoder)?)
+            }
+            SpotMessage::ErrorResponse(decoder) => NewOrderResponse::Error(decode_error(decoder)?),
+            message => {
+                bail!(
+                    "Unexpected template ID {} in new order response",
+                    message.template_id()
+                );
+            }
+        })
+    };
+
+    Ok(CancelReplaceOrder {
+        cancel_result,
+        new_order_result,
+        cancel_response,
+        new_order_response,
+    })
+}
+
+pub fn decode_order_amend_keep_priority(
+    decoder: OrderAmendKeepPriorityResponseDecoder<'_>,
+) -> anyhow::Result<OrderAmendKeepPriority> {
+    let price_exponent = decoder.price_exponent();
+    let qty_exponent = decoder.qty_exponent();
+
+    let mut list_status_decoder = decoder.try_list_status_decoder()?;
+    let count = list_status_decoder.count();
+    if count > 1 {
+        bail!("Unexpected list status count {count}");
+    }
+    let mut list_status = None;
+    for _ in 0..count {
+        list_status_decoder.try_advance()?;
+        let order_list_id = list_status_decoder.order_list_id();
+        let contingency_type = list_status_decoder.contingency_type();
+        let list_order_status = list_status_decoder.list_order_status();
+        let orders = decode_order_list_orders!(list_status_decoder);
+        list_status = Some(AmendListStatus {
+            order_list_id,
+            contingency_type,
+            list_order_status,
+            orders,
+            list_client_order_id: {
+                let coordinates = list_status_decoder.try_list_client_order_id_decoder()?;
+                let slice = list_status_decoder.list_client_order_id_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            symbol: {
+                let coordinates = list_status_decoder.try_symbol_decoder()?;
+                let slice = list_status_decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        });
+    }
+    let decoder = list_status_decoder.parent()?;
+
+    let mut related_orders_decoder = decoder.try_related_orders_decoder()?;
+    let count = related_orders_decoder.count();
+    let mut related_orders = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        related_orders_decoder.try_advance()?;
+        related_orders.push(decode_amended_order!(
+            related_orders_decoder,
+            price_exponent,
+            qty_exponent,
+            None
+        ));
+    }
+    let mut decoder = related_orders_decoder.parent()?;
+
+    Ok(OrderAmendKeepPriority {
+        transact_time: decoder.transact_time(),
+        execution_id: decoder.execution_id(),
+        amended_order: decode_amended_order!(
+            decoder,
+            price_exponent,
+            qty_exponent,
+            Some({
+                let coordinates = decoder.try_orig_client_order_id_decoder()?;
+                let slice = decoder.orig_client_order_id_slice(coordinates);
+                String::from_utf8(slice.into())?
+            })
+        ),
+        list_status,
+        related_orders,
+    })
+}
+
+pub fn decode_order_amendments(
+    decoder: OrderAmendmentsResponseDecoder<'_>,
+) -> anyhow::Result<Vec<OrderAmendment>> {
+    let mut decoder = decoder.try_amendments_decoder()?;
+    let count = decoder.count();
+    let mut amendments = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        let qty_exponent = decoder.qty_exponent();
+        amendments.push(OrderAmendment {
+            order_id: decoder.order_id(),
+            execution_id: decoder.execution_id(),
+            orig_qty: Decimal::new(decoder.orig_qty(), qty_exponent),
+            new_qty: Decimal::new(decoder.new_qty(), qty_exponent),
+            time: decoder.time(),
+            symbol: {
+                let coordinates = decoder.try_symbol_decoder()?;
+                let slice = decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            orig_client_order_id: {
+                let coordinates = decoder.try_orig_client_order_id_decoder()?;
+                let slice = decoder.orig_client_order_id_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            new_client_order_id: {
+                let coordinates = decoder.try_new_client_order_id_decoder()?;
+                let slice = decoder.new_client_order_id_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        });
+    }
+    Ok(amendments)
+}
+
+pub(crate) fn serialize_match_type<S: Serializer>(
+    val: &MatchType,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use MatchType::*;
+    let str_val = match val {
+        AutoMatch => "AUTO_MATCH",
+        OnePartyTradeReport => "ONE_PARTY_TRADE_REPORT",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+fn serialize_cancel_replace_status<S: Serializer>(
+    val: &CancelReplaceStatus,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use CancelReplaceStatus::*;
+    let str_val = match val {
+        Success => "SUCCESS",
+        Failure => "FAILURE",
+        NotAttempted => "NOT_ATTEMPTED",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
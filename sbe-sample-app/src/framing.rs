@@ -0,0 +1,96 @@
+use anyhow::bail;
+use spot_sbe::message_length::message_length;
+use std::str::FromStr;
+
+/// How messages are delimited in a capture of several SBE messages.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Framing {
+    /// Messages follow each other directly. Each one's length is found by
+    /// walking its groups and var-data fields.
+    #[default]
+    Sbe,
+    /// Each message is preceded by its length as a little-endian `u32`.
+    LengthPrefixed,
+}
+
+impl FromStr for Framing {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Ok(match s {
+            "sbe" => Self::Sbe,
+            "length-prefixed" => Self::LengthPrefixed,
+            _ => bail!("Unknown framing {s:?}; expected sbe or length-prefixed"),
+        })
+    }
+}
+
+/// A message found in a capture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Frame<'a> {
+    /// The offset of the message's frame in the capture.
+    pub offset: usize,
+    pub payload: &'a [u8],
+}
+
+/// Splits a capture into its messages.
+///
+/// Iteration stops after the first error, since the start of the next message
+/// is unknown once framing is lost.
+#[derive(Clone, Debug)]
+pub struct Frames<'a> {
+    data: &'a [u8],
+    offset: usize,
+    framing: Framing,
+}
+
+pub fn frames(data: &[u8], framing: Framing) -> Frames<'_> {
+    Frames {
+        data,
+        offset: 0,
+        framing,
+    }
+}
+
+impl<'a> Frames<'a> {
+    fn next_frame(&mut self) -> anyhow::Result<Frame<'a>> {
+        let offset = self.offset;
+        let rest = &self.data[offset..];
+        let (start, length): (usize, usize) = match self.framing {
+            Framing::Sbe => match message_length(rest) {
+                Ok(length) => (0, length),
+                Err(error) => bail!("Unframeable message at offset {offset}: {error}"),
+            },
+            Framing::LengthPrefixed => {
+                let Some(prefix) = rest.get(..4) else {
+                    bail!("Truncated length prefix at offset {offset}");
+                };
+                (4, u32::from_le_bytes(prefix.try_into()?).try_into()?)
+            }
+        };
+        let end = start + length;
+        let Some(payload) = rest.get(start..end) else {
+            bail!(
+                "Truncated message at offset {offset}: {length} bytes expected; {} available",
+                rest.len() - start
+            );
+        };
+        self.offset += end;
+        Ok(Frame { offset, payload })
+    }
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = anyhow::Result<Frame<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.data.len() {
+            return None;
+        }
+        let frame = self.next_frame();
+        if frame.is_err() {
+            self.offset = self.data.len();
+        }
+        Some(frame)
+    }
+}
@@ -0,0 +1,25 @@
+pub mod account;
+pub mod account_tracker;
+pub mod decimal;
+mod decode;
+pub mod encode;
+pub mod exchange_info;
+pub mod execution;
+pub mod framing;
+pub mod market_data;
+pub mod message;
+pub mod order;
+pub mod order_book;
+pub mod order_list_tracker;
+pub mod order_tracker;
+pub mod order_validation;
+pub mod output;
+pub mod rate_limit;
+pub mod reflect;
+pub mod render;
+pub mod response;
+pub mod rest_client;
+pub mod schema;
+pub mod user_data;
+pub mod websocket;
+pub mod websocket_client;
@@ -1,50 +1,51 @@
-use crate::{
-    exchange_info::{
-        Decimal, ErrorResponse, ExchangeFilter, ExchangeInfo, Sor, SymbolFilter, SymbolInfo,
-    },
-    rate_limit::RateLimit,
-    websocket::WebSocketMetadata,
-};
 use anyhow::bail;
-use spot_sbe::{
-    bool_enum::BoolEnum, error_response_codec, error_response_codec::ErrorResponseDecoder,
-    exchange_info_response_codec, exchange_info_response_codec::ExchangeInfoResponseDecoder,
-    exchange_max_num_algo_orders_filter_codec,
-    exchange_max_num_algo_orders_filter_codec::ExchangeMaxNumAlgoOrdersFilterDecoder,
-    exchange_max_num_iceberg_orders_filter_codec,
-    exchange_max_num_iceberg_orders_filter_codec::ExchangeMaxNumIcebergOrdersFilterDecoder,
-    exchange_max_num_order_lists_filter_codec,
-    exchange_max_num_order_lists_filter_codec::ExchangeMaxNumOrderListsFilterDecoder,
-    exchange_max_num_orders_filter_codec,
-    exchange_max_num_orders_filter_codec::ExchangeMaxNumOrdersFilterDecoder,
-    iceberg_parts_filter_codec, iceberg_parts_filter_codec::IcebergPartsFilterDecoder,
-    lot_size_filter_codec, lot_size_filter_codec::LotSizeFilterDecoder,
-    market_lot_size_filter_codec, market_lot_size_filter_codec::MarketLotSizeFilterDecoder,
-    max_num_algo_orders_filter_codec,
-    max_num_algo_orders_filter_codec::MaxNumAlgoOrdersFilterDecoder,
-    max_num_iceberg_orders_filter_codec,
-    max_num_iceberg_orders_filter_codec::MaxNumIcebergOrdersFilterDecoder,
-    max_num_order_amends_filter_codec,
-    max_num_order_amends_filter_codec::MaxNumOrderAmendsFilterDecoder,
-    max_num_order_lists_filter_codec,
-    max_num_order_lists_filter_codec::MaxNumOrderListsFilterDecoder, max_num_orders_filter_codec,
-    max_num_orders_filter_codec::MaxNumOrdersFilterDecoder, max_position_filter_codec,
-    max_position_filter_codec::MaxPositionFilterDecoder,
-    message_header_codec::MessageHeaderDecoder, min_notional_filter_codec,
-    min_notional_filter_codec::MinNotionalFilterDecoder, notional_filter_codec,
-    notional_filter_codec::NotionalFilterDecoder, percent_price_by_side_filter_codec,
-    percent_price_by_side_filter_codec::PercentPriceBySideFilterDecoder,
-    percent_price_filter_codec, percent_price_filter_codec::PercentPriceFilterDecoder,
-    price_filter_codec, price_filter_codec::PriceFilterDecoder, tp_lus_sell_filter_codec,
-    tp_lus_sell_filter_codec::TPlusSellFilterDecoder, trailing_delta_filter_codec,
-    trailing_delta_filter_codec::TrailingDeltaFilterDecoder, web_socket_response_codec,
-    web_socket_response_codec::WebSocketResponseDecoder, ReadBuf,
+use sbe_sample_app::{
+    decimal::DecimalFormat,
+    framing::{frames, Framing},
+    output::OutputFormat,
+    render::{render_payload, RenderedError},
+    rest_client::{RestClient, RestError},
+    websocket_client::{sbe_url, WebSocketClient},
+};
+use serde_json::value::RawValue;
+use std::{
+    fs::File,
+    io::{self, Read},
 };
-use std::io::{self, Read};
 
-mod exchange_info;
-mod rate_limit;
-mod websocket;
+const USAGE: &str = "\
+Usage: sbe-sample-app [OPTIONS] < payload
+       sbe-sample-app rest <url> [OPTIONS]
+       sbe-sample-app ws <url> [OPTIONS] < requests
+       sbe-sample-app stream [FILE] [OPTIONS] < capture
+
+Options:
+  -o, --output yaml|json|json-pretty|ndjson
+      --decimals string|mantissa-exponent
+      --framing sbe|length-prefixed
+      --keep-going    Skip undecodable messages in a stream. A message that cannot
+                      be framed still stops it, as the next one's start is unknown
+      --reflect       Decode with the embedded schema instead of the typed decoders";
+
+enum Command {
+    /// Decode a single SBE payload from STDIN.
+    Decode,
+    /// Send a GET request to a REST API URL and decode the response.
+    Rest { url: String },
+    /// Send the JSON requests from STDIN to a WebSocket API URL and decode each response.
+    WebSocket { url: String },
+    /// Decode every message of a capture read from a file or STDIN.
+    Stream { path: Option<String> },
+}
+
+struct Args {
+    command: Command,
+    output: OutputFormat,
+    decimals: DecimalFormat,
+    framing: Framing,
+    keep_going: bool,
+    reflect: bool,
+}
 
 fn read_payload(mut stream: impl Read) -> io::Result<Vec<u8>> {
     let mut payload = Vec::with_capacity(64 * 1024);
@@ -52,426 +53,177 @@ fn read_payload(mut stream: impl Read) -> io::Result<Vec<u8>> {
     Ok(payload)
 }
 
-fn into_bool(value: BoolEnum) -> anyhow::Result<bool> {
-    Ok(match value {
-        BoolEnum::True => true,
-        BoolEnum::False => false,
-        BoolEnum::NullVal => {
-            bail!("Bool value unexpectedly set to NullVal");
+fn parse_args(mut args: impl Iterator<Item = String>) -> anyhow::Result<Args> {
+    let mut command = Command::Decode;
+    let mut output = OutputFormat::default();
+    let mut decimals = DecimalFormat::default();
+    let mut framing = Framing::default();
+    let mut keep_going = false;
+    let mut reflect = false;
+    while let Some(arg) = args.next() {
+        if let Some(value) = arg.strip_prefix("--output=") {
+            output = value.parse()?;
+        } else if let Some(value) = arg.strip_prefix("--decimals=") {
+            decimals = value.parse()?;
+        } else if let Some(value) = arg.strip_prefix("--framing=") {
+            framing = value.parse()?;
+        } else if arg == "--output" || arg == "-o" || arg == "--decimals" || arg == "--framing" {
+            let Some(value) = args.next() else {
+                bail!("Missing value for {arg}\n{USAGE}");
+            };
+            match arg.as_str() {
+                "--decimals" => decimals = value.parse()?,
+                "--framing" => framing = value.parse()?,
+                _ => output = value.parse()?,
+            }
+        } else if arg == "--keep-going" {
+            keep_going = true;
+        } else if arg == "--reflect" {
+            reflect = true;
+        } else if arg == "stream" && matches!(command, Command::Decode) {
+            command = Command::Stream { path: None };
+        } else if (arg == "rest" || arg == "ws") && matches!(command, Command::Decode) {
+            let Some(url) = args.next() else {
+                bail!("Missing URL for {arg}\n{USAGE}");
+            };
+            command = if arg == "rest" {
+                Command::Rest { url }
+            } else {
+                Command::WebSocket { url }
+            };
+        } else if arg == "--help" || arg == "-h" {
+            println!("{USAGE}");
+            std::process::exit(0);
+        } else if let Command::Stream { path: path @ None } = &mut command {
+            *path = Some(arg);
+        } else {
+            bail!("Unexpected argument {arg:?}\n{USAGE}");
         }
+    }
+    Ok(Args {
+        command,
+        output,
+        decimals,
+        framing,
+        keep_going,
+        reflect,
     })
 }
 
-fn decode_error(header: MessageHeaderDecoder<ReadBuf<'_>>) -> anyhow::Result<ErrorResponse> {
-    let mut decoder = ErrorResponseDecoder::default().header(header, 0);
-    let response = ErrorResponse {
-        code: decoder.code(),
-        server_time: decoder.server_time(),
-        retry_after: decoder.retry_after(),
-        msg: {
-            let (offset, length) = decoder.msg_decoder();
-            let slice = decoder.msg_slice((offset, length));
-            String::from_utf8(slice.into())?
-        },
-    };
-    Ok(response)
+fn print_rendered(rendered: &str) {
+    // An empty NDJSON array renders to no lines at all.
+    if !rendered.is_empty() {
+        println!("{rendered}");
+    }
 }
 
-fn decode_exchange_filter(
-    header: MessageHeaderDecoder<ReadBuf<'_>>,
-) -> anyhow::Result<ExchangeFilter> {
-    Ok(match header.template_id() {
-        exchange_max_num_orders_filter_codec::SBE_TEMPLATE_ID => {
-            let decoder = ExchangeMaxNumOrdersFilterDecoder::default().header(header, 0);
-            ExchangeFilter::MaxNumOrders {
-                max_num_orders: decoder.max_num_orders(),
-            }
-        }
-        exchange_max_num_algo_orders_filter_codec::SBE_TEMPLATE_ID => {
-            let decoder = ExchangeMaxNumAlgoOrdersFilterDecoder::default().header(header, 0);
-            ExchangeFilter::MaxNumAlgoOrders {
-                max_num_algo_orders: decoder.max_num_algo_orders(),
-            }
-        }
+fn send_rest_request(
+    url: &str,
+    output: OutputFormat,
+    decimals: DecimalFormat,
+    reflect: bool,
+) -> anyhow::Result<()> {
+    match RestClient::new()?.fetch("GET", url) {
+        Ok(body) => print_rendered(&render_payload(&body, output, decimals, reflect)?),
+        Err(RestError::Api { error, .. }) => {
+            return Err(RenderedError(output.render(&error, decimals)?).into())
+        }
+        Err(RestError::Other(error)) => return Err(error),
+    }
+    Ok(())
+}
 
-        exchange_max_num_iceberg_orders_filter_codec::SBE_TEMPLATE_ID => {
-            let decoder = ExchangeMaxNumIcebergOrdersFilterDecoder::default().header(header, 0);
-            ExchangeFilter::MaxNumIcebergOrders {
-                max_num_iceberg_orders: decoder.max_num_iceberg_orders(),
-            }
-        }
-        exchange_max_num_order_lists_filter_codec::SBE_TEMPLATE_ID => {
-            let decoder = ExchangeMaxNumOrderListsFilterDecoder::default().header(header, 0);
-            ExchangeFilter::MaxNumOrderLists {
-                max_num_order_lists: decoder.max_num_order_lists(),
-            }
-        }
-        template_id => {
-            bail!("Unexpected exchange filter template ID: {template_id}");
-        }
-    })
+fn send_websocket_requests(
+    url: &str,
+    output: OutputFormat,
+    decimals: DecimalFormat,
+    reflect: bool,
+) -> anyhow::Result<()> {
+    let mut requests = String::new();
+    io::stdin().read_to_string(&mut requests)?;
+    let mut client = WebSocketClient::connect(&sbe_url(url))?;
+    // Requests may be concatenated or given one per line.
+    for request in serde_json::Deserializer::from_str(&requests).into_iter::<&RawValue>() {
+        let frame = client.request(request?.get())?;
+        print_rendered(&render_payload(&frame, output, decimals, reflect)?);
+    }
+    client.close()
 }
 
-fn decode_symbol_filter(header: MessageHeaderDecoder<ReadBuf<'_>>) -> anyhow::Result<SymbolFilter> {
-    Ok(match header.template_id() {
-        price_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = PriceFilterDecoder::default().header(header, 0);
-            let exponent = filter.price_exponent();
-            SymbolFilter::Price {
-                min_price: Decimal::new(filter.min_price(), exponent),
-                max_price: Decimal::new(filter.max_price(), exponent),
-                tick_size: Decimal::new(filter.tick_size(), exponent),
-            }
-        }
-        percent_price_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = PercentPriceFilterDecoder::default().header(header, 0);
-            let exponent = filter.multiplier_exponent();
-            SymbolFilter::PercentPrice {
-                multiplier_up: Decimal::new(filter.multiplier_up(), exponent),
-                multiplier_down: Decimal::new(filter.multiplier_down(), exponent),
-                avg_price_mins: filter.avg_price_mins(),
-            }
-        }
-        percent_price_by_side_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = PercentPriceBySideFilterDecoder::default().header(header, 0);
-            let exponent = filter.multiplier_exponent();
-            SymbolFilter::PercentPriceBySide {
-                bid_multiplier_up: Decimal::new(filter.bid_multiplier_up(), exponent),
-                bid_multiplier_down: Decimal::new(filter.bid_multiplier_down(), exponent),
-                ask_multiplier_up: Decimal::new(filter.ask_multiplier_up(), exponent),
-                ask_multiplier_down: Decimal::new(filter.ask_multiplier_down(), exponent),
-                avg_price_mins: filter.avg_price_mins(),
-            }
-        }
-        lot_size_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = LotSizeFilterDecoder::default().header(header, 0);
-            let exponent = filter.qty_exponent();
-            SymbolFilter::LotSize {
-                min_qty: Decimal::new(filter.min_qty(), exponent),
-                max_qty: Decimal::new(filter.max_qty(), exponent),
-                step_size: Decimal::new(filter.step_size(), exponent),
-            }
-        }
-        min_notional_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = MinNotionalFilterDecoder::default().header(header, 0);
-            let exponent = filter.price_exponent();
-            SymbolFilter::MinNotional {
-                min_notional: Decimal::new(filter.min_notional(), exponent),
-                apply_to_market: into_bool(filter.apply_to_market())?,
-                avg_price_mins: filter.avg_price_mins(),
-            }
-        }
-        notional_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = NotionalFilterDecoder::default().header(header, 0);
-            let exponent = filter.price_exponent();
-            SymbolFilter::Notional {
-                min_notional: Decimal::new(filter.min_notional(), exponent),
-                apply_min_to_market: into_bool(filter.apply_min_to_market())?,
-                max_notional: Decimal::new(filter.max_notional(), exponent),
-                apply_max_to_market: into_bool(filter.apply_max_to_market())?,
-                avg_price_mins: filter.avg_price_mins(),
-            }
-        }
-        iceberg_parts_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = IcebergPartsFilterDecoder::default().header(header, 0);
-            SymbolFilter::IcebergParts {
-                filter_limit: filter.filter_limit(),
-            }
-        }
-        market_lot_size_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = MarketLotSizeFilterDecoder::default().header(header, 0);
-            let exponent = filter.qty_exponent();
-            SymbolFilter::MarketLotSize {
-                min_qty: Decimal::new(filter.min_qty(), exponent),
-                max_qty: Decimal::new(filter.max_qty(), exponent),
-                step_size: Decimal::new(filter.step_size(), exponent),
-            }
-        }
-        max_num_orders_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = MaxNumOrdersFilterDecoder::default().header(header, 0);
-            SymbolFilter::MaxNumOrders {
-                max_num_orders: filter.max_num_orders(),
-            }
-        }
-        max_num_algo_orders_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = MaxNumAlgoOrdersFilterDecoder::default().header(header, 0);
-            SymbolFilter::MaxNumAlgoOrders {
-                max_num_algo_orders: filter.max_num_algo_orders(),
-            }
-        }
-        max_num_iceberg_orders_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = MaxNumIcebergOrdersFilterDecoder::default().header(header, 0);
-            SymbolFilter::MaxNumIcebergOrders {
-                max_num_iceberg_orders: filter.max_num_iceberg_orders(),
-            }
-        }
-        max_num_order_amends_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = MaxNumOrderAmendsFilterDecoder::default().header(header, 0);
-            SymbolFilter::MaxNumOrderAmends {
-                max_num_order_amends: filter.max_num_order_amends(),
-            }
-        }
-        max_num_order_lists_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = MaxNumOrderListsFilterDecoder::default().header(header, 0);
-            SymbolFilter::MaxNumOrderLists {
-                max_num_order_lists: filter.max_num_order_lists(),
-            }
-        }
-        max_position_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = MaxPositionFilterDecoder::default().header(header, 0);
-            let exponent = filter.qty_exponent();
-            SymbolFilter::MaxPosition {
-                max_position: Decimal::new(filter.max_position(), exponent),
-            }
-        }
-        trailing_delta_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = TrailingDeltaFilterDecoder::default().header(header, 0);
-            SymbolFilter::TrailingDelta {
-                min_trailing_above_delta: filter.min_trailing_above_delta(),
-                max_trailing_above_delta: filter.max_trailing_above_delta(),
-                min_trailing_below_delta: filter.min_trailing_below_delta(),
-                max_trailing_below_delta: filter.max_trailing_below_delta(),
+/// Decodes and prints each message of a capture. Undecodable messages fail the
+/// whole stream unless `keep_going` is set, in which case they are reported on
+/// STDERR with their offset. Framing errors fail the stream either way, after
+/// the messages before them were printed.
+fn decode_stream(
+    path: Option<&str>,
+    framing: Framing,
+    keep_going: bool,
+    output: OutputFormat,
+    decimals: DecimalFormat,
+    reflect: bool,
+) -> anyhow::Result<()> {
+    let capture = match path {
+        Some(path) => read_payload(File::open(path)?)?,
+        None => read_payload(io::stdin())?,
+    };
+    for frame in frames(&capture, framing) {
+        let frame = match frame {
+            Ok(frame) => frame,
+            Err(error) if keep_going => {
+                bail!("{error}; the rest of the stream cannot be framed, so it is not decoded")
             }
-        }
-        tp_lus_sell_filter_codec::SBE_TEMPLATE_ID => {
-            let filter = TPlusSellFilterDecoder::default().header(header, 0);
-            SymbolFilter::TPlusSell {
-                end_time: filter.end_time(),
+            Err(error) => return Err(error),
+        };
+        match render_payload(frame.payload, output, decimals, reflect) {
+            Ok(rendered) => print_rendered(&rendered),
+            Err(error) if keep_going => {
+                eprintln!(
+                    "Warning: Skipping message at offset {}: {error}",
+                    frame.offset
+                )
             }
+            Err(error) => bail!("Message at offset {}: {error}", frame.offset),
         }
-        template_id => {
-            bail!("Unexpected symbol filter message ID: {template_id}");
-        }
-    })
-}
-
-fn decode_websocket_metadata(
-    header: MessageHeaderDecoder<ReadBuf<'_>>,
-) -> anyhow::Result<(WebSocketMetadata, usize)> {
-    let decoder = WebSocketResponseDecoder::default().header(header, 0);
-    if into_bool(decoder.sbe_schema_id_version_deprecated())? {
-        println!("Warning: sbe-sample-app is using a deprecated schema");
     }
-    let status = decoder.status();
-    let mut decoder = decoder.rate_limits_decoder();
-    let count = decoder.count();
-    let mut rate_limits = Vec::with_capacity(count.try_into()?);
-    for _ in 0..count {
-        decoder.advance()?;
-        let rate_limit = RateLimit {
-            rate_limit_type: decoder.rate_limit_type(),
-            interval: decoder.interval(),
-            interval_num: decoder.interval_num(),
-            limit: decoder.rate_limit(),
-            count: Some(decoder.current()),
-        };
-        rate_limits.push(rate_limit);
-    }
-    let mut decoder = decoder.parent()?;
-    let coordinates = decoder.id_decoder();
-    let id = decoder.id_slice(coordinates);
-    let id = String::from_utf8(id.to_vec())?;
-    let response = WebSocketMetadata::new(status, rate_limits, id);
-    let coordinates = decoder.result_decoder();
-    Ok((response, coordinates.0))
+    Ok(())
 }
 
-fn main() -> anyhow::Result<()> {
-    let payload = read_payload(io::stdin())?;
-    let mut decoder = MessageHeaderDecoder::default().wrap(ReadBuf::new(&payload), 0);
-    // A separate "ErrorResponse" message is returned for errors and its format
-    // is expected to be backwards compatible across all schema IDs.
-    if decoder.template_id() == error_response_codec::SBE_TEMPLATE_ID {
-        let response = decode_error(decoder)?;
-        let yaml = serde_yaml::to_string(&response)?;
-        bail!(yaml);
-    }
-    let mut websocket_meta = None;
-    let schema_id = decoder.schema_id();
-    if schema_id != exchange_info_response_codec::SBE_SCHEMA_ID {
-        bail!(
-            "Unexpected schema ID. Got {schema_id}; expected {}",
-            exchange_info_response_codec::SBE_SCHEMA_ID
-        );
-    }
-    let version = decoder.version();
-    if version != exchange_info_response_codec::SBE_SCHEMA_VERSION {
-        println!(
-            "Warning: Unexpected schema version. Got {version}; expected {}",
-            exchange_info_response_codec::SBE_SCHEMA_VERSION,
-        );
-        // Schemas with the same ID are expected to be backwards compatible.
+fn run() -> anyhow::Result<()> {
+    let args = parse_args(std::env::args().skip(1))?;
+    match args.command {
+        Command::Decode => {
+            let payload = read_payload(io::stdin())?;
+            print_rendered(&render_payload(
+                &payload,
+                args.output,
+                args.decimals,
+                args.reflect,
+            )?);
+            Ok(())
+        }
+        Command::Rest { url } => send_rest_request(&url, args.output, args.decimals, args.reflect),
+        Command::WebSocket { url } => {
+            send_websocket_requests(&url, args.output, args.decimals, args.reflect)
+        }
+        Command::Stream { path } => decode_stream(
+            path.as_deref(),
+            args.framing,
+            args.keep_going,
+            args.output,
+            args.decimals,
+            args.reflect,
+        ),
     }
-    if decoder.template_id() == web_socket_response_codec::SBE_TEMPLATE_ID {
-        let (websocket, offset) = decode_websocket_metadata(decoder)?;
-        websocket_meta = Some(websocket);
-        decoder = MessageHeaderDecoder::default().wrap(ReadBuf::new(&payload[offset..]), 0);
-        if decoder.template_id() == error_response_codec::SBE_TEMPLATE_ID {
-            let response = decode_error(decoder)?;
-            let yaml = if let Some(websocket_meta) = websocket_meta.as_mut() {
-                websocket_meta.set_error(response);
-                serde_yaml::to_string(&websocket_meta)?
-            } else {
-                serde_yaml::to_string(&response)?
-            };
-            bail!(yaml);
-        }
-    }
-    if decoder.template_id() != exchange_info_response_codec::SBE_TEMPLATE_ID {
-        bail!("Unexpected template ID {}", decoder.template_id());
-    }
-    let decoder = ExchangeInfoResponseDecoder::default().header(decoder, 0);
-    let mut decoder = decoder.rate_limits_decoder();
-    let count = decoder.count();
-    let mut rate_limits = Vec::with_capacity(count.try_into()?);
-    for _ in 0..count {
-        decoder.advance()?;
-        let rate_limit = RateLimit {
-            rate_limit_type: decoder.rate_limit_type(),
-            interval: decoder.interval(),
-            interval_num: decoder.interval_num(),
-            limit: decoder.rate_limit(),
-            count: None,
-        };
-        rate_limits.push(rate_limit);
-    }
-    let decoder = decoder.parent()?;
-
-    let mut decoder = decoder.exchange_filters_decoder();
-    let count = decoder.count();
-    let mut exchange_filters = Vec::with_capacity(count.try_into()?);
-    for _ in 0..count {
-        decoder.advance()?;
-        let coordinates = decoder.filter_decoder();
-        let slice = decoder.filter_slice(coordinates);
-        let decoder = MessageHeaderDecoder::default().wrap(ReadBuf::new(slice), 0);
-        let filter = decode_exchange_filter(decoder)?;
-        exchange_filters.push(filter);
-    }
-    let decoder = decoder.parent()?;
-
-    let mut decoder = decoder.symbols_decoder();
-    let count = decoder.count().try_into()?;
-    let mut symbols = Vec::with_capacity(count);
-    for _ in 0..count {
-        decoder.advance()?;
-        symbols.push(SymbolInfo {
-            status: decoder.status(),
-            base_asset_precision: decoder.base_asset_precision(),
-            quote_asset_precision: decoder.quote_asset_precision(),
-            base_commission_precision: decoder.base_commission_precision(),
-            quote_commission_precision: decoder.quote_commission_precision(),
-            order_types: decoder.order_types(),
-            iceberg_allowed: into_bool(decoder.iceberg_allowed())?,
-            oco_allowed: into_bool(decoder.oco_allowed())?,
-            oto_allowed: into_bool(decoder.oto_allowed())?,
-            quote_order_qty_market_allowed: into_bool(decoder.quote_order_qty_market_allowed())?,
-            allow_trailing_stop: into_bool(decoder.allow_trailing_stop())?,
-            cancel_replace_allowed: into_bool(decoder.cancel_replace_allowed())?,
-            amend_allowed: into_bool(decoder.amend_allowed())?,
-            is_spot_trading_allowed: into_bool(decoder.is_spot_trading_allowed())?,
-            is_margin_trading_allowed: into_bool(decoder.is_margin_trading_allowed())?,
-            default_self_trade_prevention_mode: decoder.default_self_trade_prevention_mode(),
-            allowed_self_trade_prevention_modes: decoder.allowed_self_trade_prevention_modes(),
-            peg_instructions_allowed: Some(into_bool(decoder.peg_instructions_allowed())?),
-            filters: {
-                let mut filters_decoder = decoder.filters_decoder();
-                let count = filters_decoder.count().try_into()?;
-                let mut filters = Vec::with_capacity(count);
-                for _ in 0..count {
-                    filters_decoder.advance()?;
-                    let coordinates = filters_decoder.filter_decoder();
-                    let slice = filters_decoder.filter_slice(coordinates);
-                    let header = MessageHeaderDecoder::default().wrap(ReadBuf::new(slice), 0);
-                    let filter = decode_symbol_filter(header)?;
-                    filters.push(filter);
-                }
-                decoder = filters_decoder.parent()?;
-                filters
-            },
-            permission_sets: {
-                let mut permissions_sets_decoder = decoder.permission_sets_decoder();
-                let count = permissions_sets_decoder.count().try_into()?;
-                let mut all_permissions = Vec::with_capacity(count);
-
-                for _ in 0..count {
-                    permissions_sets_decoder.advance()?;
-
-                    let mut permissions_decoder = permissions_sets_decoder.permissions_decoder();
-                    let count = permissions_decoder.count().try_into()?;
-                    let mut permission_sets = Vec::with_capacity(count);
-
-                    for _ in 0..count {
-                        permissions_decoder.advance()?;
-                        let coordinates = permissions_decoder.permission_decoder();
-                        let slice = permissions_decoder.permission_slice(coordinates);
-                        permission_sets.push(String::from_utf8(slice.into())?);
-                    }
-
-                    // IMPORTANT: give back the parent so we can use it again
-                    permissions_sets_decoder = permissions_decoder.parent()?;
-
-                    all_permissions.push(permission_sets);
-                }
-
-                // now we can go back up another level
-                decoder = permissions_sets_decoder.parent()?;
-                all_permissions
-            },
-            symbol: {
-                let coordinates = decoder.symbol_decoder();
-                let slice = decoder.symbol_slice(coordinates);
-                String::from_utf8(slice.into())?
-            },
-            base_asset: {
-                let coordinates = decoder.base_asset_decoder();
-                let slice = decoder.base_asset_slice(coordinates);
-                String::from_utf8(slice.into())?
-            },
-            quote_asset: {
-                let coordinates = decoder.quote_asset_decoder();
-                let slice = decoder.quote_asset_slice(coordinates);
-                String::from_utf8(slice.into())?
-            },
-        });
-    }
-    let decoder = decoder.parent()?;
+}
 
-    let mut decoder = decoder.sors_decoder();
-    let count = decoder.count().try_into()?;
-    let mut sors = Vec::with_capacity(count);
-    for _ in 0..count {
-        decoder.advance()?;
-        let mut symbols_decoder = decoder.sor_symbols_decoder();
-        let count = symbols_decoder.count().try_into()?;
-        let mut symbols = Vec::with_capacity(count);
-        for _ in 0..count {
-            symbols_decoder.advance()?;
-            let coordinates = symbols_decoder.symbol_decoder();
-            let slice = symbols_decoder.symbol_slice(coordinates);
-            symbols.push(String::from_utf8(slice.into())?);
+fn main() {
+    if let Err(error) = run() {
+        // Error responses are printed as the document they were rendered to,
+        // so that `-o json` output can be piped to other tools.
+        match error.downcast_ref::<RenderedError>() {
+            Some(rendered) => eprintln!("{rendered}"),
+            None => eprintln!("Error: {error:?}"),
         }
-        decoder = symbols_decoder.parent()?;
-        let coordinates = decoder.base_asset_decoder();
-        let slice = decoder.base_asset_slice(coordinates);
-        let base_asset = String::from_utf8(slice.into())?;
-        sors.push(Sor {
-            symbols,
-            base_asset,
-        });
+        std::process::exit(1);
     }
-    let response = ExchangeInfo {
-        rate_limits,
-        exchange_filters,
-        symbols,
-        sors,
-    };
-    let yaml = if let Some(websocket_meta) = websocket_meta.as_mut() {
-        websocket_meta.set_exchange_info(response);
-        serde_yaml::to_string(&websocket_meta)?
-    } else {
-        serde_yaml::to_string(&response)?
-    };
-    println!("{}", yaml);
-    Ok(())
 }
@@ -0,0 +1,567 @@
+use crate::{
+    decimal::Decimal,
+    decode::{into_bool, preallocated},
+};
+use serde::{ser::SerializeTuple, Serialize, Serializer};
+use spot_sbe::{
+    agg_trades_response_codec::AggTradesResponseDecoder,
+    average_price_response_codec::AveragePriceResponseDecoder,
+    book_ticker_response_codec::BookTickerResponseDecoder,
+    book_ticker_symbol_response_codec::BookTickerSymbolResponseDecoder,
+    depth_response_codec::DepthResponseDecoder, klines_response_codec::KlinesResponseDecoder,
+    price_ticker_response_codec::PriceTickerResponseDecoder,
+    price_ticker_symbol_response_codec::PriceTickerSymbolResponseDecoder,
+    ticker_24_hf_ull_response_codec::Ticker24hFullResponseDecoder,
+    ticker_24_hm_ini_response_codec::Ticker24hMiniResponseDecoder,
+    ticker_24_hs_ymbol_full_response_codec::Ticker24hSymbolFullResponseDecoder,
+    ticker_24_hs_ymbol_mini_response_codec::Ticker24hSymbolMiniResponseDecoder,
+    ticker_full_response_codec::TickerFullResponseDecoder,
+    ticker_mini_response_codec::TickerMiniResponseDecoder,
+    ticker_symbol_full_response_codec::TickerSymbolFullResponseDecoder,
+    ticker_symbol_mini_response_codec::TickerSymbolMiniResponseDecoder,
+    trades_response_codec::TradesResponseDecoder,
+};
+
+/// Serialized as a `[price, qty]` pair, as in the JSON API.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PriceLevel {
+    pub price: Decimal,
+    pub qty: Decimal,
+}
+
+impl Serialize for PriceLevel {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.price)?;
+        tuple.serialize_element(&self.qty)?;
+        tuple.end()
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Depth {
+    pub last_update_id: i64,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Trade {
+    pub id: i64,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub quote_qty: Decimal,
+    pub time: i64,
+    pub is_buyer_maker: bool,
+    pub is_best_match: bool,
+}
+
+/// Serialized with the single-letter keys of the JSON API.
+#[derive(Serialize)]
+pub struct AggTrade {
+    #[serde(rename = "a")]
+    pub agg_trade_id: i64,
+    #[serde(rename = "p")]
+    pub price: Decimal,
+    #[serde(rename = "q")]
+    pub qty: Decimal,
+    #[serde(rename = "f")]
+    pub first_trade_id: i64,
+    #[serde(rename = "l")]
+    pub last_trade_id: i64,
+    #[serde(rename = "T")]
+    pub time: i64,
+    #[serde(rename = "m")]
+    pub is_buyer_maker: bool,
+    #[serde(rename = "M")]
+    pub is_best_match: bool,
+}
+
+/// Serialized as an array in the field order of the JSON API.
+pub struct Kline {
+    pub open_time: i64,
+    pub open_price: Decimal,
+    pub high_price: Decimal,
+    pub low_price: Decimal,
+    pub close_price: Decimal,
+    pub volume: Decimal,
+    pub close_time: i64,
+    pub quote_volume: Decimal,
+    pub num_trades: i64,
+    pub taker_buy_base_volume: Decimal,
+    pub taker_buy_quote_volume: Decimal,
+}
+
+impl Serialize for Kline {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(12)?;
+        tuple.serialize_element(&self.open_time)?;
+        tuple.serialize_element(&self.open_price)?;
+        tuple.serialize_element(&self.high_price)?;
+        tuple.serialize_element(&self.low_price)?;
+        tuple.serialize_element(&self.close_price)?;
+        tuple.serialize_element(&self.volume)?;
+        tuple.serialize_element(&self.close_time)?;
+        tuple.serialize_element(&self.quote_volume)?;
+        tuple.serialize_element(&self.num_trades)?;
+        tuple.serialize_element(&self.taker_buy_base_volume)?;
+        tuple.serialize_element(&self.taker_buy_quote_volume)?;
+        // The JSON API ends every kline with an unused "0" entry.
+        tuple.serialize_element("0")?;
+        tuple.end()
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AveragePrice {
+    pub mins: i64,
+    pub price: Option<Decimal>,
+    pub close_time: Option<i64>,
+}
+
+/// 24hr rolling window statistics, as returned by `ticker/24hr` with `type=FULL`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ticker24h {
+    pub symbol: String,
+    pub price_change: Option<Decimal>,
+    pub price_change_percent: Option<f32>,
+    pub weighted_avg_price: Option<Decimal>,
+    pub prev_close_price: Option<Decimal>,
+    pub last_price: Option<Decimal>,
+    pub last_qty: Decimal,
+    pub bid_price: Option<Decimal>,
+    pub bid_qty: Decimal,
+    pub ask_price: Option<Decimal>,
+    pub ask_qty: Decimal,
+    pub open_price: Option<Decimal>,
+    pub high_price: Option<Decimal>,
+    pub low_price: Option<Decimal>,
+    pub volume: Decimal,
+    pub quote_volume: Decimal,
+    pub open_time: i64,
+    pub close_time: i64,
+    pub first_id: Option<i64>,
+    pub last_id: Option<i64>,
+    pub count: i64,
+}
+
+/// Rolling window statistics, as returned by `ticker` with `type=FULL`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ticker {
+    pub symbol: String,
+    pub price_change: Option<Decimal>,
+    pub price_change_percent: Option<f32>,
+    pub weighted_avg_price: Option<Decimal>,
+    pub open_price: Option<Decimal>,
+    pub high_price: Option<Decimal>,
+    pub low_price: Option<Decimal>,
+    pub last_price: Option<Decimal>,
+    pub volume: Decimal,
+    pub quote_volume: Decimal,
+    pub open_time: i64,
+    pub close_time: i64,
+    pub first_id: Option<i64>,
+    pub last_id: Option<i64>,
+    pub count: i64,
+}
+
+/// Statistics returned by both `ticker/24hr` and `ticker` with `type=MINI`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MiniTicker {
+    pub symbol: String,
+    pub open_price: Option<Decimal>,
+    pub high_price: Option<Decimal>,
+    pub low_price: Option<Decimal>,
+    pub last_price: Option<Decimal>,
+    pub volume: Decimal,
+    pub quote_volume: Decimal,
+    pub open_time: i64,
+    pub close_time: i64,
+    pub first_id: Option<i64>,
+    pub last_id: Option<i64>,
+    pub count: i64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceTicker {
+    pub symbol: String,
+    pub price: Option<Decimal>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BookTicker {
+    pub symbol: String,
+    pub bid_price: Option<Decimal>,
+    pub bid_qty: Decimal,
+    pub ask_price: Option<Decimal>,
+    pub ask_qty: Decimal,
+}
+
+// The single-symbol responses and the entries of the multi-symbol groups
+// expose identical accessors on unrelated decoder types, so the field mapping
+// is shared through macros rather than duplicated per decoder.
+macro_rules! decode_ticker_24h {
+    ($decoder:ident) => {{
+        let price_exponent = $decoder.price_exponent();
+        let qty_exponent = $decoder.qty_exponent();
+        let price = |mantissa: i64| Decimal::new(mantissa, price_exponent);
+        Ticker24h {
+            price_change: $decoder.price_change().map(price),
+            price_change_percent: $decoder.price_change_percent(),
+            weighted_avg_price: $decoder.weighted_avg_price().map(price),
+            prev_close_price: $decoder.prev_close_price().map(price),
+            last_price: $decoder.last_price().map(price),
+            last_qty: Decimal::from_le_bytes($decoder.last_qty(), qty_exponent),
+            bid_price: $decoder.bid_price().map(price),
+            bid_qty: Decimal::new($decoder.bid_qty(), qty_exponent),
+            ask_price: $decoder.ask_price().map(price),
+            ask_qty: Decimal::new($decoder.ask_qty(), qty_exponent),
+            open_price: $decoder.open_price().map(price),
+            high_price: $decoder.high_price().map(price),
+            low_price: $decoder.low_price().map(price),
+            volume: Decimal::from_le_bytes($decoder.volume(), qty_exponent),
+            quote_volume: Decimal::from_le_bytes($decoder.quote_volume(), price_exponent),
+            open_time: $decoder.open_time(),
+            close_time: $decoder.close_time(),
+            first_id: $decoder.first_id(),
+            last_id: $decoder.last_id(),
+            count: $decoder.num_trades(),
+            symbol: {
+                let coordinates = $decoder.try_symbol_decoder()?;
+                let slice = $decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        }
+    }};
+}
+
+macro_rules! decode_ticker {
+    ($decoder:ident) => {{
+        let price_exponent = $decoder.price_exponent();
+        let qty_exponent = $decoder.qty_exponent();
+        let price = |mantissa: i64| Decimal::new(mantissa, price_exponent);
+        Ticker {
+            price_change: $decoder.price_change().map(price),
+            price_change_percent: $decoder.price_change_percent(),
+            weighted_avg_price: $decoder.weighted_avg_price().map(price),
+            open_price: $decoder.open_price().map(price),
+            high_price: $decoder.high_price().map(price),
+            low_price: $decoder.low_price().map(price),
+            last_price: $decoder.last_price().map(price),
+            volume: Decimal::from_le_bytes($decoder.volume(), qty_exponent),
+            quote_volume: Decimal::from_le_bytes($decoder.quote_volume(), price_exponent),
+            open_time: $decoder.open_time(),
+            close_time: $decoder.close_time(),
+            first_id: $decoder.first_id(),
+            last_id: $decoder.last_id(),
+            count: $decoder.num_trades(),
+            symbol: {
+                let coordinates = $decoder.try_symbol_decoder()?;
+                let slice = $decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        }
+    }};
+}
+
+macro_rules! decode_mini_ticker {
+    ($decoder:ident) => {{
+        let price_exponent = $decoder.price_exponent();
+        let qty_exponent = $decoder.qty_exponent();
+        let price = |mantissa: i64| Decimal::new(mantissa, price_exponent);
+        MiniTicker {
+            open_price: $decoder.open_price().map(price),
+            high_price: $decoder.high_price().map(price),
+            low_price: $decoder.low_price().map(price),
+            last_price: $decoder.last_price().map(price),
+            volume: Decimal::from_le_bytes($decoder.volume(), qty_exponent),
+            quote_volume: Decimal::from_le_bytes($decoder.quote_volume(), price_exponent),
+            open_time: $decoder.open_time(),
+            close_time: $decoder.close_time(),
+            first_id: $decoder.first_id(),
+            last_id: $decoder.last_id(),
+            count: $decoder.num_trades(),
+            symbol: {
+                let coordinates = $decoder.try_symbol_decoder()?;
+                let slice = $decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        }
+    }};
+}
+
+macro_rules! decode_price_ticker {
+    ($decoder:ident) => {{
+        let price_exponent = $decoder.price_exponent();
+        PriceTicker {
+            price: $decoder
+                .price()
+                .map(|mantissa| Decimal::new(mantissa, price_exponent)),
+            symbol: {
+                let coordinates = $decoder.try_symbol_decoder()?;
+                let slice = $decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        }
+    }};
+}
+
+macro_rules! decode_book_ticker {
+    ($decoder:ident) => {{
+        let price_exponent = $decoder.price_exponent();
+        let qty_exponent = $decoder.qty_exponent();
+        let price = |mantissa: i64| Decimal::new(mantissa, price_exponent);
+        BookTicker {
+            bid_price: $decoder.bid_price().map(price),
+            bid_qty: Decimal::new($decoder.bid_qty(), qty_exponent),
+            ask_price: $decoder.ask_price().map(price),
+            ask_qty: Decimal::new($decoder.ask_qty(), qty_exponent),
+            symbol: {
+                let coordinates = $decoder.try_symbol_decoder()?;
+                let slice = $decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        }
+    }};
+}
+
+pub fn decode_depth(decoder: DepthResponseDecoder<'_>) -> anyhow::Result<Depth> {
+    let last_update_id = decoder.last_update_id();
+    let price_exponent = decoder.price_exponent();
+    let qty_exponent = decoder.qty_exponent();
+
+    let mut decoder = decoder.try_bids_decoder()?;
+    let count = decoder.count();
+    let mut bids = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        bids.push(PriceLevel {
+            price: Decimal::new(decoder.price(), price_exponent),
+            qty: Decimal::new(decoder.qty(), qty_exponent),
+        });
+    }
+    let decoder = decoder.parent()?;
+
+    let mut decoder = decoder.try_asks_decoder()?;
+    let count = decoder.count();
+    let mut asks = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        asks.push(PriceLevel {
+            price: Decimal::new(decoder.price(), price_exponent),
+            qty: Decimal::new(decoder.qty(), qty_exponent),
+        });
+    }
+    Ok(Depth {
+        last_update_id,
+        bids,
+        asks,
+    })
+}
+
+pub fn decode_trades(decoder: TradesResponseDecoder<'_>) -> anyhow::Result<Vec<Trade>> {
+    let price_exponent = decoder.price_exponent();
+    let qty_exponent = decoder.qty_exponent();
+    let mut decoder = decoder.try_trades_decoder()?;
+    let count = decoder.count();
+    let mut trades = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        trades.push(Trade {
+            id: decoder.id(),
+            price: Decimal::new(decoder.price(), price_exponent),
+            qty: Decimal::new(decoder.qty(), qty_exponent),
+            quote_qty: Decimal::new(decoder.quote_qty(), price_exponent),
+            time: decoder.time(),
+            is_buyer_maker: into_bool(decoder.is_buyer_maker())?,
+            is_best_match: into_bool(decoder.is_best_match())?,
+        });
+    }
+    Ok(trades)
+}
+
+pub fn decode_agg_trades(decoder: AggTradesResponseDecoder<'_>) -> anyhow::Result<Vec<AggTrade>> {
+    let price_exponent = decoder.price_exponent();
+    let qty_exponent = decoder.qty_exponent();
+    let mut decoder = decoder.try_agg_trades_decoder()?;
+    let count = decoder.count();
+    let mut agg_trades = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        agg_trades.push(AggTrade {
+            agg_trade_id: decoder.agg_trade_id(),
+            price: Decimal::new(decoder.price(), price_exponent),
+            qty: Decimal::new(decoder.qty(), qty_exponent),
+            first_trade_id: decoder.first_trade_id(),
+            last_trade_id: decoder.last_trade_id(),
+            time: decoder.time(),
+            is_buyer_maker: into_bool(decoder.is_buyer_maker())?,
+            is_best_match: into_bool(decoder.is_best_match())?,
+        });
+    }
+    Ok(agg_trades)
+}
+
+pub fn decode_klines(decoder: KlinesResponseDecoder<'_>) -> anyhow::Result<Vec<Kline>> {
+    let price_exponent = decoder.price_exponent();
+    let qty_exponent = decoder.qty_exponent();
+    let mut decoder = decoder.try_klines_decoder()?;
+    let count = decoder.count();
+    let mut klines = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        klines.push(Kline {
+            open_time: decoder.open_time(),
+            open_price: Decimal::new(decoder.open_price(), price_exponent),
+            high_price: Decimal::new(decoder.high_price(), price_exponent),
+            low_price: Decimal::new(decoder.low_price(), price_exponent),
+            close_price: Decimal::new(decoder.close_price(), price_exponent),
+            volume: Decimal::from_le_bytes(decoder.volume(), qty_exponent),
+            close_time: decoder.close_time(),
+            quote_volume: Decimal::from_le_bytes(decoder.quote_volume(), price_exponent),
+            num_trades: decoder.num_trades(),
+            taker_buy_base_volume: Decimal::from_le_bytes(
+                decoder.taker_buy_base_volume(),
+                qty_exponent,
+            ),
+            taker_buy_quote_volume: Decimal::from_le_bytes(
+                decoder.taker_buy_quote_volume(),
+                price_exponent,
+            ),
+        });
+    }
+    Ok(klines)
+}
+
+pub fn decode_average_price(
+    decoder: AveragePriceResponseDecoder<'_>,
+) -> anyhow::Result<AveragePrice> {
+    let price_exponent = decoder.price_exponent();
+    Ok(AveragePrice {
+        mins: decoder.mins(),
+        price: decoder
+            .price()
+            .map(|mantissa| Decimal::new(mantissa, price_exponent)),
+        close_time: decoder.close_time(),
+    })
+}
+
+pub fn decode_ticker_24h_symbol_full(
+    mut decoder: Ticker24hSymbolFullResponseDecoder<'_>,
+) -> anyhow::Result<Ticker24h> {
+    Ok(decode_ticker_24h!(decoder))
+}
+
+pub fn decode_ticker_24h_full(
+    decoder: Ticker24hFullResponseDecoder<'_>,
+) -> anyhow::Result<Vec<Ticker24h>> {
+    let mut decoder = decoder.try_tickers_decoder()?;
+    let count = decoder.count();
+    let mut tickers = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        tickers.push(decode_ticker_24h!(decoder));
+    }
+    Ok(tickers)
+}
+
+pub fn decode_ticker_24h_symbol_mini(
+    mut decoder: Ticker24hSymbolMiniResponseDecoder<'_>,
+) -> anyhow::Result<MiniTicker> {
+    Ok(decode_mini_ticker!(decoder))
+}
+
+pub fn decode_ticker_24h_mini(
+    decoder: Ticker24hMiniResponseDecoder<'_>,
+) -> anyhow::Result<Vec<MiniTicker>> {
+    let mut decoder = decoder.try_tickers_decoder()?;
+    let count = decoder.count();
+    let mut tickers = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        tickers.push(decode_mini_ticker!(decoder));
+    }
+    Ok(tickers)
+}
+
+pub fn decode_ticker_symbol_full(
+    mut decoder: TickerSymbolFullResponseDecoder<'_>,
+) -> anyhow::Result<Ticker> {
+    Ok(decode_ticker!(decoder))
+}
+
+pub fn decode_ticker_full(decoder: TickerFullResponseDecoder<'_>) -> anyhow::Result<Vec<Ticker>> {
+    let mut decoder = decoder.try_tickers_decoder()?;
+    let count = decoder.count();
+    let mut tickers = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        tickers.push(decode_ticker!(decoder));
+    }
+    Ok(tickers)
+}
+
+pub fn decode_ticker_symbol_mini(
+    mut decoder: TickerSymbolMiniResponseDecoder<'_>,
+) -> anyhow::Result<MiniTicker> {
+    Ok(decode_mini_ticker!(decoder))
+}
+
+pub fn decode_ticker_mini(
+    decoder: TickerMiniResponseDecoder<'_>,
+) -> anyhow::Result<Vec<MiniTicker>> {
+    let mut decoder = decoder.try_tickers_decoder()?;
+    let count = decoder.count();
+    let mut tickers = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        tickers.push(decode_mini_ticker!(decoder));
+    }
+    Ok(tickers)
+}
+
+pub fn decode_price_ticker_symbol(
+    mut decoder: PriceTickerSymbolResponseDecoder<'_>,
+) -> anyhow::Result<PriceTicker> {
+    Ok(decode_price_ticker!(decoder))
+}
+
+pub fn decode_price_ticker(
+    decoder: PriceTickerResponseDecoder<'_>,
+) -> anyhow::Result<Vec<PriceTicker>> {
+    let mut decoder = decoder.try_tickers_decoder()?;
+    let count = decoder.count();
+    let mut tickers = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        tickers.push(decode_price_ticker!(decoder));
+    }
+    Ok(tickers)
+}
+
+pub fn decode_book_ticker_symbol(
+    mut decoder: BookTickerSymbolResponseDecoder<'_>,
+) -> anyhow::Result<BookTicker> {
+    Ok(decode_book_ticker!(decoder))
+}
+
+pub fn decode_book_ticker(
+    decoder: BookTickerResponseDecoder<'_>,
+) -> anyhow::Result<Vec<BookTicker>> {
+    let mut decoder = decoder.try_tickers_decoder()?;
+    let count = decoder.count();
+    let mut tickers = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        tickers.push(decode_book_ticker!(decoder));
+    }
+    Ok(tickers)
+}
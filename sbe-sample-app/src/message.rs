@@ -0,0 +1,177 @@
+use anyhow::bail;
+use spot_sbe::{
+    error_response_codec, message_header_codec::MessageHeaderDecoder, ReadBuf, SbeSchema,
+    SBE_SCHEMA_ID,
+};
+use std::borrow::Cow;
+
+macro_rules! spot_messages {
+    ($($variant:ident => $codec:ident::$decoder:ident,)*) => {
+        /// A Spot SBE message wrapped in the generated decoder for its template ID.
+        pub enum SpotMessage<'a> {
+            $($variant(spot_sbe::$codec::$decoder<'a>),)*
+        }
+
+        impl SpotMessage<'_> {
+            pub fn template_id(&self) -> u16 {
+                match self {
+                    $(Self::$variant(_) => spot_sbe::$codec::SBE_TEMPLATE_ID,)*
+                }
+            }
+
+            pub fn acting_version(&self) -> u16 {
+                match self {
+                    $(Self::$variant(decoder) => decoder.acting_version,)*
+                }
+            }
+        }
+
+        fn decode_template(
+            header: MessageHeaderDecoder<ReadBuf<'_>>,
+        ) -> anyhow::Result<SpotMessage<'_>> {
+            Ok(match header.template_id() {
+                $(spot_sbe::$codec::SBE_TEMPLATE_ID => SpotMessage::$variant(
+                    spot_sbe::$codec::$decoder::default().try_header(header, 0)?,
+                ),)*
+                template_id => {
+                    bail!("Unexpected template ID {template_id}");
+                }
+            })
+        }
+    };
+}
+
+spot_messages! {
+    PriceFilter => price_filter_codec::PriceFilterDecoder,
+    PercentPriceFilter => percent_price_filter_codec::PercentPriceFilterDecoder,
+    PercentPriceBySideFilter => percent_price_by_side_filter_codec::PercentPriceBySideFilterDecoder,
+    LotSizeFilter => lot_size_filter_codec::LotSizeFilterDecoder,
+    MinNotionalFilter => min_notional_filter_codec::MinNotionalFilterDecoder,
+    NotionalFilter => notional_filter_codec::NotionalFilterDecoder,
+    IcebergPartsFilter => iceberg_parts_filter_codec::IcebergPartsFilterDecoder,
+    MarketLotSizeFilter => market_lot_size_filter_codec::MarketLotSizeFilterDecoder,
+    MaxNumOrdersFilter => max_num_orders_filter_codec::MaxNumOrdersFilterDecoder,
+    MaxNumAlgoOrdersFilter => max_num_algo_orders_filter_codec::MaxNumAlgoOrdersFilterDecoder,
+    MaxNumIcebergOrdersFilter => max_num_iceberg_orders_filter_codec::MaxNumIcebergOrdersFilterDecoder,
+    MaxPositionFilter => max_position_filter_codec::MaxPositionFilterDecoder,
+    TrailingDeltaFilter => trailing_delta_filter_codec::TrailingDeltaFilterDecoder,
+    TPlusSellFilter => tp_lus_sell_filter_codec::TPlusSellFilterDecoder,
+    ExchangeMaxNumOrdersFilter => exchange_max_num_orders_filter_codec::ExchangeMaxNumOrdersFilterDecoder,
+    ExchangeMaxNumAlgoOrdersFilter => exchange_max_num_algo_orders_filter_codec::ExchangeMaxNumAlgoOrdersFilterDecoder,
+    ExchangeMaxNumIcebergOrdersFilter => exchange_max_num_iceberg_orders_filter_codec::ExchangeMaxNumIcebergOrdersFilterDecoder,
+    MaxNumOrderListsFilter => max_num_order_lists_filter_codec::MaxNumOrderListsFilterDecoder,
+    ExchangeMaxNumOrderListsFilter => exchange_max_num_order_lists_filter_codec::ExchangeMaxNumOrderListsFilterDecoder,
+    MaxNumOrderAmendsFilter => max_num_order_amends_filter_codec::MaxNumOrderAmendsFilterDecoder,
+    MaxAssetFilter => max_asset_filter_codec::MaxAssetFilterDecoder,
+    PriceRangeExecutionRule => price_range_execution_rule_codec::PriceRangeExecutionRuleDecoder,
+    WebSocketResponse => web_socket_response_codec::WebSocketResponseDecoder,
+    WebSocketSessionLogonResponse => web_socket_session_logon_response_codec::WebSocketSessionLogonResponseDecoder,
+    WebSocketSessionStatusResponse => web_socket_session_status_response_codec::WebSocketSessionStatusResponseDecoder,
+    WebSocketSessionLogoutResponse => web_socket_session_logout_response_codec::WebSocketSessionLogoutResponseDecoder,
+    WebSocketSessionSubscriptionsResponse => web_socket_session_subscriptions_response_codec::WebSocketSessionSubscriptionsResponseDecoder,
+    ErrorResponse => error_response_codec::ErrorResponseDecoder,
+    PingResponse => ping_response_codec::PingResponseDecoder,
+    ServerTimeResponse => server_time_response_codec::ServerTimeResponseDecoder,
+    ExchangeInfoResponse => exchange_info_response_codec::ExchangeInfoResponseDecoder,
+    ExecutionRulesResponse => execution_rules_response_codec::ExecutionRulesResponseDecoder,
+    MyFiltersResponse => my_filters_response_codec::MyFiltersResponseDecoder,
+    DepthResponse => depth_response_codec::DepthResponseDecoder,
+    TradesResponse => trades_response_codec::TradesResponseDecoder,
+    AggTradesResponse => agg_trades_response_codec::AggTradesResponseDecoder,
+    KlinesResponse => klines_response_codec::KlinesResponseDecoder,
+    AveragePriceResponse => average_price_response_codec::AveragePriceResponseDecoder,
+    Ticker24hSymbolFullResponse => ticker_24_hs_ymbol_full_response_codec::Ticker24hSymbolFullResponseDecoder,
+    Ticker24hFullResponse => ticker_24_hf_ull_response_codec::Ticker24hFullResponseDecoder,
+    Ticker24hSymbolMiniResponse => ticker_24_hs_ymbol_mini_response_codec::Ticker24hSymbolMiniResponseDecoder,
+    Ticker24hMiniResponse => ticker_24_hm_ini_response_codec::Ticker24hMiniResponseDecoder,
+    PriceTickerSymbolResponse => price_ticker_symbol_response_codec::PriceTickerSymbolResponseDecoder,
+    PriceTickerResponse => price_ticker_response_codec::PriceTickerResponseDecoder,
+    BookTickerSymbolResponse => book_ticker_symbol_response_codec::BookTickerSymbolResponseDecoder,
+    BookTickerResponse => book_ticker_response_codec::BookTickerResponseDecoder,
+    TickerSymbolFullResponse => ticker_symbol_full_response_codec::TickerSymbolFullResponseDecoder,
+    TickerFullResponse => ticker_full_response_codec::TickerFullResponseDecoder,
+    TickerSymbolMiniResponse => ticker_symbol_mini_response_codec::TickerSymbolMiniResponseDecoder,
+    TickerMiniResponse => ticker_mini_response_codec::TickerMiniResponseDecoder,
+    ReferencePriceResponse => reference_price_response_codec::ReferencePriceResponseDecoder,
+    ReferencePriceCalculationResponse => reference_price_calculation_response_codec::ReferencePriceCalculationResponseDecoder,
+    BlockTradesResponse => block_trades_response_codec::BlockTradesResponseDecoder,
+    NewOrderAckResponse => new_order_ack_response_codec::NewOrderAckResponseDecoder,
+    NewOrderResultResponse => new_order_result_response_codec::NewOrderResultResponseDecoder,
+    NewOrderFullResponse => new_order_full_response_codec::NewOrderFullResponseDecoder,
+    OrderTestResponse => order_test_response_codec::OrderTestResponseDecoder,
+    OrderResponse => order_response_codec::OrderResponseDecoder,
+    CancelOrderResponse => cancel_order_response_codec::CancelOrderResponseDecoder,
+    CancelOpenOrdersResponse => cancel_open_orders_response_codec::CancelOpenOrdersResponseDecoder,
+    CancelReplaceOrderResponse => cancel_replace_order_response_codec::CancelReplaceOrderResponseDecoder,
+    OrdersResponse => orders_response_codec::OrdersResponseDecoder,
+    NewOrderListAckResponse => new_order_list_ack_response_codec::NewOrderListAckResponseDecoder,
+    NewOrderListResultResponse => new_order_list_result_response_codec::NewOrderListResultResponseDecoder,
+    NewOrderListFullResponse => new_order_list_full_response_codec::NewOrderListFullResponseDecoder,
+    CancelOrderListResponse => cancel_order_list_response_codec::CancelOrderListResponseDecoder,
+    OrderListResponse => order_list_response_codec::OrderListResponseDecoder,
+    OrderListsResponse => order_lists_response_codec::OrderListsResponseDecoder,
+    OrderTestWithCommissionsResponse => order_test_with_commissions_response_codec::OrderTestWithCommissionsResponseDecoder,
+    OrderAmendmentsResponse => order_amendments_response_codec::OrderAmendmentsResponseDecoder,
+    OrderAmendKeepPriorityResponse => order_amend_keep_priority_response_codec::OrderAmendKeepPriorityResponseDecoder,
+    AccountResponse => account_response_codec::AccountResponseDecoder,
+    AccountTradesResponse => account_trades_response_codec::AccountTradesResponseDecoder,
+    AccountOrderRateLimitResponse => account_order_rate_limit_response_codec::AccountOrderRateLimitResponseDecoder,
+    AccountPreventedMatchesResponse => account_prevented_matches_response_codec::AccountPreventedMatchesResponseDecoder,
+    AccountAllocationsResponse => account_allocations_response_codec::AccountAllocationsResponseDecoder,
+    AccountCommissionResponse => account_commission_response_codec::AccountCommissionResponseDecoder,
+    UserDataStreamStartResponse => user_data_stream_start_response_codec::UserDataStreamStartResponseDecoder,
+    UserDataStreamPingResponse => user_data_stream_ping_response_codec::UserDataStreamPingResponseDecoder,
+    UserDataStreamStopResponse => user_data_stream_stop_response_codec::UserDataStreamStopResponseDecoder,
+    UserDataStreamSubscribeResponse => user_data_stream_subscribe_response_codec::UserDataStreamSubscribeResponseDecoder,
+    UserDataStreamUnsubscribeResponse => user_data_stream_unsubscribe_response_codec::UserDataStreamUnsubscribeResponseDecoder,
+    UserDataStreamSubscribeListenTokenResponse => user_data_stream_subscribe_listen_token_response_codec::UserDataStreamSubscribeListenTokenResponseDecoder,
+    AllocationReportEvent => allocation_report_event_codec::AllocationReportEventDecoder,
+    BalanceUpdateEvent => balance_update_event_codec::BalanceUpdateEventDecoder,
+    EventStreamTerminatedEvent => event_stream_terminated_event_codec::EventStreamTerminatedEventDecoder,
+    ExecutionReportEvent => execution_report_event_codec::ExecutionReportEventDecoder,
+    ExternalLockUpdateEvent => external_lock_update_event_codec::ExternalLockUpdateEventDecoder,
+    ListStatusEvent => list_status_event_codec::ListStatusEventDecoder,
+    OutboundAccountPositionEvent => outbound_account_position_event_codec::OutboundAccountPositionEventDecoder,
+    ServerShutdownEvent => server_shutdown_event_codec::ServerShutdownEventDecoder,
+    NonRepresentableMessage => non_representable_message_codec::NonRepresentableMessageDecoder,
+}
+
+/// The message at the start of `payload` as the typed decoders read it: as is
+/// for the schema ID they were generated from, and re-encoded through the
+/// codecs of its own schema ID otherwise.
+pub fn upgrade(payload: &[u8]) -> anyhow::Result<Cow<'_, [u8]>> {
+    let header = MessageHeaderDecoder::default().try_wrap(ReadBuf::new(payload), 0)?;
+    let schema_id = header.schema_id();
+    Ok(match SbeSchema::from_id(schema_id) {
+        Some(schema) => schema.upgrade(payload)?,
+        // A separate "ErrorResponse" message is returned for errors and its
+        // format is expected to be backwards compatible across all schema IDs.
+        None if header.template_id() == error_response_codec::SBE_TEMPLATE_ID => {
+            Cow::Borrowed(payload)
+        }
+        None => bail!(
+            "Unexpected schema ID. Got {schema_id}; expected one of {:?}",
+            SbeSchema::ALL
+                .iter()
+                .map(|schema| schema.id())
+                .collect::<Vec<_>>()
+        ),
+    })
+}
+
+/// Decodes the message header at the start of `payload` and wraps the rest of
+/// the message in the decoder matching its template ID. Messages of other
+/// schema IDs must go through [`upgrade`] first.
+pub fn decode_any(payload: &[u8]) -> anyhow::Result<SpotMessage<'_>> {
+    let header = MessageHeaderDecoder::default().try_wrap(ReadBuf::new(payload), 0)?;
+    // A separate "ErrorResponse" message is returned for errors and its format
+    // is expected to be backwards compatible across all schema IDs.
+    let schema_id = header.schema_id();
+    if header.template_id() != error_response_codec::SBE_TEMPLATE_ID && schema_id != SBE_SCHEMA_ID {
+        if SbeSchema::from_id(schema_id).is_some() {
+            bail!("Schema ID {schema_id} must be upgraded to {SBE_SCHEMA_ID} before it is decoded");
+        }
+        bail!("Unexpected schema ID. Got {schema_id}; expected {SBE_SCHEMA_ID}");
+    }
+    decode_template(header)
+}
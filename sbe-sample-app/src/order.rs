@@ -0,0 +1,424 @@
+use crate::{
+    decimal::Decimal,
+    decode::{into_bool, preallocated},
+};
+use serde::{Serialize, Serializer};
+use spot_sbe::{
+    contingency_type::ContingencyType, expiry_reason::ExpiryReason, floor::Floor,
+    list_order_status::ListOrderStatus, list_status_type::ListStatusType,
+    order_capacity::OrderCapacity, order_list_response_codec::OrderListResponseDecoder,
+    order_lists_response_codec::OrderListsResponseDecoder,
+    order_response_codec::OrderResponseDecoder, order_side::OrderSide, order_status::OrderStatus,
+    order_type::OrderType, orders_response_codec::OrdersResponseDecoder,
+    peg_offset_type::PegOffsetType, peg_price_type::PegPriceType, time_in_force::TimeInForce,
+};
+
+/// An order as returned by the order query endpoints.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub symbol: String,
+    pub order_id: i64,
+    pub order_list_id: Option<i64>,
+    pub client_order_id: String,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub executed_qty: Decimal,
+    pub cummulative_quote_qty: Decimal,
+    #[serde(serialize_with = "serialize_order_status")]
+    pub status: OrderStatus,
+    #[serde(serialize_with = "serialize_time_in_force")]
+    pub time_in_force: TimeInForce,
+    #[serde(rename = "type", serialize_with = "serialize_order_type")]
+    pub order_type: OrderType,
+    #[serde(serialize_with = "serialize_order_side")]
+    pub side: OrderSide,
+    pub stop_price: Option<Decimal>,
+    pub trailing_delta: Option<i64>,
+    pub trailing_time: Option<i64>,
+    pub iceberg_qty: Option<Decimal>,
+    pub time: i64,
+    pub update_time: i64,
+    pub is_working: bool,
+    pub working_time: Option<i64>,
+    pub orig_quote_order_qty: Decimal,
+    pub strategy_id: Option<i64>,
+    pub strategy_type: Option<i32>,
+    #[serde(serialize_with = "serialize_order_capacity")]
+    pub order_capacity: OrderCapacity,
+    #[serde(serialize_with = "serialize_floor")]
+    pub working_floor: Floor,
+    pub prevented_match_id: Option<i64>,
+    pub prevented_quantity: Decimal,
+    pub used_sor: bool,
+    #[serde(serialize_with = "serialize_peg_price_type")]
+    pub peg_price_type: PegPriceType,
+    #[serde(serialize_with = "serialize_peg_offset_type")]
+    pub peg_offset_type: PegOffsetType,
+    pub peg_offset_value: Option<u8>,
+    pub pegged_price: Option<Decimal>,
+    #[serde(serialize_with = "serialize_expiry_reason")]
+    pub expiry_reason: ExpiryReason,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderListOrder {
+    pub symbol: String,
+    pub order_id: i64,
+    pub client_order_id: String,
+}
+
+/// An order list (OCO, OTO, OTOCO) as returned by the order list query endpoints.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderList {
+    pub order_list_id: i64,
+    #[serde(serialize_with = "serialize_contingency_type")]
+    pub contingency_type: ContingencyType,
+    #[serde(serialize_with = "serialize_list_status_type")]
+    pub list_status_type: ListStatusType,
+    #[serde(serialize_with = "serialize_list_order_status")]
+    pub list_order_status: ListOrderStatus,
+    pub list_client_order_id: String,
+    pub transaction_time: i64,
+    pub symbol: String,
+    pub orders: Vec<OrderListOrder>,
+}
+
+// `OrderResponse` and the entries of `OrdersResponse` expose identical
+// accessors on unrelated decoder types, as do `OrderListResponse` and the
+// entries of `OrderListsResponse`.
+macro_rules! decode_order {
+    ($decoder:ident) => {{
+        let price_exponent = $decoder.price_exponent();
+        let qty_exponent = $decoder.qty_exponent();
+        let price = |mantissa: i64| Decimal::new(mantissa, price_exponent);
+        let qty = |mantissa: i64| Decimal::new(mantissa, qty_exponent);
+        Order {
+            order_id: $decoder.order_id(),
+            order_list_id: $decoder.order_list_id(),
+            price: price($decoder.price()),
+            orig_qty: qty($decoder.orig_qty()),
+            executed_qty: qty($decoder.executed_qty()),
+            cummulative_quote_qty: price($decoder.cummulative_quote_qty()),
+            status: $decoder.status(),
+            time_in_force: $decoder.time_in_force(),
+            order_type: $decoder.order_type(),
+            side: $decoder.side(),
+            stop_price: $decoder.stop_price().map(price),
+            trailing_delta: $decoder.trailing_delta(),
+            trailing_time: $decoder.trailing_time(),
+            iceberg_qty: $decoder.iceberg_qty().map(qty),
+            time: $decoder.time(),
+            update_time: $decoder.update_time(),
+            is_working: into_bool($decoder.is_working())?,
+            working_time: $decoder.working_time(),
+            orig_quote_order_qty: price($decoder.orig_quote_order_qty()),
+            strategy_id: $decoder.strategy_id(),
+            strategy_type: $decoder.strategy_type(),
+            order_capacity: $decoder.order_capacity(),
+            working_floor: $decoder.working_floor(),
+            prevented_match_id: $decoder.prevented_match_id(),
+            prevented_quantity: qty($decoder.prevented_quantity()),
+            used_sor: into_bool($decoder.used_sor())?,
+            peg_price_type: $decoder.peg_price_type(),
+            peg_offset_type: $decoder.peg_offset_type(),
+            peg_offset_value: $decoder.peg_offset_value(),
+            pegged_price: $decoder.pegged_price().map(price),
+            expiry_reason: $decoder.expiry_reason(),
+            symbol: {
+                let coordinates = $decoder.try_symbol_decoder()?;
+                let slice = $decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            client_order_id: {
+                let coordinates = $decoder.try_client_order_id_decoder()?;
+                let slice = $decoder.client_order_id_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        }
+    }};
+}
+
+/// Decodes the `orders` group shared by every order list message, leaving
+/// `$decoder` positioned after it.
+macro_rules! decode_order_list_orders {
+    ($decoder:ident) => {{
+        let mut orders_decoder = $decoder.try_orders_decoder()?;
+        let count = orders_decoder.count();
+        let mut orders = Vec::with_capacity(preallocated(count.try_into()?));
+        for _ in 0..count {
+            orders_decoder.try_advance()?;
+            orders.push(OrderListOrder {
+                order_id: orders_decoder.order_id(),
+                symbol: {
+                    let coordinates = orders_decoder.try_symbol_decoder()?;
+                    let slice = orders_decoder.symbol_slice(coordinates);
+                    String::from_utf8(slice.into())?
+                },
+                client_order_id: {
+                    let coordinates = orders_decoder.try_client_order_id_decoder()?;
+                    let slice = orders_decoder.client_order_id_slice(coordinates);
+                    String::from_utf8(slice.into())?
+                },
+            });
+        }
+        $decoder = orders_decoder.parent()?;
+        orders
+    }};
+}
+pub(crate) use decode_order_list_orders;
+
+macro_rules! decode_order_list {
+    ($decoder:ident) => {{
+        let order_list_id = $decoder.order_list_id();
+        let contingency_type = $decoder.contingency_type();
+        let list_status_type = $decoder.list_status_type();
+        let list_order_status = $decoder.list_order_status();
+        let transaction_time = $decoder.transaction_time();
+        let orders = decode_order_list_orders!($decoder);
+        OrderList {
+            order_list_id,
+            contingency_type,
+            list_status_type,
+            list_order_status,
+            transaction_time,
+            orders,
+            list_client_order_id: {
+                let coordinates = $decoder.try_list_client_order_id_decoder()?;
+                let slice = $decoder.list_client_order_id_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+            symbol: {
+                let coordinates = $decoder.try_symbol_decoder()?;
+                let slice = $decoder.symbol_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        }
+    }};
+}
+
+pub fn decode_order(mut decoder: OrderResponseDecoder<'_>) -> anyhow::Result<Order> {
+    Ok(decode_order!(decoder))
+}
+
+pub fn decode_orders(decoder: OrdersResponseDecoder<'_>) -> anyhow::Result<Vec<Order>> {
+    let mut decoder = decoder.try_orders_decoder()?;
+    let count = decoder.count();
+    let mut orders = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        orders.push(decode_order!(decoder));
+    }
+    Ok(orders)
+}
+
+pub fn decode_order_list(mut decoder: OrderListResponseDecoder<'_>) -> anyhow::Result<OrderList> {
+    Ok(decode_order_list!(decoder))
+}
+
+pub fn decode_order_lists(
+    decoder: OrderListsResponseDecoder<'_>,
+) -> anyhow::Result<Vec<OrderList>> {
+    let mut decoder = decoder.try_order_lists_decoder()?;
+    let count = decoder.count();
+    let mut order_lists = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        order_lists.push(decode_order_list!(decoder));
+    }
+    Ok(order_lists)
+}
+
+pub(crate) fn serialize_order_status<S: Serializer>(
+    val: &OrderStatus,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use OrderStatus::*;
+    let str_val = match val {
+        New => "NEW",
+        PartiallyFilled => "PARTIALLY_FILLED",
+        Filled => "FILLED",
+        Canceled => "CANCELED",
+        PendingCancel => "PENDING_CANCEL",
+        Rejected => "REJECTED",
+        Expired => "EXPIRED",
+        ExpiredInMatch => "EXPIRED_IN_MATCH",
+        PendingNew => "PENDING_NEW",
+        Unknown => "UNKNOWN",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+pub(crate) fn serialize_time_in_force<S: Serializer>(
+    val: &TimeInForce,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use TimeInForce::*;
+    let str_val = match val {
+        Gtc => "GTC",
+        Ioc => "IOC",
+        Fok => "FOK",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+pub(crate) fn serialize_order_type<S: Serializer>(
+    val: &OrderType,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use OrderType::*;
+    let str_val = match val {
+        Market => "MARKET",
+        Limit => "LIMIT",
+        StopLoss => "STOP_LOSS",
+        StopLossLimit => "STOP_LOSS_LIMIT",
+        TakeProfit => "TAKE_PROFIT",
+        TakeProfitLimit => "TAKE_PROFIT_LIMIT",
+        LimitMaker => "LIMIT_MAKER",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+pub(crate) fn serialize_order_side<S: Serializer>(
+    val: &OrderSide,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use OrderSide::*;
+    let str_val = match val {
+        Buy => "BUY",
+        Sell => "SELL",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+pub(crate) fn serialize_order_capacity<S: Serializer>(
+    val: &OrderCapacity,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use OrderCapacity::*;
+    let str_val = match val {
+        Principal => "PRINCIPAL",
+        Agency => "AGENCY",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+pub(crate) fn serialize_floor<S: Serializer>(
+    val: &Floor,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use Floor::*;
+    let str_val = match val {
+        Exchange => "EXCHANGE",
+        Broker => "BROKER",
+        Sor => "SOR",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+pub(crate) fn serialize_peg_price_type<S: Serializer>(
+    val: &PegPriceType,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use PegPriceType::*;
+    let str_val = match val {
+        PrimaryPeg => "PRIMARY_PEG",
+        MarketPeg => "MARKET_PEG",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+pub(crate) fn serialize_peg_offset_type<S: Serializer>(
+    val: &PegOffsetType,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use PegOffsetType::*;
+    let str_val = match val {
+        PriceLevel => "PRICE_LEVEL",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+pub(crate) fn serialize_expiry_reason<S: Serializer>(
+    val: &ExpiryReason,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use ExpiryReason::*;
+    let str_val = match val {
+        Rejected => "REJECTED",
+        ExchangeCanceled => "EXCHANGE_CANCELED",
+        OcoTrigger => "OCO_TRIGGER",
+        OtoPhaseOneExpired => "OTO_PHASE_ONE_EXPIRED",
+        UnfilledIocQuantityExpired => "UNFILLED_IOC_QUANTITY_EXPIRED",
+        UnfilledFokOrderExpired => "UNFILLED_FOK_ORDER_EXPIRED",
+        InsufficientLiquidity => "INSUFFICIENT_LIQUIDITY",
+        ExecutionRulePriceRangeExceeded => "EXECUTION_RULE_PRICE_RANGE_EXCEEDED",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+pub(crate) fn serialize_contingency_type<S: Serializer>(
+    val: &ContingencyType,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use ContingencyType::*;
+    let str_val = match val {
+        Oco => "OCO",
+        Oto => "OTO",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+pub(crate) fn serialize_list_status_type<S: Serializer>(
+    val: &ListStatusType,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use ListStatusType::*;
+    let str_val = match val {
+        Response => "RESPONSE",
+        ExecStarted => "EXEC_STARTED",
+        AllDone => "ALL_DONE",
+        Updated => "UPDATED",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+pub(crate) fn serialize_list_order_status<S: Serializer>(
+    val: &ListOrderStatus,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use ListOrderStatus::*;
+    let str_val = match val {
+        Canceling => "CANCELING",
+        Executing => "EXECUTING",
+        AllDone => "ALL_DONE",
+        Reject => "REJECT",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
@@ -0,0 +1,245 @@
+use crate::{
+    decimal::Decimal,
+    market_data::{Depth, PriceLevel},
+};
+use std::{collections::BTreeMap, fmt};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BookSide {
+    Bid,
+    Ask,
+}
+
+/// An incremental update from the diff depth stream, as in `<symbol>@depth`.
+/// Levels with a zero quantity remove the price from the book.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DepthUpdate {
+    /// `U` in the JSON stream.
+    pub first_update_id: i64,
+    /// `u` in the JSON stream.
+    pub last_update_id: i64,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+}
+
+/// An update that does not continue the book's sequence. The book must be
+/// seeded again from a fresh snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SequenceGap {
+    pub expected_update_id: i64,
+    pub first_update_id: i64,
+}
+
+impl fmt::Display for SequenceGap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Depth update gap: expected update ID {}; update starts at {}",
+            self.expected_update_id, self.first_update_id
+        )
+    }
+}
+
+impl std::error::Error for SequenceGap {}
+
+/// A book whose last update ID is `i64::MAX`, which no update can follow. The
+/// book must be seeded again from a fresh snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SequenceExhausted {
+    pub last_update_id: i64,
+}
+
+impl fmt::Display for SequenceExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Depth update IDs exhausted: no update can follow {}",
+            self.last_update_id
+        )
+    }
+}
+
+impl std::error::Error for SequenceExhausted {}
+
+/// A depth update the book could not apply.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepthUpdateError {
+    SequenceGap(SequenceGap),
+    SequenceExhausted(SequenceExhausted),
+}
+
+impl fmt::Display for DepthUpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SequenceGap(error) => error.fmt(f),
+            Self::SequenceExhausted(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for DepthUpdateError {}
+
+impl From<SequenceGap> for DepthUpdateError {
+    fn from(error: SequenceGap) -> Self {
+        Self::SequenceGap(error)
+    }
+}
+
+impl From<SequenceExhausted> for DepthUpdateError {
+    fn from(error: SequenceExhausted) -> Self {
+        Self::SequenceExhausted(error)
+    }
+}
+
+/// A local order book, seeded from a `depth` snapshot and kept current with
+/// diff depth updates.
+///
+/// Prices and quantities are exact [`Decimal`]s, so levels match however many
+/// trailing zeros their exponent implies.
+#[derive(Clone, Debug)]
+pub struct OrderBook {
+    last_update_id: i64,
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+    /// Whether an update has been applied since the snapshot.
+    updated: bool,
+    /// Cleared on a sequence gap, after which updates are rejected.
+    in_sync: bool,
+}
+
+fn levels(levels: &[PriceLevel]) -> BTreeMap<Decimal, Decimal> {
+    levels
+        .iter()
+        .filter(|level| !level.qty.is_zero())
+        .map(|level| (level.price, level.qty))
+        .collect()
+}
+
+fn apply_levels(book: &mut BTreeMap<Decimal, Decimal>, levels: &[PriceLevel]) {
+    for level in levels {
+        if level.qty.is_zero() {
+            book.remove(&level.price);
+        } else {
+            book.insert(level.price, level.qty);
+        }
+    }
+}
+
+impl OrderBook {
+    pub fn from_depth(depth: &Depth) -> Self {
+        Self {
+            last_update_id: depth.last_update_id,
+            bids: levels(&depth.bids),
+            asks: levels(&depth.asks),
+            updated: false,
+            in_sync: true,
+        }
+    }
+
+    pub fn last_update_id(&self) -> i64 {
+        self.last_update_id
+    }
+
+    /// False once a sequence gap was detected.
+    pub fn is_in_sync(&self) -> bool {
+        self.in_sync
+    }
+
+    /// Applies a diff depth update.
+    ///
+    /// Returns `Ok(false)` for updates already covered by the book. The first
+    /// update after the snapshot must straddle `last_update_id + 1`; every later
+    /// one must start right after the previous one ended. Once the book reaches
+    /// the largest update ID, every update fails with [`SequenceExhausted`].
+    pub fn apply(&mut self, update: &DepthUpdate) -> Result<bool, DepthUpdateError> {
+        let expected_update_id = self
+            .last_update_id
+            .checked_add(1)
+            .ok_or(SequenceExhausted {
+                last_update_id: self.last_update_id,
+            })?;
+        let gap = SequenceGap {
+            expected_update_id,
+            first_update_id: update.first_update_id,
+        };
+        if !self.in_sync {
+            return Err(gap.into());
+        }
+        if update.last_update_id < expected_update_id {
+            return Ok(false);
+        }
+        let in_sequence = if self.updated {
+            update.first_update_id == expected_update_id
+        } else {
+            update.first_update_id <= expected_update_id
+        };
+        if !in_sequence {
+            self.in_sync = false;
+            return Err(gap.into());
+        }
+        apply_levels(&mut self.bids, &update.bids);
+        apply_levels(&mut self.asks, &update.asks);
+        self.last_update_id = update.last_update_id;
+        self.updated = true;
+        Ok(true)
+    }
+
+    /// The levels of one side, best price first.
+    pub fn levels(&self, side: BookSide) -> Box<dyn Iterator<Item = PriceLevel> + '_> {
+        let level = |(&price, &qty)| PriceLevel { price, qty };
+        match side {
+            BookSide::Bid => Box::new(self.bids.iter().rev().map(level)),
+            BookSide::Ask => Box::new(self.asks.iter().map(level)),
+        }
+    }
+
+    pub fn best_bid(&self) -> Option<PriceLevel> {
+        self.levels(BookSide::Bid).next()
+    }
+
+    pub fn best_ask(&self) -> Option<PriceLevel> {
+        self.levels(BookSide::Ask).next()
+    }
+
+    /// Best ask minus best bid.
+    pub fn spread(&self) -> Option<Decimal> {
+        self.best_ask()?.price.checked_sub(self.best_bid()?.price)
+    }
+
+    /// The quantity resting at `price`, zero if there is no such level.
+    pub fn depth_at(&self, side: BookSide, price: Decimal) -> Decimal {
+        let book = match side {
+            BookSide::Bid => &self.bids,
+            BookSide::Ask => &self.asks,
+        };
+        book.get(&price).copied().unwrap_or(Decimal::ZERO)
+    }
+
+    /// The best `n` levels of one side.
+    pub fn top(&self, side: BookSide, n: usize) -> Vec<PriceLevel> {
+        self.levels(side).take(n).collect()
+    }
+
+    /// The exact quote quantity needed to fill `qty` against one side, or
+    /// `None` if the side holds less than `qty`.
+    pub fn notional_to_size(&self, side: BookSide, qty: Decimal) -> Option<Decimal> {
+        let mut remaining = qty;
+        let mut notional = Decimal::ZERO;
+        for level in self.levels(side) {
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+            let fill = remaining.min(level.qty);
+            notional = notional.checked_add(level.price.checked_mul(fill)?)?;
+            remaining = remaining.checked_sub(fill)?;
+        }
+        (remaining <= Decimal::ZERO).then_some(notional)
+    }
+
+    /// The volume-weighted average price to fill `qty` against one side,
+    /// truncated to the given exponent.
+    pub fn vwap_to_size(&self, side: BookSide, qty: Decimal, exponent: i8) -> Option<Decimal> {
+        self.notional_to_size(side, qty)?
+            .checked_div_with_exponent(qty, exponent)
+    }
+}
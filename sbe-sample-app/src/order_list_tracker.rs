@@ -0,0 +1,284 @@
+use crate::{
+    decimal::Decimal,
+    execution::OrderListReport,
+    order::{OrderList, OrderListOrder},
+    order_tracker::{is_legal_transition, IllegalTransition},
+    user_data::{ExecutionReport, ListStatus, UserDataEvent},
+};
+use spot_sbe::{
+    contingency_type::ContingencyType, execution_type::ExecutionType,
+    list_order_status::ListOrderStatus, list_status_type::ListStatusType,
+    order_status::OrderStatus,
+};
+use std::collections::BTreeMap;
+
+/// One execution report of an order list leg.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LegUpdate {
+    pub execution_id: i64,
+    pub transact_time: i64,
+    pub execution_type: ExecutionType,
+    pub order_status: OrderStatus,
+    pub executed_qty: Decimal,
+}
+
+/// An order of an order list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OrderListLeg {
+    pub symbol: String,
+    pub order_id: i64,
+    pub client_order_id: String,
+    /// `None` until an execution report for the order was applied.
+    pub status: Option<OrderStatus>,
+    /// The order's execution reports, oldest first.
+    pub updates: Vec<LegUpdate>,
+    /// `None` until an execution report for the order was applied.
+    last_execution_id: Option<i64>,
+}
+
+impl From<&OrderListOrder> for OrderListLeg {
+    fn from(order: &OrderListOrder) -> Self {
+        Self {
+            symbol: order.symbol.clone(),
+            order_id: order.order_id,
+            client_order_id: order.client_order_id.clone(),
+            status: None,
+            updates: Vec::new(),
+            last_execution_id: None,
+        }
+    }
+}
+
+/// The state of an OCO, OTO or OTOCO order list and its legs.
+///
+/// A list first seen through an execution report of one of its legs has
+/// `NullVal` list fields until its `listStatus` event arrives.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrackedOrderList {
+    pub symbol: String,
+    pub order_list_id: i64,
+    pub list_client_order_id: String,
+    pub contingency_type: ContingencyType,
+    pub list_status_type: ListStatusType,
+    pub list_order_status: ListOrderStatus,
+    pub reject_reason: Option<String>,
+    pub legs: Vec<OrderListLeg>,
+    pub update_time: i64,
+}
+
+impl TrackedOrderList {
+    fn new(symbol: &str, order_list_id: i64) -> Self {
+        Self {
+            symbol: symbol.to_owned(),
+            order_list_id,
+            list_client_order_id: String::new(),
+            contingency_type: ContingencyType::NullVal,
+            list_status_type: ListStatusType::NullVal,
+            list_order_status: ListOrderStatus::NullVal,
+            reject_reason: None,
+            legs: Vec::new(),
+            update_time: i64::MIN,
+        }
+    }
+
+    /// Whether every leg is done or the list was rejected.
+    pub fn is_done(&self) -> bool {
+        self.list_status_type == ListStatusType::AllDone
+            || matches!(
+                self.list_order_status,
+                ListOrderStatus::AllDone | ListOrderStatus::Reject
+            )
+    }
+
+    pub fn leg(&self, order_id: i64) -> Option<&OrderListLeg> {
+        self.legs.iter().find(|leg| leg.order_id == order_id)
+    }
+
+    fn leg_mut(&mut self, order: &OrderListOrder) -> &mut OrderListLeg {
+        match self
+            .legs
+            .iter()
+            .position(|leg| leg.order_id == order.order_id)
+        {
+            Some(index) => &mut self.legs[index],
+            None => {
+                self.legs.push(OrderListLeg::from(order));
+                self.legs.last_mut().unwrap()
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        contingency_type: ContingencyType,
+        list_status_type: ListStatusType,
+        list_order_status: ListOrderStatus,
+        list_client_order_id: &str,
+        update_time: i64,
+        orders: &[OrderListOrder],
+    ) {
+        self.contingency_type = contingency_type;
+        self.list_status_type = list_status_type;
+        self.list_order_status = list_order_status;
+        self.list_client_order_id = list_client_order_id.to_owned();
+        self.update_time = update_time;
+        for order in orders {
+            self.leg_mut(order).client_order_id = order.client_order_id.clone();
+        }
+    }
+}
+
+/// Order lists keyed by symbol and order list ID, kept current with
+/// `listStatus` and `executionReport` events.
+#[derive(Clone, Debug, Default)]
+pub struct OrderListTracker {
+    order_lists: BTreeMap<(String, i64), TrackedOrderList>,
+}
+
+impl OrderListTracker {
+    /// Seeds the tracker from an `openOrderList` response.
+    pub fn from_order_lists(order_lists: &[OrderList]) -> Self {
+        let mut tracker = Self::default();
+        for order_list in order_lists {
+            tracker.insert_order_list(order_list);
+        }
+        tracker
+    }
+
+    fn entry(&mut self, symbol: &str, order_list_id: i64) -> &mut TrackedOrderList {
+        self.order_lists
+            .entry((symbol.to_owned(), order_list_id))
+            .or_insert_with(|| TrackedOrderList::new(symbol, order_list_id))
+    }
+
+    pub fn insert_order_list(&mut self, order_list: &OrderList) {
+        self.entry(&order_list.symbol, order_list.order_list_id)
+            .update(
+                order_list.contingency_type,
+                order_list.list_status_type,
+                order_list.list_order_status,
+                &order_list.list_client_order_id,
+                order_list.transaction_time,
+                &order_list.orders,
+            );
+    }
+
+    /// Tracks a list from an `orderList/*` placement or cancellation response.
+    pub fn insert_report<R>(&mut self, report: &OrderListReport<R>) {
+        self.entry(&report.symbol, report.order_list_id).update(
+            report.contingency_type,
+            report.list_status_type,
+            report.list_order_status,
+            &report.list_client_order_id,
+            report.transaction_time,
+            &report.orders,
+        );
+    }
+
+    pub fn order_list(&self, symbol: &str, order_list_id: i64) -> Option<&TrackedOrderList> {
+        self.order_lists.get(&(symbol.to_owned(), order_list_id))
+    }
+
+    /// The list that has the order as one of its legs.
+    pub fn order_list_of(&self, symbol: &str, order_id: i64) -> Option<&TrackedOrderList> {
+        self.order_lists
+            .values()
+            .find(|order_list| order_list.symbol == symbol && order_list.leg(order_id).is_some())
+    }
+
+    pub fn order_lists(&self) -> impl Iterator<Item = &TrackedOrderList> {
+        self.order_lists.values()
+    }
+
+    pub fn active_order_lists(&self) -> impl Iterator<Item = &TrackedOrderList> {
+        self.order_lists()
+            .filter(|order_list| !order_list.is_done())
+    }
+
+    /// Forgets lists whose legs are all done.
+    pub fn remove_done(&mut self) {
+        self.order_lists
+            .retain(|_, order_list| !order_list.is_done());
+    }
+
+    /// Applies the `listStatus` events and the execution reports of list legs
+    /// among user data stream events; other events are ignored.
+    ///
+    /// Returns `Ok(false)` for events that were ignored or already applied.
+    pub fn apply(&mut self, event: &UserDataEvent) -> Result<bool, IllegalTransition> {
+        match event {
+            UserDataEvent::ListStatus(event) => Ok(self.apply_list_status(event)),
+            UserDataEvent::ExecutionReport(report) => self.apply_execution_report(report),
+            _ => Ok(false),
+        }
+    }
+
+    pub fn apply_list_status(&mut self, event: &ListStatus) -> bool {
+        let order_list = self.entry(&event.symbol, event.order_list_id);
+        if event.transact_time < order_list.update_time {
+            return false;
+        }
+        order_list.update(
+            event.contingency_type,
+            event.list_status_type,
+            event.list_order_status,
+            &event.list_client_order_id,
+            event.transact_time,
+            &event.orders,
+        );
+        order_list.reject_reason = (event.reject_reason != "NONE"
+            && !event.reject_reason.is_empty())
+        .then(|| event.reject_reason.clone());
+        true
+    }
+
+    /// Records the report against its leg. Reports of orders that are not in
+    /// a list are ignored, as are reports older than the leg's last one.
+    ///
+    /// Illegal transitions, such as a fill after the leg was canceled or an
+    /// executed quantity that shrinks, leave the leg unchanged.
+    pub fn apply_execution_report(
+        &mut self,
+        report: &ExecutionReport,
+    ) -> Result<bool, IllegalTransition> {
+        let Some(order_list_id) = report.order_list_id else {
+            return Ok(false);
+        };
+        let order = OrderListOrder {
+            symbol: report.symbol.clone(),
+            order_id: report.order_id,
+            client_order_id: report.client_order_id.clone(),
+        };
+        let leg = self.entry(&report.symbol, order_list_id).leg_mut(&order);
+        if leg
+            .last_execution_id
+            .map_or(false, |execution_id| report.execution_id <= execution_id)
+        {
+            return Ok(false);
+        }
+        // A leg first seen has no status or updates, so any report is legal.
+        if let (Some(status), Some(update)) = (leg.status, leg.updates.last()) {
+            if !is_legal_transition(status, report.order_status)
+                || report.executed_qty < update.executed_qty
+            {
+                return Err(IllegalTransition {
+                    symbol: report.symbol.clone(),
+                    order_id: report.order_id,
+                    execution_type: report.execution_type,
+                    from: status,
+                    to: report.order_status,
+                });
+            }
+        }
+        leg.status = Some(report.order_status);
+        leg.last_execution_id = Some(report.execution_id);
+        leg.updates.push(LegUpdate {
+            execution_id: report.execution_id,
+            transact_time: report.transact_time,
+            execution_type: report.execution_type,
+            order_status: report.order_status,
+            executed_qty: report.executed_qty,
+        });
+        Ok(true)
+    }
+}
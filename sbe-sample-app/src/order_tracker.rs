@@ -0,0 +1,338 @@
+use crate::{
+    decimal::Decimal,
+    order::Order,
+    user_data::{ExecutionReport, UserDataEvent},
+};
+use spot_sbe::{
+    execution_type::ExecutionType, order_side::OrderSide, order_status::OrderStatus,
+    order_type::OrderType,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+/// A `Replaced` execution report, sent when an order is amended.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Amendment {
+    pub transact_time: i64,
+    pub orig_client_order_id: String,
+    pub client_order_id: String,
+    pub orig_qty: Decimal,
+}
+
+/// The state of one order as rebuilt from its execution reports.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrackedOrder {
+    pub symbol: String,
+    pub order_id: i64,
+    pub order_list_id: Option<i64>,
+    pub client_order_id: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub price: Decimal,
+    pub orig_qty: Decimal,
+    pub status: OrderStatus,
+    pub executed_qty: Decimal,
+    pub cummulative_quote_qty: Decimal,
+    /// Commissions paid so far, by commission asset.
+    pub commissions: BTreeMap<String, Decimal>,
+    pub prevented_qty: Decimal,
+    pub amendments: Vec<Amendment>,
+    pub update_time: i64,
+    /// `None` until an execution report was applied.
+    last_execution_id: Option<i64>,
+}
+
+impl TrackedOrder {
+    pub fn is_open(&self) -> bool {
+        !is_final(self.status)
+    }
+}
+
+impl From<&Order> for TrackedOrder {
+    fn from(order: &Order) -> Self {
+        Self {
+            symbol: order.symbol.clone(),
+            order_id: order.order_id,
+            order_list_id: order.order_list_id,
+            client_order_id: order.client_order_id.clone(),
+            side: order.side,
+            order_type: order.order_type,
+            price: order.price,
+            orig_qty: order.orig_qty,
+            status: order.status,
+            executed_qty: order.executed_qty,
+            cummulative_quote_qty: order.cummulative_quote_qty,
+            commissions: BTreeMap::new(),
+            prevented_qty: order.prevented_quantity,
+            amendments: Vec::new(),
+            update_time: order.update_time,
+            last_execution_id: None,
+        }
+    }
+}
+
+impl From<&ExecutionReport> for TrackedOrder {
+    fn from(report: &ExecutionReport) -> Self {
+        Self {
+            symbol: report.symbol.clone(),
+            order_id: report.order_id,
+            order_list_id: report.order_list_id,
+            client_order_id: report.client_order_id.clone(),
+            side: report.side,
+            order_type: report.order_type,
+            price: report.price,
+            orig_qty: report.orig_qty,
+            status: OrderStatus::PendingNew,
+            executed_qty: Decimal::ZERO,
+            cummulative_quote_qty: Decimal::ZERO,
+            commissions: BTreeMap::new(),
+            prevented_qty: Decimal::ZERO,
+            amendments: Vec::new(),
+            update_time: report.transact_time,
+            last_execution_id: None,
+        }
+    }
+}
+
+/// An execution report that does not follow from the order's current state.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IllegalTransition {
+    pub symbol: String,
+    pub order_id: i64,
+    pub execution_type: ExecutionType,
+    pub from: OrderStatus,
+    pub to: OrderStatus,
+}
+
+impl fmt::Display for IllegalTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Illegal {:?} execution report for {} order {}: {:?} -> {:?}",
+            self.execution_type, self.symbol, self.order_id, self.from, self.to
+        )
+    }
+}
+
+impl std::error::Error for IllegalTransition {}
+
+/// A commission that takes the order's total for its asset out of the range of
+/// [`Decimal`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommissionOverflow {
+    pub symbol: String,
+    pub order_id: i64,
+    pub commission_asset: String,
+}
+
+impl fmt::Display for CommissionOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} commission overflow for {} order {}",
+            self.commission_asset, self.symbol, self.order_id
+        )
+    }
+}
+
+impl std::error::Error for CommissionOverflow {}
+
+/// An execution report the tracker could not apply.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExecutionReportError {
+    IllegalTransition(IllegalTransition),
+    CommissionOverflow(CommissionOverflow),
+}
+
+impl fmt::Display for ExecutionReportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IllegalTransition(error) => error.fmt(f),
+            Self::CommissionOverflow(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ExecutionReportError {}
+
+impl From<IllegalTransition> for ExecutionReportError {
+    fn from(error: IllegalTransition) -> Self {
+        Self::IllegalTransition(error)
+    }
+}
+
+impl From<CommissionOverflow> for ExecutionReportError {
+    fn from(error: CommissionOverflow) -> Self {
+        Self::CommissionOverflow(error)
+    }
+}
+
+fn is_final(status: OrderStatus) -> bool {
+    use OrderStatus::*;
+    matches!(
+        status,
+        Filled | Canceled | Rejected | Expired | ExpiredInMatch
+    )
+}
+
+pub(crate) fn is_legal_transition(from: OrderStatus, to: OrderStatus) -> bool {
+    use OrderStatus::*;
+    match from {
+        _ if is_final(from) => false,
+        New => to != PendingNew,
+        PartiallyFilled | PendingCancel => !matches!(to, PendingNew | New | Rejected),
+        _ => true,
+    }
+}
+
+/// Orders keyed by symbol and order ID, kept current with `executionReport`
+/// events.
+#[derive(Clone, Debug, Default)]
+pub struct OrderTracker {
+    orders: BTreeMap<(String, i64), TrackedOrder>,
+    client_order_ids: HashMap<(String, String), i64>,
+}
+
+impl OrderTracker {
+    /// Seeds the tracker from an `openOrders` response.
+    pub fn from_orders(orders: &[Order]) -> Self {
+        let mut tracker = Self::default();
+        for order in orders {
+            tracker.insert(TrackedOrder::from(order));
+        }
+        tracker
+    }
+
+    fn insert(&mut self, order: TrackedOrder) {
+        let client_key = (order.symbol.clone(), order.client_order_id.clone());
+        self.client_order_ids.insert(client_key, order.order_id);
+        self.orders
+            .insert((order.symbol.clone(), order.order_id), order);
+    }
+
+    pub fn order(&self, symbol: &str, order_id: i64) -> Option<&TrackedOrder> {
+        self.orders.get(&(symbol.to_owned(), order_id))
+    }
+
+    pub fn order_by_client_order_id(
+        &self,
+        symbol: &str,
+        client_order_id: &str,
+    ) -> Option<&TrackedOrder> {
+        let key = (symbol.to_owned(), client_order_id.to_owned());
+        let order_id = *self.client_order_ids.get(&key)?;
+        self.order(symbol, order_id)
+    }
+
+    pub fn orders(&self) -> impl Iterator<Item = &TrackedOrder> {
+        self.orders.values()
+    }
+
+    pub fn open_orders(&self) -> impl Iterator<Item = &TrackedOrder> {
+        self.orders().filter(|order| order.is_open())
+    }
+
+    /// Forgets filled, canceled, rejected and expired orders.
+    pub fn remove_closed(&mut self) {
+        self.orders.retain(|_, order| order.is_open());
+        let orders = &self.orders;
+        self.client_order_ids
+            .retain(|(symbol, _), order_id| orders.contains_key(&(symbol.clone(), *order_id)));
+    }
+
+    /// Applies the execution reports among user data stream events; other
+    /// events are ignored.
+    pub fn apply(&mut self, event: &UserDataEvent) -> Result<bool, ExecutionReportError> {
+        match event {
+            UserDataEvent::ExecutionReport(report) => self.apply_execution_report(report),
+            _ => Ok(false),
+        }
+    }
+
+    /// Applies an execution report, tracking the order if it is new.
+    ///
+    /// Returns `Ok(false)` for reports that were already applied. Illegal
+    /// transitions, such as a fill after the order was canceled or an executed
+    /// quantity that shrinks, and commissions overflowing the order's total
+    /// leave the order unchanged, or untracked when it is first seen.
+    pub fn apply_execution_report(
+        &mut self,
+        report: &ExecutionReport,
+    ) -> Result<bool, ExecutionReportError> {
+        let key = (report.symbol.clone(), report.order_id);
+        // An order first seen is only tracked once its report is applied.
+        let mut new_order = None;
+        let order = match self.orders.get_mut(&key) {
+            Some(order) => order,
+            None => new_order.insert(TrackedOrder::from(report)),
+        };
+        if order
+            .last_execution_id
+            .map_or(false, |execution_id| report.execution_id <= execution_id)
+        {
+            return Ok(false);
+        }
+        if !is_legal_transition(order.status, report.order_status)
+            || report.executed_qty < order.executed_qty
+        {
+            return Err(IllegalTransition {
+                symbol: report.symbol.clone(),
+                order_id: report.order_id,
+                execution_type: report.execution_type,
+                from: order.status,
+                to: report.order_status,
+            }
+            .into());
+        }
+        match report.execution_type {
+            ExecutionType::Trade if !report.commission_asset.is_empty() => {
+                let commission = order
+                    .commissions
+                    .get(&report.commission_asset)
+                    .copied()
+                    .unwrap_or(Decimal::ZERO)
+                    .checked_add(report.commission)
+                    .ok_or_else(|| CommissionOverflow {
+                        symbol: report.symbol.clone(),
+                        order_id: report.order_id,
+                        commission_asset: report.commission_asset.clone(),
+                    })?;
+                order
+                    .commissions
+                    .insert(report.commission_asset.clone(), commission);
+            }
+            ExecutionType::Replaced => {
+                order.amendments.push(Amendment {
+                    transact_time: report.transact_time,
+                    orig_client_order_id: report.orig_client_order_id.clone(),
+                    client_order_id: report.client_order_id.clone(),
+                    orig_qty: report.orig_qty,
+                });
+                order.client_order_id = report.client_order_id.clone();
+            }
+            _ => {}
+        }
+        order.status = report.order_status;
+        order.orig_qty = report.orig_qty;
+        order.executed_qty = report.executed_qty;
+        order.cummulative_quote_qty = report.cummulative_quote_qty;
+        order.prevented_qty = report.prevented_qty;
+        order.update_time = report.transact_time;
+        order.last_execution_id = Some(report.execution_id);
+        // Cancel reports carry the cancel request's ID in `client_order_id`,
+        // so only new orders and amendments change the index.
+        if matches!(
+            report.execution_type,
+            ExecutionType::New | ExecutionType::Replaced
+        ) {
+            let client_key = (report.symbol.clone(), report.client_order_id.clone());
+            self.client_order_ids.insert(client_key, report.order_id);
+        }
+        if let Some(order) = new_order {
+            self.orders.insert(key, order);
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,409 @@
+use crate::{
+    decimal::Decimal,
+    exchange_info::{ExchangeInfo, SymbolFilter},
+};
+use anyhow::anyhow;
+use spot_sbe::{order_side::OrderSide, order_type::OrderType};
+use std::fmt;
+
+/// An order to check before it is sent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrderRequest {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    /// Required for every type except `MARKET`, `STOP_LOSS` and `TAKE_PROFIT`.
+    pub price: Option<Decimal>,
+    pub qty: Decimal,
+    /// In basis points.
+    pub trailing_delta: Option<i64>,
+    pub iceberg_qty: Option<Decimal>,
+}
+
+/// Market and account state that some filters depend on. Filters whose inputs
+/// are missing are not checked.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarketState {
+    /// The average price over the filters' `avgPriceMins`, as returned by
+    /// `avgPrice`. Used by PERCENT_PRICE(_BY_SIDE) and for the notional of
+    /// market orders.
+    pub avg_price: Option<Decimal>,
+    /// Open orders on the symbol.
+    pub open_orders: Option<i64>,
+    /// Open STOP_LOSS(_LIMIT) and TAKE_PROFIT(_LIMIT) orders on the symbol.
+    pub open_algo_orders: Option<i64>,
+    pub open_iceberg_orders: Option<i64>,
+    /// The base asset balance plus the quantity of open BUY orders.
+    pub position: Option<Decimal>,
+}
+
+/// A filter that the order would fail, with the rule it breaks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// The `filterType`, e.g. `PRICE_FILTER`.
+    pub filter: &'static str,
+    pub rule: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.filter, self.rule)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+fn is_market(order_type: OrderType) -> bool {
+    matches!(order_type, OrderType::Market)
+}
+
+fn is_algo(order_type: OrderType) -> bool {
+    matches!(
+        order_type,
+        OrderType::StopLoss
+            | OrderType::StopLossLimit
+            | OrderType::TakeProfit
+            | OrderType::TakeProfitLimit
+    )
+}
+
+/// Whether `value` lies on the grid `min + k * step`. A zero step disables the check.
+fn on_step(value: Decimal, min: Decimal, step: Decimal) -> bool {
+    step.is_zero()
+        || value
+            .checked_sub(min)
+            .and_then(|offset| offset.checked_rem(step))
+            .map_or(false, |remainder| remainder.is_zero())
+}
+
+/// Rounds `value` onto the grid `min + k * step`. Returns `None` on overflow.
+pub fn round_to_step(
+    value: Decimal,
+    min: Decimal,
+    step: Decimal,
+    rounding: Rounding,
+) -> Option<Decimal> {
+    if step.is_zero() || value <= min {
+        return Some(value);
+    }
+    let remainder = value.checked_sub(min)?.checked_rem(step)?;
+    let down = value.checked_sub(remainder)?;
+    match rounding {
+        Rounding::Down => Some(down),
+        Rounding::Up if remainder.is_zero() => Some(down),
+        Rounding::Up => down.checked_add(step),
+    }
+}
+
+/// Rounds a price onto the PRICE_FILTER tick size.
+pub fn round_price(
+    filters: &[SymbolFilter],
+    price: Decimal,
+    rounding: Rounding,
+) -> Option<Decimal> {
+    filters
+        .iter()
+        .find_map(|filter| match filter {
+            SymbolFilter::Price {
+                min_price,
+                tick_size,
+                ..
+            } => Some(round_to_step(price, *min_price, *tick_size, rounding)),
+            _ => None,
+        })
+        .unwrap_or(Some(price))
+}
+
+/// Rounds a quantity down onto the step size of LOT_SIZE, or of
+/// MARKET_LOT_SIZE for market orders.
+pub fn round_qty(filters: &[SymbolFilter], qty: Decimal, order_type: OrderType) -> Option<Decimal> {
+    let mut rounded = qty;
+    for filter in filters {
+        match filter {
+            SymbolFilter::LotSize {
+                min_qty, step_size, ..
+            } => rounded = round_to_step(rounded, *min_qty, *step_size, Rounding::Down)?,
+            SymbolFilter::MarketLotSize {
+                min_qty, step_size, ..
+            } if is_market(order_type) => {
+                rounded = round_to_step(rounded, *min_qty, *step_size, Rounding::Down)?
+            }
+            _ => {}
+        }
+    }
+    Some(rounded)
+}
+
+struct Checker {
+    filter: &'static str,
+    violations: Vec<Violation>,
+}
+
+impl Checker {
+    fn fail(&mut self, rule: String) {
+        self.violations.push(Violation {
+            filter: self.filter,
+            rule,
+        });
+    }
+
+    fn check_range(&mut self, name: &str, value: Decimal, min: Decimal, max: Decimal) {
+        if !min.is_zero() && value < min {
+            self.fail(format!("{name} {value} is below the minimum {min}"));
+        }
+        if !max.is_zero() && value > max {
+            self.fail(format!("{name} {value} is above the maximum {max}"));
+        }
+    }
+
+    fn check_step(&mut self, name: &str, value: Decimal, min: Decimal, step: Decimal) {
+        if !on_step(value, min, step) {
+            self.fail(format!(
+                "{name} {value} minus {min} is not a multiple of {step}"
+            ));
+        }
+    }
+
+    fn check_lot(&mut self, order: &OrderRequest, min: Decimal, max: Decimal, step: Decimal) {
+        self.check_range("quantity", order.qty, min, max);
+        self.check_step("quantity", order.qty, min, step);
+        if let Some(iceberg_qty) = order.iceberg_qty {
+            self.check_range("iceberg quantity", iceberg_qty, min, max);
+            self.check_step("iceberg quantity", iceberg_qty, min, step);
+        }
+    }
+
+    fn check_multiplier(&mut self, price: Decimal, avg_price: Decimal, up: Decimal, down: Decimal) {
+        if let Some(max) = avg_price.checked_mul(up) {
+            if price > max {
+                self.fail(format!(
+                    "price {price} is above {up} x average price {avg_price}"
+                ));
+            }
+        }
+        if let Some(min) = avg_price.checked_mul(down) {
+            if price < min {
+                self.fail(format!(
+                    "price {price} is below {down} x average price {avg_price}"
+                ));
+            }
+        }
+    }
+
+    fn check_count(&mut self, kind: &str, open: Option<i64>, max: i64) {
+        if let Some(open) = open {
+            if open >= max {
+                self.fail(format!(
+                    "{open} open {kind} already reach the maximum {max}"
+                ));
+            }
+        }
+    }
+}
+
+/// Checks `order` against the filters of its symbol in `exchange_info`.
+pub fn validate_order(
+    exchange_info: &ExchangeInfo,
+    order: &OrderRequest,
+    state: &MarketState,
+) -> anyhow::Result<Vec<Violation>> {
+    let symbol = exchange_info
+        .symbols
+        .iter()
+        .find(|symbol| symbol.symbol == order.symbol)
+        .ok_or_else(|| anyhow!("Unknown symbol {:?}", order.symbol))?;
+    Ok(validate_filters(&symbol.filters, order, state))
+}
+
+/// Checks `order` against a symbol's filters, returning every violation.
+pub fn validate_filters(
+    filters: &[SymbolFilter],
+    order: &OrderRequest,
+    state: &MarketState,
+) -> Vec<Violation> {
+    let market = is_market(order.order_type);
+    // Market orders are valued at the average price.
+    let price = if market { state.avg_price } else { order.price };
+    let notional = price.and_then(|price| price.checked_mul(order.qty));
+    let mut checker = Checker {
+        filter: "",
+        violations: Vec::new(),
+    };
+    for filter in filters {
+        match filter {
+            SymbolFilter::Price {
+                min_price,
+                max_price,
+                tick_size,
+            } => {
+                checker.filter = "PRICE_FILTER";
+                if let Some(price) = order.price {
+                    checker.check_range("price", price, *min_price, *max_price);
+                    checker.check_step("price", price, *min_price, *tick_size);
+                }
+            }
+            SymbolFilter::PercentPrice {
+                multiplier_up,
+                multiplier_down,
+                ..
+            } => {
+                checker.filter = "PERCENT_PRICE";
+                if let (Some(price), Some(avg_price)) = (order.price, state.avg_price) {
+                    checker.check_multiplier(price, avg_price, *multiplier_up, *multiplier_down);
+                }
+            }
+            SymbolFilter::PercentPriceBySide {
+                bid_multiplier_up,
+                bid_multiplier_down,
+                ask_multiplier_up,
+                ask_multiplier_down,
+                ..
+            } => {
+                checker.filter = "PERCENT_PRICE_BY_SIDE";
+                let (up, down) = match order.side {
+                    OrderSide::Sell => (ask_multiplier_up, ask_multiplier_down),
+                    _ => (bid_multiplier_up, bid_multiplier_down),
+                };
+                if let (Some(price), Some(avg_price)) = (order.price, state.avg_price) {
+                    checker.check_multiplier(price, avg_price, *up, *down);
+                }
+            }
+            SymbolFilter::LotSize {
+                min_qty,
+                max_qty,
+                step_size,
+            } => {
+                checker.filter = "LOT_SIZE";
+                checker.check_lot(order, *min_qty, *max_qty, *step_size);
+            }
+            SymbolFilter::MarketLotSize {
+                min_qty,
+                max_qty,
+                step_size,
+            } if market => {
+                checker.filter = "MARKET_LOT_SIZE";
+                checker.check_lot(order, *min_qty, *max_qty, *step_size);
+            }
+            SymbolFilter::MinNotional {
+                min_notional,
+                apply_to_market,
+                ..
+            } if !market || *apply_to_market => {
+                checker.filter = "MIN_NOTIONAL";
+                if let Some(notional) = notional {
+                    checker.check_range("notional", notional, *min_notional, Decimal::ZERO);
+                }
+            }
+            SymbolFilter::Notional {
+                min_notional,
+                apply_min_to_market,
+                max_notional,
+                apply_max_to_market,
+                ..
+            } => {
+                checker.filter = "NOTIONAL";
+                if let Some(notional) = notional {
+                    let min = if !market || *apply_min_to_market {
+                        *min_notional
+                    } else {
+                        Decimal::ZERO
+                    };
+                    let max = if !market || *apply_max_to_market {
+                        *max_notional
+                    } else {
+                        Decimal::ZERO
+                    };
+                    checker.check_range("notional", notional, min, max);
+                }
+            }
+            SymbolFilter::IcebergParts { filter_limit } => {
+                checker.filter = "ICEBERG_PARTS";
+                if let Some(iceberg_qty) = order.iceberg_qty.filter(|qty| !qty.is_zero()) {
+                    // The number of parts is qty / icebergQty, rounded up.
+                    let parts = order.qty.checked_div_with_exponent(iceberg_qty, 0);
+                    let whole = order.qty.checked_rem(iceberg_qty).map(|rem| rem.is_zero());
+                    if let (Some(parts), Some(whole)) = (parts, whole) {
+                        let parts = parts.mantissa() + i128::from(!whole);
+                        if parts > i128::from(*filter_limit) {
+                            checker.fail(format!(
+                                "{parts} iceberg parts exceed the limit {filter_limit}"
+                            ));
+                        }
+                    }
+                }
+            }
+            SymbolFilter::MaxPosition { max_position } => {
+                checker.filter = "MAX_POSITION";
+                let position = state
+                    .position
+                    .filter(|_| matches!(order.side, OrderSide::Buy))
+                    .and_then(|position| position.checked_add(order.qty));
+                if let Some(position) = position {
+                    if position > *max_position {
+                        checker.fail(format!(
+                            "position {position} after the order is above the maximum {max_position}"
+                        ));
+                    }
+                }
+            }
+            SymbolFilter::TrailingDelta {
+                min_trailing_above_delta,
+                max_trailing_above_delta,
+                min_trailing_below_delta,
+                max_trailing_below_delta,
+            } => {
+                checker.filter = "TRAILING_DELTA";
+                // Stops that trigger when the price rises use the "above" range.
+                let above = matches!(
+                    (order.order_type, order.side),
+                    (
+                        OrderType::StopLoss | OrderType::StopLossLimit,
+                        OrderSide::Buy
+                    ) | (
+                        OrderType::TakeProfit | OrderType::TakeProfitLimit,
+                        OrderSide::Sell
+                    )
+                );
+                let (min, max, direction) = if above {
+                    (min_trailing_above_delta, max_trailing_above_delta, "above")
+                } else {
+                    (min_trailing_below_delta, max_trailing_below_delta, "below")
+                };
+                if let Some(delta) = order.trailing_delta {
+                    if delta < *min || delta > *max {
+                        checker.fail(format!(
+                            "trailing delta {delta} is outside the {direction} range [{min}, {max}]"
+                        ));
+                    }
+                }
+            }
+            SymbolFilter::MaxNumOrders { max_num_orders } => {
+                checker.filter = "MAX_NUM_ORDERS";
+                checker.check_count("orders", state.open_orders, *max_num_orders);
+            }
+            SymbolFilter::MaxNumAlgoOrders {
+                max_num_algo_orders,
+            } if is_algo(order.order_type) => {
+                checker.filter = "MAX_NUM_ALGO_ORDERS";
+                checker.check_count("algo orders", state.open_algo_orders, *max_num_algo_orders);
+            }
+            SymbolFilter::MaxNumIcebergOrders {
+                max_num_iceberg_orders,
+            } if order.iceberg_qty.is_some() => {
+                checker.filter = "MAX_NUM_ICEBERG_ORDERS";
+                checker.check_count(
+                    "iceberg orders",
+                    state.open_iceberg_orders,
+                    *max_num_iceberg_orders,
+                );
+            }
+            // Order lists, amendments and T+ selling are not checked per order.
+            _ => {}
+        }
+    }
+    checker.violations
+}
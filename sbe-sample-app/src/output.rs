@@ -0,0 +1,63 @@
+use crate::decimal::{with_decimal_format, DecimalFormat};
+use anyhow::bail;
+use serde::Serialize;
+use serde_json::value::RawValue;
+use std::str::FromStr;
+
+/// How decoded messages are rendered on STDOUT.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Yaml,
+    /// Compact JSON on a single line.
+    Json,
+    JsonPretty,
+    /// Compact JSON with one line per array element, so that responses holding
+    /// a list (trades, klines, orders, ...) can be streamed into line-based tools.
+    Ndjson,
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Ok(match s {
+            "yaml" => Self::Yaml,
+            "json" => Self::Json,
+            "json-pretty" => Self::JsonPretty,
+            "ndjson" => Self::Ndjson,
+            _ => bail!("Unknown output format {s:?}; expected yaml, json, json-pretty or ndjson"),
+        })
+    }
+}
+
+impl OutputFormat {
+    /// Renders `value` without a trailing newline, with its decimals in `decimals`.
+    pub fn render<T: Serialize>(
+        self,
+        value: &T,
+        decimals: DecimalFormat,
+    ) -> anyhow::Result<String> {
+        with_decimal_format(decimals, || self.render_value(value))
+    }
+
+    fn render_value<T: Serialize>(self, value: &T) -> anyhow::Result<String> {
+        Ok(match self {
+            Self::Yaml => serde_yaml::to_string(value)?,
+            Self::Json => serde_json::to_string(value)?,
+            Self::JsonPretty => serde_json::to_string_pretty(value)?,
+            Self::Ndjson => {
+                let json = serde_json::to_string(value)?;
+                if json.starts_with('[') {
+                    // Split the array without going through `serde_json::Value`,
+                    // which would reorder keys and reject 128-bit integers.
+                    let elements: Vec<&RawValue> = serde_json::from_str(&json)?;
+                    let lines: Vec<&str> = elements.iter().map(|element| element.get()).collect();
+                    lines.join("\n")
+                } else {
+                    json
+                }
+            }
+        })
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Serialize, Serializer};
 use spot_sbe::{rate_limit_interval::RateLimitInterval, rate_limit_type::RateLimitType};
 
-#[derive(Serialize)]
+#[derive(Debug, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RateLimit {
     #[serde(serialize_with = "serialize_rate_limit_type")]
@@ -10,6 +10,7 @@ pub struct RateLimit {
     pub interval: RateLimitInterval,
     pub interval_num: u8,
     pub limit: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub count: Option<i64>,
 }
 
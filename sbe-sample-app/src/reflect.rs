@@ -0,0 +1,430 @@
+use crate::schema::{
+    Body, ByteOrder, CompositeType, EncodedType, Field, Group, Presence, Primitive, Schema, Type,
+};
+use anyhow::{anyhow, bail};
+use serde::{
+    ser::{SerializeMap, SerializeSeq},
+    Serialize, Serializer,
+};
+use spot_sbe::SbeErr;
+use std::fmt::Write;
+
+/// A field value decoded by walking the schema rather than with generated code.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// An optional field holding its null value, or a field, group or var-data
+    /// newer than the message's version.
+    Null,
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    /// The name of an enum's valid value; `None` for values the schema does not
+    /// know, such as those added by a newer version.
+    Enum {
+        name: Option<String>,
+        value: i64,
+    },
+    /// The names of the set's choices that are set.
+    Set(Vec<String>),
+    String(String),
+    /// A `uint8` array or binary var-data. Rendered as hex.
+    Bytes(Vec<u8>),
+    /// A fixed-length array of numbers.
+    Array(Vec<Value>),
+    /// A message, a group entry or a composite, with members in wire order.
+    Struct(Vec<(String, Value)>),
+    /// A repeating group's entries, each a `Struct`.
+    Group(Vec<Value>),
+}
+
+impl Value {
+    /// The member with the given name, for `Struct` values.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        match self {
+            Self::Struct(members) => members
+                .iter()
+                .find(|(member, _)| member == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Null => serializer.serialize_none(),
+            Self::Int(value) => serializer.serialize_i64(*value),
+            Self::UInt(value) => serializer.serialize_u64(*value),
+            Self::Float(value) => serializer.serialize_f64(*value),
+            Self::Enum {
+                name: Some(name), ..
+            } => serializer.serialize_str(name),
+            Self::Enum { name: None, value } => serializer.serialize_i64(*value),
+            Self::Set(choices) => choices.serialize(serializer),
+            Self::String(value) => serializer.serialize_str(value),
+            Self::Bytes(bytes) => {
+                let mut hex = String::with_capacity(bytes.len() * 2);
+                for byte in bytes {
+                    write!(hex, "{byte:02x}").unwrap();
+                }
+                serializer.serialize_str(&hex)
+            }
+            Self::Array(values) | Self::Group(values) => {
+                let mut seq = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    seq.serialize_element(value)?;
+                }
+                seq.end()
+            }
+            Self::Struct(members) => {
+                let mut map = serializer.serialize_map(Some(members.len()))?;
+                for (name, value) in members {
+                    map.serialize_entry(name, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+/// A message decoded by [`decode`]. Serializes as `{name: value}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReflectedMessage {
+    pub name: String,
+    pub template_id: u16,
+    pub schema_id: u16,
+    /// The version from the message header, which may differ from the schema's.
+    pub version: u16,
+    /// The number of bytes the message occupies, header included.
+    pub length: usize,
+    pub value: Value,
+}
+
+impl Serialize for ReflectedMessage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&self.name, &self.value)?;
+        map.end()
+    }
+}
+
+/// Decodes any message of `schema` into a [`Value`] tree.
+///
+/// The block lengths from the message header and group dimensions are
+/// honoured, so messages of a newer version than the schema decode with their
+/// new fields skipped, and fields newer than the message's version are
+/// `Null`. The header's schema ID is not checked.
+pub fn decode(schema: &Schema, buffer: &[u8]) -> anyhow::Result<ReflectedMessage> {
+    let reader = WireReader {
+        buffer,
+        byte_order: schema.byte_order,
+    };
+    let header = |name| -> anyhow::Result<u64> {
+        let (offset, primitive) = schema.header.primitive_member(name)?;
+        reader.unsigned(offset, primitive.size())
+    };
+    let block_length = header("blockLength")?;
+    let template_id = header("templateId")?;
+    let schema_id = header("schemaId")?;
+    let version = header("version")?;
+    let template_id = u16::try_from(template_id)?;
+    let message = schema
+        .message(template_id)
+        .ok_or(SbeErr::UnknownTemplateId { template_id })?;
+    let mut decoder = BodyDecoder {
+        reader,
+        version: u16::try_from(version)?,
+        limit: composite_size(&schema.header),
+    };
+    let value = decoder.body(&message.body, usize::try_from(block_length)?)?;
+    Ok(ReflectedMessage {
+        name: message.name.clone(),
+        template_id,
+        schema_id: u16::try_from(schema_id)?,
+        version: decoder.version,
+        length: decoder.limit,
+        value,
+    })
+}
+
+fn composite_size(composite: &CompositeType) -> usize {
+    Type::Composite(composite.clone()).size()
+}
+
+#[derive(Clone, Copy)]
+struct WireReader<'a> {
+    buffer: &'a [u8],
+    byte_order: ByteOrder,
+}
+
+impl<'a> WireReader<'a> {
+    fn bytes(&self, offset: usize, length: usize) -> Result<&'a [u8], SbeErr> {
+        offset
+            .checked_add(length)
+            .and_then(|end| self.buffer.get(offset..end))
+            .ok_or(SbeErr::BufferTooShort {
+                offset,
+                needed: length,
+                available: self.buffer.len().saturating_sub(offset),
+            })
+    }
+
+    fn unsigned(&self, offset: usize, size: usize) -> anyhow::Result<u64> {
+        let bytes = self.bytes(offset, size)?;
+        let fold = |value: u64, byte: &u8| value << 8 | u64::from(*byte);
+        Ok(match self.byte_order {
+            ByteOrder::LittleEndian => bytes.iter().rev().fold(0, fold),
+            ByteOrder::BigEndian => bytes.iter().fold(0, fold),
+        })
+    }
+
+    fn primitive(&self, offset: usize, primitive: Primitive) -> anyhow::Result<Value> {
+        let size = primitive.size();
+        let raw = self.unsigned(offset, size)?;
+        Ok(match primitive {
+            Primitive::Float => Value::Float(f64::from(f32::from_bits(raw as u32))),
+            Primitive::Double => Value::Float(f64::from_bits(raw)),
+            primitive if primitive.is_signed() => Value::Int(sign_extend(raw, size)),
+            _ => Value::UInt(raw),
+        })
+    }
+
+    /// Reads an enum's encoding, which may be signed.
+    fn integer(&self, offset: usize, primitive: Primitive) -> anyhow::Result<i64> {
+        let raw = self.unsigned(offset, primitive.size())?;
+        Ok(if primitive.is_signed() {
+            sign_extend(raw, primitive.size())
+        } else {
+            raw as i64
+        })
+    }
+}
+
+fn sign_extend(raw: u64, size: usize) -> i64 {
+    let shift = 64 - 8 * size as u32;
+    ((raw << shift) as i64) >> shift
+}
+
+/// The value a primitive holds when an optional field is absent.
+fn null_value(primitive: Primitive, null_value: Option<&str>) -> Option<Value> {
+    let bits = 8 * primitive.size() as u32;
+    Some(match (primitive, null_value) {
+        (Primitive::Float | Primitive::Double, Some(value)) => Value::Float(value.parse().ok()?),
+        (Primitive::Float | Primitive::Double, None) => Value::Float(f64::NAN),
+        (primitive, Some(value)) if primitive.is_signed() => Value::Int(value.parse().ok()?),
+        (primitive, None) if primitive.is_signed() => Value::Int(-1 << (bits - 1)),
+        (_, Some(value)) => Value::UInt(value.parse().ok()?),
+        (Primitive::Char, None) => Value::UInt(0),
+        (_, None) => Value::UInt(u64::MAX >> (64 - bits)),
+    })
+}
+
+fn is_null(value: &Value, encoded: &EncodedType) -> bool {
+    match (
+        value,
+        null_value(encoded.primitive, encoded.null_value.as_deref()),
+    ) {
+        (Value::Float(value), Some(Value::Float(null))) => {
+            value == &null || (value.is_nan() && null.is_nan())
+        }
+        (value, null) => Some(value) == null.as_ref(),
+    }
+}
+
+/// Decodes blocks, groups and var-data in wire order, tracking the end of
+/// what was read so far.
+struct BodyDecoder<'a> {
+    reader: WireReader<'a>,
+    version: u16,
+    limit: usize,
+}
+
+impl BodyDecoder<'_> {
+    fn body(&mut self, body: &Body, block_length: usize) -> anyhow::Result<Value> {
+        let block = self.limit;
+        self.reader.bytes(block, block_length)?;
+        self.limit += block_length;
+        let mut members = Vec::new();
+        for field in &body.fields {
+            let value = if field.since_version > self.version {
+                Value::Null
+            } else if let Presence::Constant(value) = &field.presence {
+                constant(&field.ty, value)?
+            } else if field.offset + field.ty.size() > block_length {
+                // Written by an older version, before the field was added.
+                Value::Null
+            } else {
+                self.field(field, block + field.offset)?
+            };
+            members.push((field.name.clone(), value));
+        }
+        for group in &body.groups {
+            let value = if group.since_version > self.version {
+                Value::Null
+            } else {
+                self.group(group)?
+            };
+            members.push((group.name.clone(), value));
+        }
+        for data in &body.data {
+            if data.since_version > self.version {
+                members.push((data.name.clone(), Value::Null));
+                continue;
+            }
+            let (length_offset, length_primitive) = data.ty.primitive_member("length")?;
+            let data_offset = data
+                .ty
+                .member("varData")
+                .ok_or_else(|| anyhow!("Composite {} has no varData member", data.ty.name))?
+                .offset;
+            let length = self
+                .reader
+                .unsigned(self.limit + length_offset, length_primitive.size())?;
+            let start = self.limit + data_offset;
+            let bytes = self.reader.bytes(start, usize::try_from(length)?)?;
+            self.limit = start + bytes.len();
+            let value = match data.character_encoding() {
+                Some(_) => match std::str::from_utf8(bytes) {
+                    Ok(text) => Value::String(text.to_owned()),
+                    Err(_) => Value::Bytes(bytes.to_vec()),
+                },
+                None => Value::Bytes(bytes.to_vec()),
+            };
+            members.push((data.name.clone(), value));
+        }
+        Ok(Value::Struct(members))
+    }
+
+    fn group(&mut self, group: &Group) -> anyhow::Result<Value> {
+        let (block_length_offset, block_length_primitive) =
+            group.dimension.primitive_member("blockLength")?;
+        let (count_offset, count_primitive) = group.dimension.primitive_member("numInGroup")?;
+        let block_length = self.reader.unsigned(
+            self.limit + block_length_offset,
+            block_length_primitive.size(),
+        )?;
+        let count = self
+            .reader
+            .unsigned(self.limit + count_offset, count_primitive.size())?;
+        self.limit += composite_size(&group.dimension);
+        let block_length = usize::try_from(block_length)?;
+        // Check the count against the remaining bytes before decoding, so that
+        // a corrupt count fails fast rather than allocating billions of entries.
+        let available = self.reader.buffer.len().saturating_sub(self.limit);
+        let needed = usize::try_from(count)?.saturating_mul(block_length.max(1));
+        if needed > available {
+            bail!(SbeErr::BufferTooShort {
+                offset: self.limit,
+                needed,
+                available,
+            });
+        }
+        let entries = (0..count)
+            .map(|_| self.body(&group.body, block_length))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Value::Group(entries))
+    }
+
+    fn field(&self, field: &Field, offset: usize) -> anyhow::Result<Value> {
+        self.typed(&field.ty, offset, field.presence == Presence::Optional)
+    }
+
+    fn typed(&self, ty: &Type, offset: usize, optional: bool) -> anyhow::Result<Value> {
+        Ok(match ty {
+            Type::Encoded(encoded) => {
+                let optional = optional || encoded.presence == Presence::Optional;
+                match (encoded.primitive, encoded.length) {
+                    (Primitive::Char, length) => {
+                        let bytes = self.reader.bytes(offset, length)?;
+                        let end = bytes.iter().position(|byte| *byte == 0).unwrap_or(length);
+                        match std::str::from_utf8(&bytes[..end]) {
+                            Ok("") if optional => Value::Null,
+                            Ok(text) => Value::String(text.to_owned()),
+                            Err(_) => Value::Bytes(bytes.to_vec()),
+                        }
+                    }
+                    (primitive, 1) => {
+                        let value = self.reader.primitive(offset, primitive)?;
+                        if optional && is_null(&value, encoded) {
+                            Value::Null
+                        } else {
+                            value
+                        }
+                    }
+                    (Primitive::UInt8, length) => {
+                        Value::Bytes(self.reader.bytes(offset, length)?.to_vec())
+                    }
+                    (primitive, length) => Value::Array(
+                        (0..length)
+                            .map(|index| {
+                                self.reader
+                                    .primitive(offset + index * primitive.size(), primitive)
+                            })
+                            .collect::<anyhow::Result<_>>()?,
+                    ),
+                }
+            }
+            Type::Enum(ty) => {
+                let value = self.reader.integer(offset, ty.encoding)?;
+                match ty.value_name(value) {
+                    Some(name) => Value::Enum {
+                        name: Some(name.to_owned()),
+                        value,
+                    },
+                    None if Some(self.reader.primitive(offset, ty.encoding)?)
+                        == null_value(ty.encoding, None) =>
+                    {
+                        Value::Null
+                    }
+                    None => Value::Enum { name: None, value },
+                }
+            }
+            Type::Set(ty) => {
+                let bits = self.reader.unsigned(offset, ty.encoding.size())?;
+                Value::Set(
+                    ty.choices
+                        .iter()
+                        .filter(|(_, bit)| bits.checked_shr(*bit).unwrap_or(0) & 1 != 0)
+                        .map(|(name, _)| name.clone())
+                        .collect(),
+                )
+            }
+            Type::Composite(ty) => Value::Struct(
+                ty.members
+                    .iter()
+                    .map(|member| {
+                        Ok((
+                            member.name.clone(),
+                            self.typed(&member.ty, offset + member.offset, optional)?,
+                        ))
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+            ),
+        })
+    }
+}
+
+/// The value of a constant field, which is not on the wire.
+fn constant(ty: &Type, value: &str) -> anyhow::Result<Value> {
+    Ok(match ty {
+        Type::Enum(ty) => Value::Enum {
+            name: Some(value.to_owned()),
+            value: ty
+                .values
+                .iter()
+                .find(|(name, _)| name == value)
+                .map(|(_, value)| *value)
+                .ok_or_else(|| anyhow!("Enum {} has no {value} value", ty.name))?,
+        },
+        Type::Encoded(encoded) if encoded.primitive == Primitive::Char => {
+            Value::String(value.to_owned())
+        }
+        Type::Encoded(encoded) => match encoded.primitive {
+            Primitive::Float | Primitive::Double => Value::Float(value.parse()?),
+            primitive if primitive.is_signed() => Value::Int(value.parse()?),
+            _ => Value::UInt(value.parse()?),
+        },
+        ty => bail!("Constant {} must be an enum or a primitive", ty.name()),
+    })
+}
@@ -0,0 +1,110 @@
+//! Decoding of a single SBE payload into the app's rendered output, shared by
+//! every command of the binary.
+
+use crate::{
+    decimal::DecimalFormat,
+    exchange_info::decode_error,
+    message::{decode_any, upgrade, SpotMessage},
+    output::OutputFormat,
+    reflect,
+    response::decode_response,
+    schema::{spot_schema, spot_schema_for},
+    user_data::{decode_user_data_event, is_user_data_event},
+    websocket::decode_websocket_response,
+};
+use anyhow::bail;
+use spot_sbe::{
+    error_response_codec, message_header_codec::MessageHeaderDecoder, ReadBuf, SbeSchema,
+    SBE_SCHEMA_VERSION,
+};
+use std::fmt;
+
+/// An error response rendered in the requested output format, to be printed
+/// as is rather than as an application error.
+#[derive(Debug)]
+pub struct RenderedError(pub String);
+
+impl fmt::Display for RenderedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for RenderedError {}
+
+/// Decodes an SBE payload and renders it with its decimals in `decimals`,
+/// failing with a [`RenderedError`] for error responses.
+pub fn render_payload(
+    payload: &[u8],
+    output: OutputFormat,
+    decimals: DecimalFormat,
+    reflect: bool,
+) -> anyhow::Result<String> {
+    if reflect {
+        return render_reflected(payload, output, decimals);
+    }
+    let payload = upgrade(payload)?;
+    let payload = payload.as_ref();
+    let message = decode_any(payload)?;
+    let version = message.acting_version();
+    if !matches!(message, SpotMessage::ErrorResponse(_)) && version != SBE_SCHEMA_VERSION {
+        eprintln!(
+            "Warning: Unexpected schema version. Got {version}; expected {SBE_SCHEMA_VERSION}"
+        );
+        // Schemas with the same ID are expected to be backwards compatible.
+    }
+    Ok(match message {
+        SpotMessage::WebSocketResponse(_) => {
+            let websocket_meta = decode_websocket_response(payload)?;
+            let rendered = output.render(&websocket_meta, decimals)?;
+            if websocket_meta.is_error() {
+                return Err(RenderedError(rendered).into());
+            }
+            rendered
+        }
+        SpotMessage::ErrorResponse(decoder) => {
+            return Err(RenderedError(output.render(&decode_error(decoder)?, decimals)?).into());
+        }
+        message if is_user_data_event(&message) => {
+            output.render(&decode_user_data_event(message)?, decimals)?
+        }
+        message => output.render(&decode_response(message)?, decimals)?,
+    })
+}
+
+/// Decodes any SBE payload by walking the embedded schema with the header's
+/// schema ID, failing with a [`RenderedError`] for error responses.
+fn render_reflected(
+    payload: &[u8],
+    output: OutputFormat,
+    decimals: DecimalFormat,
+) -> anyhow::Result<String> {
+    let header = MessageHeaderDecoder::default().try_wrap(ReadBuf::new(payload), 0)?;
+    let is_error = header.template_id() == error_response_codec::SBE_TEMPLATE_ID;
+    let schema = match spot_schema_for(header.schema_id()) {
+        Some(schema) => schema,
+        // Error responses are expected to be backwards compatible across all
+        // schema IDs.
+        None if is_error => spot_schema(),
+        None => bail!(
+            "Unexpected schema ID. Got {}; expected one of {:?}",
+            header.schema_id(),
+            SbeSchema::ALL
+                .iter()
+                .map(|schema| schema.id())
+                .collect::<Vec<_>>()
+        ),
+    };
+    let message = reflect::decode(schema, payload)?;
+    if message.version > schema.version {
+        eprintln!(
+            "Warning: Schema version {} is newer than {}; fields added since are skipped",
+            message.version, schema.version
+        );
+    }
+    let rendered = output.render(&message, decimals)?;
+    if is_error {
+        return Err(RenderedError(rendered).into());
+    }
+    Ok(rendered)
+}
@@ -0,0 +1,184 @@
+use crate::{
+    account::{
+        decode_account, decode_account_allocations, decode_account_commission,
+        decode_account_order_rate_limits, decode_account_prevented_matches, decode_account_trades,
+        Account, AccountCommission, AccountTrade, Allocation, PreventedMatch,
+    },
+    exchange_info::{decode_exchange_info, ExchangeInfo},
+    execution::{
+        decode_cancel_open_orders, decode_cancel_order, decode_cancel_order_list,
+        decode_cancel_replace_order, decode_new_order_ack, decode_new_order_full,
+        decode_new_order_list_ack, decode_new_order_list_full, decode_new_order_list_result,
+        decode_new_order_result, decode_order_amend_keep_priority, decode_order_amendments,
+        CancelReplaceOrder, CanceledOpenOrder, CanceledOrder, NewOrderAck, NewOrderFull,
+        NewOrderResult, OrderAmendKeepPriority, OrderAmendment, OrderListReport,
+    },
+    market_data::{
+        decode_agg_trades, decode_average_price, decode_book_ticker, decode_book_ticker_symbol,
+        decode_depth, decode_klines, decode_price_ticker, decode_price_ticker_symbol,
+        decode_ticker_24h_full, decode_ticker_24h_mini, decode_ticker_24h_symbol_full,
+        decode_ticker_24h_symbol_mini, decode_ticker_full, decode_ticker_mini,
+        decode_ticker_symbol_full, decode_ticker_symbol_mini, decode_trades, AggTrade,
+        AveragePrice, BookTicker, Depth, Kline, MiniTicker, PriceTicker, Ticker, Ticker24h, Trade,
+    },
+    message::SpotMessage,
+    order::{decode_order, decode_order_list, decode_order_lists, decode_orders, Order, OrderList},
+    rate_limit::RateLimit,
+};
+use anyhow::bail;
+use serde::Serialize;
+
+/// An owned, serializable decoding of a successful response.
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Response {
+    ExchangeInfo(ExchangeInfo),
+    Depth(Depth),
+    Trades(Vec<Trade>),
+    AggTrades(Vec<AggTrade>),
+    Klines(Vec<Kline>),
+    AveragePrice(AveragePrice),
+    Ticker24h(Ticker24h),
+    Tickers24h(Vec<Ticker24h>),
+    Ticker(Ticker),
+    Tickers(Vec<Ticker>),
+    MiniTicker(MiniTicker),
+    MiniTickers(Vec<MiniTicker>),
+    PriceTicker(PriceTicker),
+    PriceTickers(Vec<PriceTicker>),
+    BookTicker(BookTicker),
+    BookTickers(Vec<BookTicker>),
+    Account(Account),
+    AccountTrades(Vec<AccountTrade>),
+    AccountCommission(AccountCommission),
+    AccountOrderRateLimits(Vec<RateLimit>),
+    AccountPreventedMatches(Vec<PreventedMatch>),
+    AccountAllocations(Vec<Allocation>),
+    Order(Order),
+    Orders(Vec<Order>),
+    OrderList(OrderList),
+    OrderLists(Vec<OrderList>),
+    NewOrderAck(NewOrderAck),
+    NewOrderResult(NewOrderResult),
+    NewOrderFull(NewOrderFull),
+    NewOrderListAck(OrderListReport<NewOrderAck>),
+    NewOrderListResult(OrderListReport<NewOrderResult>),
+    NewOrderListFull(OrderListReport<NewOrderFull>),
+    CanceledOrder(CanceledOrder),
+    CanceledOrderList(OrderListReport<CanceledOrder>),
+    CanceledOpenOrders(Vec<CanceledOpenOrder>),
+    CancelReplaceOrder(CancelReplaceOrder),
+    OrderAmendKeepPriority(OrderAmendKeepPriority),
+    OrderAmendments(Vec<OrderAmendment>),
+}
+
+pub fn decode_response(message: SpotMessage<'_>) -> anyhow::Result<Response> {
+    Ok(match message {
+        SpotMessage::ExchangeInfoResponse(decoder) => {
+            Response::ExchangeInfo(decode_exchange_info(decoder)?)
+        }
+        SpotMessage::DepthResponse(decoder) => Response::Depth(decode_depth(decoder)?),
+        SpotMessage::TradesResponse(decoder) => Response::Trades(decode_trades(decoder)?),
+        SpotMessage::AggTradesResponse(decoder) => Response::AggTrades(decode_agg_trades(decoder)?),
+        SpotMessage::KlinesResponse(decoder) => Response::Klines(decode_klines(decoder)?),
+        SpotMessage::AveragePriceResponse(decoder) => {
+            Response::AveragePrice(decode_average_price(decoder)?)
+        }
+        SpotMessage::Ticker24hSymbolFullResponse(decoder) => {
+            Response::Ticker24h(decode_ticker_24h_symbol_full(decoder)?)
+        }
+        SpotMessage::Ticker24hFullResponse(decoder) => {
+            Response::Tickers24h(decode_ticker_24h_full(decoder)?)
+        }
+        SpotMessage::Ticker24hSymbolMiniResponse(decoder) => {
+            Response::MiniTicker(decode_ticker_24h_symbol_mini(decoder)?)
+        }
+        SpotMessage::Ticker24hMiniResponse(decoder) => {
+            Response::MiniTickers(decode_ticker_24h_mini(decoder)?)
+        }
+        SpotMessage::TickerSymbolFullResponse(decoder) => {
+            Response::Ticker(decode_ticker_symbol_full(decoder)?)
+        }
+        SpotMessage::TickerFullResponse(decoder) => Response::Tickers(decode_ticker_full(decoder)?),
+        SpotMessage::TickerSymbolMiniResponse(decoder) => {
+            Response::MiniTicker(decode_ticker_symbol_mini(decoder)?)
+        }
+        SpotMessage::TickerMiniResponse(decoder) => {
+            Response::MiniTickers(decode_ticker_mini(decoder)?)
+        }
+        SpotMessage::PriceTickerSymbolResponse(decoder) => {
+            Response::PriceTicker(decode_price_ticker_symbol(decoder)?)
+        }
+        SpotMessage::PriceTickerResponse(decoder) => {
+            Response::PriceTickers(decode_price_ticker(decoder)?)
+        }
+        SpotMessage::BookTickerSymbolResponse(decoder) => {
+            Response::BookTicker(decode_book_ticker_symbol(decoder)?)
+        }
+        SpotMessage::BookTickerResponse(decoder) => {
+            Response::BookTickers(decode_book_ticker(decoder)?)
+        }
+        SpotMessage::AccountResponse(decoder) => Response::Account(decode_account(decoder)?),
+        SpotMessage::AccountTradesResponse(decoder) => {
+            Response::AccountTrades(decode_account_trades(decoder)?)
+        }
+        SpotMessage::AccountCommissionResponse(decoder) => {
+            Response::AccountCommission(decode_account_commission(decoder)?)
+        }
+        SpotMessage::AccountOrderRateLimitResponse(decoder) => {
+            Response::AccountOrderRateLimits(decode_account_order_rate_limits(decoder)?)
+        }
+        SpotMessage::AccountPreventedMatchesResponse(decoder) => {
+            Response::AccountPreventedMatches(decode_account_prevented_matches(decoder)?)
+        }
+        SpotMessage::AccountAllocationsResponse(decoder) => {
+            Response::AccountAllocations(decode_account_allocations(decoder)?)
+        }
+        SpotMessage::OrderResponse(decoder) => Response::Order(decode_order(decoder)?),
+        SpotMessage::OrdersResponse(decoder) => Response::Orders(decode_orders(decoder)?),
+        SpotMessage::OrderListResponse(decoder) => Response::OrderList(decode_order_list(decoder)?),
+        SpotMessage::OrderListsResponse(decoder) => {
+            Response::OrderLists(decode_order_lists(decoder)?)
+        }
+        SpotMessage::NewOrderAckResponse(decoder) => {
+            Response::NewOrderAck(decode_new_order_ack(decoder)?)
+        }
+        SpotMessage::NewOrderResultResponse(decoder) => {
+            Response::NewOrderResult(decode_new_order_result(decoder)?)
+        }
+        SpotMessage::NewOrderFullResponse(decoder) => {
+            Response::NewOrderFull(decode_new_order_full(decoder)?)
+        }
+        SpotMessage::NewOrderListAckResponse(decoder) => {
+            Response::NewOrderListAck(decode_new_order_list_ack(decoder)?)
+        }
+        SpotMessage::NewOrderListResultResponse(decoder) => {
+            Response::NewOrderListResult(decode_new_order_list_result(decoder)?)
+        }
+        SpotMessage::NewOrderListFullResponse(decoder) => {
+            Response::NewOrderListFull(decode_new_order_list_full(decoder)?)
+        }
+        SpotMessage::CancelOrderResponse(decoder) => {
+            Response::CanceledOrder(decode_cancel_order(decoder)?)
+        }
+        SpotMessage::CancelOrderListResponse(decoder) => {
+            Response::CanceledOrderList(decode_cancel_order_list(decoder)?)
+        }
+        SpotMessage::CancelOpenOrdersResponse(decoder) => {
+            Response::CanceledOpenOrders(decode_cancel_open_orders(decoder)?)
+        }
+        SpotMessage::CancelReplaceOrderResponse(decoder) => {
+            Response::CancelReplaceOrder(decode_cancel_replace_order(decoder)?)
+        }
+        SpotMessage::OrderAmendKeepPriorityResponse(decoder) => {
+            Response::OrderAmendKeepPriority(decode_order_amend_keep_priority(decoder)?)
+        }
+        SpotMessage::OrderAmendmentsResponse(decoder) => {
+            Response::OrderAmendments(decode_order_amendments(decoder)?)
+        }
+        message => {
+            bail!("Unexpected template ID {}", message.template_id());
+        }
+    })
+}
@@ -0,0 +1,117 @@
+use crate::{
+    exchange_info::{decode_error, ErrorResponse},
+    message::{decode_any, upgrade, SpotMessage},
+    response::{decode_response, Response},
+};
+use anyhow::anyhow;
+use spot_sbe::{SBE_SCHEMA_ID, SBE_SCHEMA_VERSION};
+use std::{fmt, io::Read, sync::Arc};
+
+pub const SBE_CONTENT_TYPE: &str = "application/sbe";
+
+/// The `X-MBX-SBE` header value selecting this crate's schema, e.g. `3:4`.
+pub fn sbe_header_value() -> String {
+    format!("{SBE_SCHEMA_ID}:{SBE_SCHEMA_VERSION}")
+}
+
+#[derive(Debug)]
+pub enum RestError {
+    /// The server answered with an SBE `ErrorResponse`.
+    Api { status: u16, error: ErrorResponse },
+    /// The request failed, or the body could not be decoded.
+    Other(anyhow::Error),
+}
+
+impl RestError {
+    /// How long to back off before retrying, for rate limit and IP ban errors.
+    pub fn retry_after(&self) -> Option<i64> {
+        match self {
+            Self::Api { error, .. } => error.retry_after,
+            Self::Other(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for RestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Api { status, error } => {
+                write!(f, "HTTP {status}: code {}: {}", error.code, error.msg)
+            }
+            Self::Other(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for RestError {}
+
+impl From<anyhow::Error> for RestError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::Other(error)
+    }
+}
+
+/// A blocking REST API client that requests SBE responses.
+pub struct RestClient {
+    agent: ureq::Agent,
+    api_key: Option<String>,
+}
+
+impl RestClient {
+    pub fn new() -> anyhow::Result<Self> {
+        let agent = ureq::AgentBuilder::new()
+            .tls_connector(Arc::new(native_tls::TlsConnector::new()?))
+            .build();
+        Ok(Self {
+            agent,
+            api_key: None,
+        })
+    }
+
+    /// Sends `key` as `X-MBX-APIKEY`. Requests still need to be signed by the caller.
+    pub fn with_api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Sends a request and returns the SBE body of a successful response.
+    /// `ErrorResponse` bodies are returned as [`RestError::Api`].
+    pub fn fetch(&self, method: &str, url: &str) -> Result<Vec<u8>, RestError> {
+        let mut request = self
+            .agent
+            .request(method, url)
+            .set("Accept", SBE_CONTENT_TYPE)
+            .set("X-MBX-SBE", &sbe_header_value());
+        if let Some(api_key) = &self.api_key {
+            request = request.set("X-MBX-APIKEY", api_key);
+        }
+        let response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(error) => return Err(anyhow!(error).into()),
+        };
+        let status = response.status();
+        let is_sbe = response.content_type() == SBE_CONTENT_TYPE;
+        let mut body = Vec::new();
+        response
+            .into_reader()
+            .read_to_end(&mut body)
+            .map_err(anyhow::Error::from)?;
+        if !is_sbe {
+            // e.g. a JSON error when the requested schema is not supported.
+            let body = String::from_utf8_lossy(&body);
+            return Err(anyhow!("HTTP {status}: expected an SBE response; got {body}").into());
+        }
+        if let SpotMessage::ErrorResponse(decoder) = decode_any(&upgrade(&body)?)? {
+            let error = decode_error(decoder)?;
+            return Err(RestError::Api { status, error });
+        }
+        Ok(body)
+    }
+
+    /// Sends a `GET` request and decodes the response.
+    pub fn get(&self, url: &str) -> Result<Response, RestError> {
+        let body = self.fetch("GET", url)?;
+        Ok(decode_response(decode_any(&upgrade(&body)?)?)?)
+    }
+}
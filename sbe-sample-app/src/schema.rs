@@ -0,0 +1,26 @@
+use spot_sbe::SbeSchema;
+use std::sync::OnceLock;
+
+pub use sbe_codegen::schema::*;
+
+/// The schemas embedded in `spot_sbe`, in `SbeSchema::ALL` order, parsed on
+/// first use.
+fn spot_schemas() -> &'static [Schema] {
+    static SCHEMAS: OnceLock<Vec<Schema>> = OnceLock::new();
+    SCHEMAS.get_or_init(|| {
+        SbeSchema::ALL
+            .iter()
+            .map(|schema| Schema::parse(schema.xml()).expect("Invalid embedded schema"))
+            .collect()
+    })
+}
+
+/// The embedded Spot schema the `spot_sbe` root codecs were generated from.
+pub fn spot_schema() -> &'static Schema {
+    &spot_schemas()[0]
+}
+
+/// The embedded Spot schema with `schema_id`, if `spot_sbe` has codecs for it.
+pub fn spot_schema_for(schema_id: u16) -> Option<&'static Schema> {
+    spot_schemas().iter().find(|schema| schema.id == schema_id)
+}
@@ -0,0 +1,586 @@
+use crate::{
+    decimal::Decimal,
+    decode::{into_bool, preallocated},
+    exchange_info::serialize_self_trade_prevention_mode,
+    execution::serialize_match_type,
+    message::SpotMessage,
+    order::{
+        decode_order_list_orders, serialize_contingency_type, serialize_expiry_reason,
+        serialize_floor, serialize_list_order_status, serialize_list_status_type,
+        serialize_order_capacity, serialize_order_side, serialize_order_status,
+        serialize_order_type, serialize_peg_offset_type, serialize_peg_price_type,
+        serialize_time_in_force, OrderListOrder,
+    },
+};
+use anyhow::bail;
+use serde::{Serialize, Serializer};
+use spot_sbe::{
+    allocation_report_event_codec::AllocationReportEventDecoder,
+    allocation_report_type::AllocationReportType, allocation_status::AllocationStatus,
+    balance_update_event_codec::BalanceUpdateEventDecoder, contingency_type::ContingencyType,
+    event_stream_terminated_event_codec::EventStreamTerminatedEventDecoder,
+    execution_report_event_codec::ExecutionReportEventDecoder, execution_type::ExecutionType,
+    expiry_reason::ExpiryReason, external_lock_update_event_codec::ExternalLockUpdateEventDecoder,
+    floor::Floor, list_order_status::ListOrderStatus,
+    list_status_event_codec::ListStatusEventDecoder, list_status_type::ListStatusType,
+    match_type::MatchType, order_capacity::OrderCapacity, order_side::OrderSide,
+    order_status::OrderStatus, order_type::OrderType,
+    outbound_account_position_event_codec::OutboundAccountPositionEventDecoder,
+    peg_offset_type::PegOffsetType, peg_price_type::PegPriceType,
+    self_trade_prevention_mode::SelfTradePreventionMode,
+    server_shutdown_event_codec::ServerShutdownEventDecoder, time_in_force::TimeInForce,
+};
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionReport {
+    pub subscription_id: Option<u16>,
+    pub event_time: i64,
+    pub transact_time: i64,
+    pub symbol: String,
+    pub client_order_id: String,
+    pub orig_client_order_id: String,
+    pub order_id: i64,
+    pub order_list_id: Option<i64>,
+    pub order_creation_time: Option<i64>,
+    pub working_time: Option<i64>,
+    #[serde(serialize_with = "serialize_execution_type")]
+    pub execution_type: ExecutionType,
+    #[serde(serialize_with = "serialize_order_status")]
+    pub order_status: OrderStatus,
+    pub reject_reason: String,
+    #[serde(rename = "type", serialize_with = "serialize_order_type")]
+    pub order_type: OrderType,
+    #[serde(serialize_with = "serialize_order_side")]
+    pub side: OrderSide,
+    #[serde(serialize_with = "serialize_time_in_force")]
+    pub time_in_force: TimeInForce,
+    pub orig_qty: Decimal,
+    pub price: Decimal,
+    pub orig_quote_order_qty: Decimal,
+    pub iceberg_qty: Decimal,
+    pub stop_price: Decimal,
+    pub trade_id: Option<i64>,
+    pub execution_id: i64,
+    pub executed_qty: Decimal,
+    pub cummulative_quote_qty: Decimal,
+    pub last_qty: Decimal,
+    pub last_price: Decimal,
+    pub quote_qty: Decimal,
+    pub commission: Decimal,
+    pub commission_asset: String,
+    pub is_working: bool,
+    pub is_maker: bool,
+    pub is_best_match: bool,
+    #[serde(serialize_with = "serialize_match_type")]
+    pub match_type: MatchType,
+    #[serde(serialize_with = "serialize_self_trade_prevention_mode")]
+    pub self_trade_prevention_mode: SelfTradePreventionMode,
+    #[serde(serialize_with = "serialize_order_capacity")]
+    pub order_capacity: OrderCapacity,
+    #[serde(serialize_with = "serialize_floor")]
+    pub working_floor: Floor,
+    pub used_sor: bool,
+    pub alloc_id: Option<i64>,
+    pub trailing_delta: Option<u64>,
+    pub trailing_time: Option<i64>,
+    pub trade_group_id: Option<i64>,
+    pub counter_symbol: String,
+    pub prevented_qty: Decimal,
+    pub last_prevented_qty: Option<Decimal>,
+    pub prevented_match_id: Option<i64>,
+    pub prevented_execution_qty: Option<Decimal>,
+    pub prevented_execution_price: Option<Decimal>,
+    pub prevented_execution_quote_qty: Option<Decimal>,
+    pub strategy_type: Option<i32>,
+    pub strategy_id: Option<i64>,
+    pub counter_order_id: Option<i64>,
+    #[serde(serialize_with = "serialize_peg_price_type")]
+    pub peg_price_type: PegPriceType,
+    #[serde(serialize_with = "serialize_peg_offset_type")]
+    pub peg_offset_type: PegOffsetType,
+    pub peg_offset_value: Option<u8>,
+    pub pegged_price: Option<Decimal>,
+    #[serde(serialize_with = "serialize_expiry_reason")]
+    pub expiry_reason: ExpiryReason,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceUpdate {
+    pub subscription_id: Option<u16>,
+    pub event_time: i64,
+    pub clear_time: Option<i64>,
+    pub asset: String,
+    pub free_qty_delta: Decimal,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionBalance {
+    pub asset: String,
+    pub free: Decimal,
+    pub locked: Decimal,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboundAccountPosition {
+    pub subscription_id: Option<u16>,
+    pub event_time: i64,
+    pub update_time: i64,
+    pub balances: Vec<PositionBalance>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListStatus {
+    pub subscription_id: Option<u16>,
+    pub event_time: i64,
+    pub transact_time: i64,
+    pub symbol: String,
+    pub order_list_id: i64,
+    #[serde(serialize_with = "serialize_contingency_type")]
+    pub contingency_type: ContingencyType,
+    #[serde(serialize_with = "serialize_list_status_type")]
+    pub list_status_type: ListStatusType,
+    #[serde(serialize_with = "serialize_list_order_status")]
+    pub list_order_status: ListOrderStatus,
+    pub list_client_order_id: String,
+    pub reject_reason: String,
+    pub orders: Vec<OrderListOrder>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationReport {
+    pub subscription_id: Option<u16>,
+    pub event_time: i64,
+    pub transact_time: i64,
+    pub symbol: String,
+    #[serde(serialize_with = "serialize_allocation_report_type")]
+    pub allocation_report_type: AllocationReportType,
+    #[serde(serialize_with = "serialize_allocation_status")]
+    pub allocation_status: AllocationStatus,
+    #[serde(serialize_with = "serialize_order_side")]
+    pub side: OrderSide,
+    pub allocation_report_id: i64,
+    pub allocation_id: i64,
+    pub source_symbol: String,
+    pub source_order_id: i64,
+    pub source_order_list_id: Option<i64>,
+    pub source_trade_id: Option<i64>,
+    pub source_allocation_id: Option<i64>,
+    pub price: Decimal,
+    pub qty: Decimal,
+    pub quote_qty: Decimal,
+    pub commission: Decimal,
+    pub commission_asset: String,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalLockUpdate {
+    pub subscription_id: Option<u16>,
+    pub event_time: i64,
+    pub clear_time: i64,
+    pub asset: String,
+    pub locked_qty_delta: Decimal,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventStreamTerminated {
+    pub subscription_id: Option<u16>,
+    pub event_time: i64,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerShutdown {
+    pub event_time: i64,
+}
+
+/// An event pushed on the user data stream. The variant names match the
+/// event type names of the JSON stream.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "eventType", rename_all = "camelCase")]
+pub enum UserDataEvent {
+    ExecutionReport(ExecutionReport),
+    BalanceUpdate(BalanceUpdate),
+    OutboundAccountPosition(OutboundAccountPosition),
+    ListStatus(ListStatus),
+    AllocationReport(AllocationReport),
+    ExternalLockUpdate(ExternalLockUpdate),
+    EventStreamTerminated(EventStreamTerminated),
+    ServerShutdown(ServerShutdown),
+}
+
+impl UserDataEvent {
+    /// The user data stream subscription the event was delivered for. `None`
+    /// for events that are not tied to a subscription, such as `ServerShutdown`.
+    pub fn subscription_id(&self) -> Option<u16> {
+        match self {
+            Self::ExecutionReport(event) => event.subscription_id,
+            Self::BalanceUpdate(event) => event.subscription_id,
+            Self::OutboundAccountPosition(event) => event.subscription_id,
+            Self::ListStatus(event) => event.subscription_id,
+            Self::AllocationReport(event) => event.subscription_id,
+            Self::ExternalLockUpdate(event) => event.subscription_id,
+            Self::EventStreamTerminated(event) => event.subscription_id,
+            Self::ServerShutdown(_) => None,
+        }
+    }
+
+    pub fn event_time(&self) -> i64 {
+        match self {
+            Self::ExecutionReport(event) => event.event_time,
+            Self::BalanceUpdate(event) => event.event_time,
+            Self::OutboundAccountPosition(event) => event.event_time,
+            Self::ListStatus(event) => event.event_time,
+            Self::AllocationReport(event) => event.event_time,
+            Self::ExternalLockUpdate(event) => event.event_time,
+            Self::EventStreamTerminated(event) => event.event_time,
+            Self::ServerShutdown(event) => event.event_time,
+        }
+    }
+}
+
+pub fn is_user_data_event(message: &SpotMessage<'_>) -> bool {
+    matches!(
+        message,
+        SpotMessage::ExecutionReportEvent(_)
+            | SpotMessage::BalanceUpdateEvent(_)
+            | SpotMessage::OutboundAccountPositionEvent(_)
+            | SpotMessage::ListStatusEvent(_)
+            | SpotMessage::AllocationReportEvent(_)
+            | SpotMessage::ExternalLockUpdateEvent(_)
+            | SpotMessage::EventStreamTerminatedEvent(_)
+            | SpotMessage::ServerShutdownEvent(_)
+    )
+}
+
+pub fn decode_user_data_event(message: SpotMessage<'_>) -> anyhow::Result<UserDataEvent> {
+    Ok(match message {
+        SpotMessage::ExecutionReportEvent(decoder) => {
+            UserDataEvent::ExecutionReport(decode_execution_report(decoder)?)
+        }
+        SpotMessage::BalanceUpdateEvent(decoder) => {
+            UserDataEvent::BalanceUpdate(decode_balance_update(decoder)?)
+        }
+        SpotMessage::OutboundAccountPositionEvent(decoder) => {
+            UserDataEvent::OutboundAccountPosition(decode_outbound_account_position(decoder)?)
+        }
+        SpotMessage::ListStatusEvent(decoder) => {
+            UserDataEvent::ListStatus(decode_list_status(decoder)?)
+        }
+        SpotMessage::AllocationReportEvent(decoder) => {
+            UserDataEvent::AllocationReport(decode_allocation_report(decoder)?)
+        }
+        SpotMessage::ExternalLockUpdateEvent(decoder) => {
+            UserDataEvent::ExternalLockUpdate(decode_external_lock_update(decoder)?)
+        }
+        SpotMessage::EventStreamTerminatedEvent(decoder) => {
+            UserDataEvent::EventStreamTerminated(decode_event_stream_terminated(decoder))
+        }
+        SpotMessage::ServerShutdownEvent(decoder) => {
+            UserDataEvent::ServerShutdown(decode_server_shutdown(decoder))
+        }
+        message => {
+            bail!("Unexpected template ID {}", message.template_id());
+        }
+    })
+}
+
+pub fn decode_execution_report(
+    mut decoder: ExecutionReportEventDecoder<'_>,
+) -> anyhow::Result<ExecutionReport> {
+    let price_exponent = decoder.price_exponent();
+    let qty_exponent = decoder.qty_exponent();
+    let price = |mantissa: i64| Decimal::new(mantissa, price_exponent);
+    let qty = |mantissa: i64| Decimal::new(mantissa, qty_exponent);
+    Ok(ExecutionReport {
+        subscription_id: decoder.subscription_id(),
+        event_time: decoder.event_time(),
+        transact_time: decoder.transact_time(),
+        order_creation_time: decoder.order_creation_time(),
+        working_time: decoder.working_time(),
+        order_id: decoder.order_id(),
+        order_list_id: decoder.order_list_id(),
+        orig_qty: qty(decoder.orig_qty()),
+        price: price(decoder.price()),
+        orig_quote_order_qty: price(decoder.orig_quote_order_qty()),
+        iceberg_qty: qty(decoder.iceberg_qty()),
+        stop_price: price(decoder.stop_price()),
+        order_type: decoder.order_type(),
+        side: decoder.side(),
+        time_in_force: decoder.time_in_force(),
+        execution_type: decoder.execution_type(),
+        order_status: decoder.order_status(),
+        trade_id: decoder.trade_id(),
+        execution_id: decoder.execution_id(),
+        executed_qty: qty(decoder.executed_qty()),
+        cummulative_quote_qty: price(decoder.cummulative_quote_qty()),
+        last_qty: qty(decoder.last_qty()),
+        last_price: price(decoder.last_price()),
+        quote_qty: price(decoder.quote_qty()),
+        commission: Decimal::new(decoder.commission(), decoder.commission_exponent()),
+        is_working: into_bool(decoder.is_working())?,
+        is_maker: into_bool(decoder.is_maker())?,
+        is_best_match: into_bool(decoder.is_best_match())?,
+        match_type: decoder.match_type(),
+        self_trade_prevention_mode: decoder.self_trade_prevention_mode(),
+        order_capacity: decoder.order_capacity(),
+        working_floor: decoder.working_floor(),
+        used_sor: into_bool(decoder.used_sor())?,
+        alloc_id: decoder.alloc_id(),
+        trailing_delta: decoder.trailing_delta(),
+        trailing_time: decoder.trailing_time(),
+        trade_group_id: decoder.trade_group_id(),
+        prevented_qty: qty(decoder.prevented_qty()),
+        last_prevented_qty: decoder.last_prevented_qty().map(qty),
+        prevented_match_id: decoder.prevented_match_id(),
+        prevented_execution_qty: decoder.prevented_execution_qty().map(qty),
+        prevented_execution_price: decoder.prevented_execution_price().map(price),
+        prevented_execution_quote_qty: decoder.prevented_execution_quote_qty().map(price),
+        strategy_type: decoder.strategy_type(),
+        strategy_id: decoder.strategy_id(),
+        counter_order_id: decoder.counter_order_id(),
+        peg_price_type: decoder.peg_price_type(),
+        peg_offset_type: decoder.peg_offset_type(),
+        peg_offset_value: decoder.peg_offset_value(),
+        pegged_price: decoder.pegged_price().map(price),
+        expiry_reason: decoder.expiry_reason(),
+        symbol: {
+            let coordinates = decoder.try_symbol_decoder()?;
+            let slice = decoder.symbol_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+        client_order_id: {
+            let coordinates = decoder.try_client_order_id_decoder()?;
+            let slice = decoder.client_order_id_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+        orig_client_order_id: {
+            let coordinates = decoder.try_orig_client_order_id_decoder()?;
+            let slice = decoder.orig_client_order_id_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+        commission_asset: {
+            let coordinates = decoder.try_commission_asset_decoder()?;
+            let slice = decoder.commission_asset_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+        reject_reason: {
+            let coordinates = decoder.try_reject_reason_decoder()?;
+            let slice = decoder.reject_reason_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+        counter_symbol: {
+            let coordinates = decoder.try_counter_symbol_decoder()?;
+            let slice = decoder.counter_symbol_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+    })
+}
+
+pub fn decode_balance_update(
+    mut decoder: BalanceUpdateEventDecoder<'_>,
+) -> anyhow::Result<BalanceUpdate> {
+    Ok(BalanceUpdate {
+        subscription_id: decoder.subscription_id(),
+        event_time: decoder.event_time(),
+        clear_time: decoder.clear_time(),
+        free_qty_delta: Decimal::new(decoder.free_qty_delta(), decoder.qty_exponent()),
+        asset: {
+            let coordinates = decoder.try_asset_decoder()?;
+            let slice = decoder.asset_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+    })
+}
+
+pub fn decode_outbound_account_position(
+    decoder: OutboundAccountPositionEventDecoder<'_>,
+) -> anyhow::Result<OutboundAccountPosition> {
+    let subscription_id = decoder.subscription_id();
+    let event_time = decoder.event_time();
+    let update_time = decoder.update_time();
+
+    let mut decoder = decoder.try_balances_decoder()?;
+    let count = decoder.count();
+    let mut balances = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        let exponent = decoder.exponent();
+        balances.push(PositionBalance {
+            free: Decimal::new(decoder.free(), exponent),
+            locked: Decimal::new(decoder.locked(), exponent),
+            asset: {
+                let coordinates = decoder.try_asset_decoder()?;
+                let slice = decoder.asset_slice(coordinates);
+                String::from_utf8(slice.into())?
+            },
+        });
+    }
+    Ok(OutboundAccountPosition {
+        subscription_id,
+        event_time,
+        update_time,
+        balances,
+    })
+}
+
+pub fn decode_list_status(mut decoder: ListStatusEventDecoder<'_>) -> anyhow::Result<ListStatus> {
+    let subscription_id = decoder.subscription_id();
+    let event_time = decoder.event_time();
+    let transact_time = decoder.transact_time();
+    let order_list_id = decoder.order_list_id();
+    let contingency_type = decoder.contingency_type();
+    let list_status_type = decoder.list_status_type();
+    let list_order_status = decoder.list_order_status();
+    let orders = decode_order_list_orders!(decoder);
+    Ok(ListStatus {
+        subscription_id,
+        event_time,
+        transact_time,
+        order_list_id,
+        contingency_type,
+        list_status_type,
+        list_order_status,
+        orders,
+        symbol: {
+            let coordinates = decoder.try_symbol_decoder()?;
+            let slice = decoder.symbol_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+        list_client_order_id: {
+            let coordinates = decoder.try_list_client_order_id_decoder()?;
+            let slice = decoder.list_client_order_id_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+        reject_reason: {
+            let coordinates = decoder.try_reject_reason_decoder()?;
+            let slice = decoder.reject_reason_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+    })
+}
+
+pub fn decode_allocation_report(
+    mut decoder: AllocationReportEventDecoder<'_>,
+) -> anyhow::Result<AllocationReport> {
+    let price_exponent = decoder.price_exponent();
+    let qty_exponent = decoder.qty_exponent();
+    Ok(AllocationReport {
+        subscription_id: decoder.subscription_id(),
+        event_time: decoder.event_time(),
+        transact_time: decoder.transact_time(),
+        allocation_report_type: decoder.allocation_report_type(),
+        allocation_status: decoder.allocation_status(),
+        side: decoder.side(),
+        allocation_report_id: decoder.allocation_report_id(),
+        allocation_id: decoder.allocation_id(),
+        source_order_id: decoder.source_order_id(),
+        source_order_list_id: decoder.source_order_list_id(),
+        source_trade_id: decoder.source_trade_id(),
+        source_allocation_id: decoder.source_allocation_id(),
+        price: Decimal::new(decoder.price(), price_exponent),
+        qty: Decimal::new(decoder.qty(), qty_exponent),
+        quote_qty: Decimal::new(decoder.quote_qty(), price_exponent),
+        commission: Decimal::new(decoder.commission(), decoder.commission_exponent()),
+        symbol: {
+            let coordinates = decoder.try_symbol_decoder()?;
+            let slice = decoder.symbol_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+        commission_asset: {
+            let coordinates = decoder.try_commission_asset_decoder()?;
+            let slice = decoder.commission_asset_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+        source_symbol: {
+            let coordinates = decoder.try_source_symbol_decoder()?;
+            let slice = decoder.source_symbol_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+    })
+}
+
+pub fn decode_external_lock_update(
+    mut decoder: ExternalLockUpdateEventDecoder<'_>,
+) -> anyhow::Result<ExternalLockUpdate> {
+    Ok(ExternalLockUpdate {
+        subscription_id: decoder.subscription_id(),
+        event_time: decoder.event_time(),
+        clear_time: decoder.clear_time(),
+        locked_qty_delta: Decimal::new(decoder.locked_qty_delta(), decoder.qty_exponent()),
+        asset: {
+            let coordinates = decoder.try_asset_decoder()?;
+            let slice = decoder.asset_slice(coordinates);
+            String::from_utf8(slice.into())?
+        },
+    })
+}
+
+pub fn decode_event_stream_terminated(
+    decoder: EventStreamTerminatedEventDecoder<'_>,
+) -> EventStreamTerminated {
+    EventStreamTerminated {
+        subscription_id: decoder.subscription_id(),
+        event_time: decoder.event_time(),
+    }
+}
+
+pub fn decode_server_shutdown(decoder: ServerShutdownEventDecoder<'_>) -> ServerShutdown {
+    ServerShutdown {
+        event_time: decoder.event_time(),
+    }
+}
+
+fn serialize_execution_type<S: Serializer>(
+    val: &ExecutionType,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use ExecutionType::*;
+    let str_val = match val {
+        New => "NEW",
+        Canceled => "CANCELED",
+        Replaced => "REPLACED",
+        Rejected => "REJECTED",
+        Trade => "TRADE",
+        Expired => "EXPIRED",
+        TradePrevention => "TRADE_PREVENTION",
+        Unknown => "UNKNOWN",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+fn serialize_allocation_report_type<S: Serializer>(
+    val: &AllocationReportType,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use AllocationReportType::*;
+    let str_val = match val {
+        Accept => "ACCEPT",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
+
+fn serialize_allocation_status<S: Serializer>(
+    val: &AllocationStatus,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use AllocationStatus::*;
+    let str_val = match val {
+        Accepted => "ACCEPTED",
+        NonRepresentable => "NON_REPRESENTABLE",
+        NullVal => return serializer.serialize_none(),
+    };
+    serializer.serialize_str(str_val)
+}
@@ -1,38 +1,95 @@
-use crate::exchange_info::{ErrorResponse, ExchangeInfo};
-use crate::rate_limit::RateLimit;
+use crate::{
+    decode::{into_bool, preallocated},
+    exchange_info::{decode_error, ErrorResponse},
+    message::{decode_any, upgrade, SpotMessage},
+    rate_limit::RateLimit,
+    response::{decode_response, Response},
+};
+use anyhow::bail;
 use serde::Serialize;
+use spot_sbe::web_socket_response_codec::WebSocketResponseDecoder;
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 enum WebSocketResult {
-    Unset,
     Error(ErrorResponse),
-    ExchangeInfo(ExchangeInfo),
+    Result(Box<Response>),
 }
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WebSocketMetadata {
+    id: String,
     status: u16,
+    /// Flattened into an `error` or `result` key, as in the JSON API.
+    #[serde(flatten)]
+    result: Option<WebSocketResult>,
     rate_limits: Vec<RateLimit>,
-    id: String,
-    result: WebSocketResult,
 }
 
 impl WebSocketMetadata {
     pub fn new(status: u16, rate_limits: Vec<RateLimit>, id: String) -> Self {
         Self {
+            id,
             status,
+            result: None,
             rate_limits,
-            id,
-            result: WebSocketResult::Unset,
         }
     }
 
     pub fn set_error(&mut self, error: ErrorResponse) {
-        self.result = WebSocketResult::Error(error);
+        self.result = Some(WebSocketResult::Error(error));
+    }
+
+    pub fn set_response(&mut self, response: Response) {
+        self.result = Some(WebSocketResult::Result(Box::new(response)));
     }
 
-    pub fn set_exchange_info(&mut self, exchange_info: ExchangeInfo) {
-        self.result = WebSocketResult::ExchangeInfo(exchange_info);
+    pub fn is_error(&self) -> bool {
+        matches!(self.result, Some(WebSocketResult::Error(_)))
+    }
+}
+
+pub fn decode_websocket_metadata(
+    decoder: WebSocketResponseDecoder<'_>,
+) -> anyhow::Result<(WebSocketMetadata, usize)> {
+    if into_bool(decoder.sbe_schema_id_version_deprecated())? {
+        eprintln!("Warning: sbe-sample-app is using a deprecated schema");
+    }
+    let status = decoder.status();
+    let mut decoder = decoder.try_rate_limits_decoder()?;
+    let count = decoder.count();
+    let mut rate_limits = Vec::with_capacity(preallocated(count.try_into()?));
+    for _ in 0..count {
+        decoder.try_advance()?;
+        let rate_limit = RateLimit {
+            rate_limit_type: decoder.rate_limit_type(),
+            interval: decoder.interval(),
+            interval_num: decoder.interval_num(),
+            limit: decoder.rate_limit(),
+            count: Some(decoder.current()),
+        };
+        rate_limits.push(rate_limit);
+    }
+    let mut decoder = decoder.parent()?;
+    let coordinates = decoder.try_id_decoder()?;
+    let id = decoder.id_slice(coordinates);
+    let id = String::from_utf8(id.to_vec())?;
+    let response = WebSocketMetadata::new(status, rate_limits, id);
+    let coordinates = decoder.try_result_decoder()?;
+    Ok((response, coordinates.0))
+}
+
+/// Decodes a `WebSocketResponse` envelope together with the response or error it wraps.
+pub fn decode_websocket_response(payload: &[u8]) -> anyhow::Result<WebSocketMetadata> {
+    let payload = upgrade(payload)?;
+    let SpotMessage::WebSocketResponse(decoder) = decode_any(&payload)? else {
+        bail!("Expected a WebSocketResponse envelope");
+    };
+    let (mut websocket_meta, offset) = decode_websocket_metadata(decoder)?;
+    match decode_any(&upgrade(&payload[offset..])?)? {
+        SpotMessage::ErrorResponse(decoder) => websocket_meta.set_error(decode_error(decoder)?),
+        message => websocket_meta.set_response(decode_response(message)?),
     }
+    Ok(websocket_meta)
 }
@@ -0,0 +1,66 @@
+use anyhow::bail;
+use spot_sbe::{SBE_SCHEMA_ID, SBE_SCHEMA_VERSION};
+use std::net::TcpStream;
+use tungstenite::{stream::MaybeTlsStream, Message, WebSocket};
+
+/// Adds the query parameters that select SBE responses for this crate's schema,
+/// unless the URL already chooses a `responseFormat`.
+pub fn sbe_url(url: &str) -> String {
+    if url.contains("responseFormat=") {
+        return url.to_owned();
+    }
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!(
+        "{url}{separator}responseFormat=sbe&sbeSchemaId={SBE_SCHEMA_ID}&sbeSchemaVersion={SBE_SCHEMA_VERSION}"
+    )
+}
+
+/// A blocking WebSocket API connection that sends JSON requests and receives
+/// SBE frames.
+pub struct WebSocketClient {
+    socket: WebSocket<MaybeTlsStream<TcpStream>>,
+}
+
+impl WebSocketClient {
+    /// Connects to `url` as given; see [`sbe_url`] to request SBE responses.
+    pub fn connect(url: &str) -> anyhow::Result<Self> {
+        let (socket, _) = tungstenite::connect(url)?;
+        Ok(Self { socket })
+    }
+
+    pub fn send(&mut self, request: &str) -> anyhow::Result<()> {
+        self.socket.send(Message::Text(request.to_owned()))?;
+        Ok(())
+    }
+
+    /// Returns the next binary frame. Pings are answered while waiting.
+    pub fn receive(&mut self) -> anyhow::Result<Vec<u8>> {
+        loop {
+            match self.socket.read()? {
+                Message::Binary(frame) => return Ok(frame),
+                // The server falls back to JSON when the SBE parameters are rejected.
+                Message::Text(text) => bail!("Expected an SBE frame; got text: {text}"),
+                Message::Close(frame) => bail!("Connection closed by the server: {frame:?}"),
+                Message::Ping(_) | Message::Pong(_) | Message::Frame(_) => {}
+            }
+        }
+    }
+
+    /// Sends `request` and waits for the frame that follows it.
+    pub fn request(&mut self, request: &str) -> anyhow::Result<Vec<u8>> {
+        self.send(request)?;
+        self.receive()
+    }
+
+    pub fn close(mut self) -> anyhow::Result<()> {
+        self.socket.close(None)?;
+        // Drain until the server acknowledges the close.
+        loop {
+            match self.socket.read() {
+                Ok(_) => {}
+                Err(tungstenite::Error::ConnectionClosed) => return Ok(()),
+                Err(error) => return Err(error.into()),
+            }
+        }
+    }
+}
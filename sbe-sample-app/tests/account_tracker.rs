@@ -0,0 +1,177 @@
+mod common;
+
+use common::d;
+use sbe_sample_app::{
+    account::{Account, Balance, CommissionRates},
+    account_tracker::{
+        AccountEventError, AccountTracker, AssetBalance, BalanceChangeCause, BalanceOverflow,
+        OutOfOrderEvent,
+    },
+    decimal::Decimal,
+    user_data::{
+        BalanceUpdate, ExternalLockUpdate, OutboundAccountPosition, PositionBalance, UserDataEvent,
+    },
+};
+use spot_sbe::account_type::AccountType;
+
+fn balance(free: &str, locked: &str) -> AssetBalance {
+    AssetBalance {
+        free: d(free),
+        locked: d(locked),
+    }
+}
+
+fn tracker() -> AccountTracker {
+    let rate = d("0.0010");
+    AccountTracker::from_account(&Account {
+        commission_rates: CommissionRates {
+            maker: rate,
+            taker: rate,
+            buyer: Decimal::ZERO,
+            seller: Decimal::ZERO,
+        },
+        can_trade: true,
+        can_withdraw: true,
+        can_deposit: true,
+        brokered: false,
+        require_self_trade_prevention: false,
+        prevent_sor: false,
+        update_time: 1000,
+        account_type: AccountType::Spot,
+        trade_group_id: None,
+        uid: 1,
+        balances: vec![
+            Balance {
+                asset: "BTC".to_owned(),
+                free: d("1.00000000"),
+                locked: d("0.00000000"),
+            },
+            Balance {
+                asset: "USDT".to_owned(),
+                free: d("500.00000000"),
+                locked: d("100.00000000"),
+            },
+        ],
+        permissions: vec!["SPOT".to_owned()],
+        reduce_only_assets: vec![],
+    })
+}
+
+fn balance_update(event_time: i64, asset: &str, delta: &str) -> UserDataEvent {
+    UserDataEvent::BalanceUpdate(BalanceUpdate {
+        subscription_id: Some(0),
+        event_time,
+        clear_time: Some(event_time),
+        asset: asset.to_owned(),
+        free_qty_delta: d(delta),
+    })
+}
+
+#[test]
+fn applies_account_events() {
+    let mut tracker = tracker();
+    assert_eq!(tracker.balance("BTC"), balance("1", "0"));
+    assert_eq!(tracker.balance("ETH"), AssetBalance::default());
+
+    assert_eq!(tracker.apply(&balance_update(1001, "BTC", "0.5")), Ok(true));
+    assert_eq!(tracker.balance("BTC"), balance("1.5", "0"));
+
+    let lock = UserDataEvent::ExternalLockUpdate(ExternalLockUpdate {
+        subscription_id: Some(0),
+        event_time: 1002,
+        clear_time: 1002,
+        asset: "BTC".to_owned(),
+        locked_qty_delta: d("0.25"),
+    });
+    assert_eq!(tracker.apply(&lock), Ok(true));
+    assert_eq!(tracker.balance("BTC"), balance("1.25", "0.25"));
+
+    let position = UserDataEvent::OutboundAccountPosition(OutboundAccountPosition {
+        subscription_id: Some(0),
+        event_time: 1004,
+        update_time: 1003,
+        balances: vec![PositionBalance {
+            asset: "USDT".to_owned(),
+            free: d("400.00000000"),
+            locked: d("200.00000000"),
+        }],
+    });
+    assert_eq!(tracker.apply(&position), Ok(true));
+    assert_eq!(tracker.balance("USDT"), balance("400", "200"));
+    assert_eq!(tracker.update_time(), 1003);
+
+    let causes: Vec<_> = tracker
+        .history()
+        .iter()
+        .map(|change| (change.asset.as_str(), change.cause))
+        .collect();
+    assert_eq!(
+        causes,
+        [
+            ("BTC", BalanceChangeCause::BalanceUpdate),
+            ("BTC", BalanceChangeCause::ExternalLockUpdate),
+            ("USDT", BalanceChangeCause::OutboundAccountPosition),
+        ]
+    );
+}
+
+#[test]
+fn skips_events_covered_by_snapshot() {
+    let mut tracker = tracker();
+    assert_eq!(
+        tracker.apply(&balance_update(1000, "BTC", "0.5")),
+        Ok(false)
+    );
+    assert_eq!(tracker.balance("BTC"), balance("1", "0"));
+    assert!(tracker.history().is_empty());
+}
+
+#[test]
+fn detects_out_of_order_events() {
+    let mut tracker = tracker();
+    assert_eq!(tracker.apply(&balance_update(1005, "BTC", "0.5")), Ok(true));
+    assert_eq!(
+        tracker.apply(&balance_update(1003, "BTC", "0.5")),
+        Err(AccountEventError::OutOfOrder(OutOfOrderEvent {
+            last_event_time: 1005,
+            event_time: 1003,
+        }))
+    );
+    assert_eq!(tracker.balance("BTC"), balance("1.5", "0"));
+}
+
+#[test]
+fn rejects_deltas_overflowing_the_balance() {
+    let mut tracker = tracker();
+    // Aligning 10^127 with the balance's exponent of -8 overflows.
+    let huge = Decimal::new(1, 127);
+    let overflow = |event_time: i64, asset: &str| {
+        Err(AccountEventError::Overflow(BalanceOverflow {
+            event_time,
+            asset: asset.to_owned(),
+        }))
+    };
+    let update = UserDataEvent::BalanceUpdate(BalanceUpdate {
+        subscription_id: Some(0),
+        event_time: 1001,
+        clear_time: Some(1001),
+        asset: "BTC".to_owned(),
+        free_qty_delta: huge,
+    });
+    assert_eq!(tracker.apply(&update), overflow(1001, "BTC"));
+    let lock = UserDataEvent::ExternalLockUpdate(ExternalLockUpdate {
+        subscription_id: Some(0),
+        event_time: 1002,
+        clear_time: 1002,
+        asset: "ETH".to_owned(),
+        locked_qty_delta: huge,
+    });
+    assert_eq!(tracker.apply(&lock), overflow(1002, "ETH"));
+
+    assert_eq!(tracker.balance("BTC"), balance("1", "0"));
+    assert_eq!(tracker.balances().count(), 2);
+    assert!(tracker.history().is_empty());
+    // The rejected events do not hold back the events that follow.
+    assert_eq!(tracker.apply(&balance_update(1001, "BTC", "0.5")), Ok(true));
+    assert_eq!(tracker.balance("BTC"), balance("1.5", "0"));
+}
@@ -0,0 +1,113 @@
+//! Helpers shared by the integration tests and benchmarks: encoders for the
+//! canned SBE messages replayed by the stub servers, and model builders.
+
+// Each test crate uses only some of the helpers.
+#![allow(dead_code)]
+
+use sbe_sample_app::{
+    decimal::Decimal, encode::encode_error, exchange_info::ErrorResponse,
+    user_data::ExecutionReport,
+};
+use spot_sbe::{
+    average_price_response_codec::AveragePriceResponseEncoder, execution_type::ExecutionType,
+    expiry_reason::ExpiryReason, floor::Floor, match_type::MatchType, message_header_codec,
+    order_capacity::OrderCapacity, order_side::OrderSide, order_status::OrderStatus,
+    order_type::OrderType, peg_offset_type::PegOffsetType, peg_price_type::PegPriceType,
+    self_trade_prevention_mode::SelfTradePreventionMode, time_in_force::TimeInForce, Encoder,
+    WriteBuf,
+};
+
+pub fn d(value: &str) -> Decimal {
+    value.parse().unwrap()
+}
+
+pub fn average_price() -> Vec<u8> {
+    let mut buf = vec![0u8; 256];
+    let encoder = AveragePriceResponseEncoder::default().wrap(
+        WriteBuf::new(&mut buf),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let mut header = encoder.header(0);
+    let mut encoder = header.parent().unwrap();
+    encoder.mins(5);
+    encoder.price_exponent(-2);
+    encoder.price(6500012);
+    encoder.close_time(1700000000000);
+    let limit = encoder.get_limit();
+    buf.truncate(limit);
+    buf
+}
+
+pub fn error(code: i16, msg: &str, retry_after: Option<i64>) -> Vec<u8> {
+    encode_error(&ErrorResponse {
+        code,
+        server_time: Some(1700000000000),
+        retry_after,
+        msg: msg.to_owned(),
+    })
+    .unwrap()
+}
+
+/// A fill of a quarter of a limit buy order. Tests override the fields they
+/// exercise with struct update syntax.
+pub fn execution_report() -> ExecutionReport {
+    ExecutionReport {
+        subscription_id: Some(0),
+        event_time: 1700000000001,
+        transact_time: 1700000000000,
+        symbol: "BNBBTC".to_owned(),
+        client_order_id: "6gCrw2kRUAF9CvJDGP16IP".to_owned(),
+        orig_client_order_id: String::new(),
+        order_id: 12569099453,
+        order_list_id: None,
+        order_creation_time: Some(1700000000000),
+        working_time: Some(1700000000000),
+        execution_type: ExecutionType::Trade,
+        order_status: OrderStatus::PartiallyFilled,
+        reject_reason: "NONE".to_owned(),
+        order_type: OrderType::Limit,
+        side: OrderSide::Buy,
+        time_in_force: TimeInForce::Gtc,
+        orig_qty: d("1.00000000"),
+        price: d("0.10264410"),
+        orig_quote_order_qty: d("0.00000000"),
+        iceberg_qty: d("0.00000000"),
+        stop_price: d("0.00000000"),
+        trade_id: Some(1064871),
+        execution_id: 32,
+        executed_qty: d("0.25000000"),
+        cummulative_quote_qty: d("0.02566102"),
+        last_qty: d("0.25000000"),
+        last_price: d("0.10264410"),
+        quote_qty: d("0.02566102"),
+        commission: d("0.00025000"),
+        commission_asset: "BNB".to_owned(),
+        is_working: true,
+        is_maker: false,
+        is_best_match: true,
+        match_type: MatchType::NullVal,
+        self_trade_prevention_mode: SelfTradePreventionMode::ExpireMaker,
+        order_capacity: OrderCapacity::NullVal,
+        working_floor: Floor::Exchange,
+        used_sor: false,
+        alloc_id: None,
+        trailing_delta: None,
+        trailing_time: None,
+        trade_group_id: None,
+        counter_symbol: String::new(),
+        prevented_qty: d("0.00000000"),
+        last_prevented_qty: None,
+        prevented_match_id: None,
+        prevented_execution_qty: None,
+        prevented_execution_price: None,
+        prevented_execution_quote_qty: None,
+        strategy_type: None,
+        strategy_id: None,
+        counter_order_id: None,
+        peg_price_type: PegPriceType::NullVal,
+        peg_offset_type: PegOffsetType::NullVal,
+        peg_offset_value: None,
+        pegged_price: None,
+        expiry_reason: ExpiryReason::NullVal,
+    }
+}
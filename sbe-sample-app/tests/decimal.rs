@@ -0,0 +1,167 @@
+mod common;
+
+use common::d;
+use sbe_sample_app::{
+    decimal::{Decimal, DecimalFormat},
+    output::OutputFormat,
+};
+use std::{
+    cmp::Ordering,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+fn hash(value: Decimal) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn round_trips_plain_notation() {
+    for value in ["0.00100000", "-0.5", "431.00000000", "0", "-12", "0.00"] {
+        assert_eq!(d(value).to_string(), value);
+    }
+    let value = d("0.00100000");
+    assert_eq!((value.mantissa(), value.exponent()), (100000, -8));
+    assert_eq!(Decimal::new(-5, -1).to_string(), "-0.5");
+    assert_eq!(Decimal::new(12, 2).to_string(), "1200");
+    assert_eq!(Decimal::new(0, 2).to_string(), "0");
+    assert_eq!(
+        Decimal::from_parts(i128::MIN, -2).to_string(),
+        "-1701411834604692317316873037158841057.28"
+    );
+}
+
+#[test]
+fn parses_lenient_forms_and_rejects_invalid_ones() {
+    assert_eq!(d("1.").to_string(), "1");
+    assert_eq!(d(".5").to_string(), "0.5");
+    assert_eq!(d("+2.50").to_string(), "2.50");
+    for value in [".", "", "-", "1.2.3", "1e5", "--1", " 1", "0x10"] {
+        assert!(value.parse::<Decimal>().is_err(), "{value:?} parsed");
+    }
+    assert!("1".repeat(40).parse::<Decimal>().is_err());
+    assert!(format!("0.{}", "0".repeat(128)).parse::<Decimal>().is_err());
+}
+
+#[test]
+fn rescales_truncating_towards_zero() {
+    assert_eq!(d("1.239").rescale(-2).unwrap().to_string(), "1.23");
+    assert_eq!(d("-1.239").rescale(-2).unwrap().to_string(), "-1.23");
+    assert_eq!(d("1.5").rescale(-3).unwrap().to_string(), "1.500");
+    assert_eq!(d("123").rescale(2).unwrap().to_string(), "100");
+    assert_eq!(Decimal::new(5, -100).rescale(100), Some(Decimal::ZERO));
+    assert_eq!(Decimal::from_parts(i128::MAX, 0).rescale(-1), None);
+}
+
+#[test]
+fn divides_to_the_requested_exponent() {
+    let third = d("1").checked_div_with_exponent(d("3"), -4).unwrap();
+    assert_eq!(third.to_string(), "0.3333");
+    let negative = d("-2").checked_div_with_exponent(d("3"), -2).unwrap();
+    assert_eq!(negative.to_string(), "-0.66");
+    assert_eq!(
+        d("10").checked_div_with_exponent(d("0.25"), 0),
+        Some(d("40"))
+    );
+    assert_eq!(d("1").checked_div_with_exponent(d("0"), -2), None);
+    assert_eq!(
+        Decimal::new(1, 0).checked_div_with_exponent(Decimal::new(1, 100), 0),
+        Some(Decimal::ZERO)
+    );
+    assert_eq!(
+        Decimal::from_parts(i128::MAX, 0).checked_div_with_exponent(d("1"), -1),
+        None
+    );
+    assert_eq!(d("7.5") / d("2"), d("3.7"));
+}
+
+#[test]
+fn orders_across_exponents() {
+    assert!(d("1.0") > d("0.99"));
+    assert!(d("-1.0") < d("-0.99"));
+    assert_eq!(d("1.0"), d("1.00"));
+    assert_eq!(d("0"), Decimal::new(0, -8));
+    let mut values = vec![d("0.5"), d("-3"), d("0.10"), d("0.1")];
+    values.sort();
+    assert_eq!(values, [d("-3"), d("0.1"), d("0.10"), d("0.5")]);
+}
+
+#[test]
+fn orders_values_whose_alignment_overflows() {
+    // Aligning 10^127 with an exponent of 0 overflows the mantissa.
+    let huge = Decimal::new(1, 127);
+    let max = Decimal::from_parts(i128::MAX, 0);
+    let min = Decimal::from_parts(i128::MIN, 0);
+    assert!(huge > max);
+    assert!(max < huge);
+    assert!(-huge < min);
+    assert!(min > -huge);
+    assert_ne!(huge, max);
+    // Aligning zero with an exponent of -39 overflows too.
+    let tiny = Decimal::from_parts(1, -39);
+    assert_eq!(Decimal::ZERO.cmp(&tiny), Ordering::Less);
+    assert_eq!(tiny.cmp(&Decimal::ZERO), Ordering::Greater);
+    assert_eq!(Decimal::ZERO.cmp(&-tiny), Ordering::Greater);
+    assert_eq!((-tiny).cmp(&Decimal::ZERO), Ordering::Less);
+    assert_eq!(Decimal::from_parts(0, 5), Decimal::from_parts(0, -39));
+}
+
+#[test]
+fn hashes_equal_values_alike() {
+    assert_eq!(hash(d("1.0")), hash(d("1.00")));
+    assert_eq!(hash(d("100")), hash(Decimal::new(1, 2)));
+    assert_eq!(hash(d("0")), hash(Decimal::new(0, -8)));
+    assert_eq!(
+        hash(Decimal::from_parts(0, 5)),
+        hash(Decimal::from_parts(0, -39))
+    );
+    assert_ne!(hash(d("1.0")), hash(d("0.1")));
+}
+
+#[test]
+fn checks_overflow() {
+    let min = Decimal::from_parts(i128::MIN, 0);
+    assert_eq!(min.checked_neg(), None);
+    assert_eq!(d("1.5").checked_neg(), Some(d("-1.5")));
+    assert_eq!(-d("-0.5"), d("0.5"));
+    let max = Decimal::from_parts(i128::MAX, 0);
+    assert_eq!(max.checked_add(d("1")), None);
+    assert_eq!(min.checked_sub(d("1")), None);
+    assert_eq!(max.checked_mul(d("2")), None);
+    assert_eq!(
+        Decimal::new(1, 100).checked_add(Decimal::new(1, -100)),
+        None
+    );
+    assert_eq!(d("7.5").checked_rem(d("2")), Some(d("1.5")));
+    assert_eq!(d("7.5").checked_rem(d("0")), None);
+}
+
+#[test]
+#[should_panic(expected = "attempt to negate with overflow")]
+fn panics_negating_the_minimum() {
+    let _ = -Decimal::from_parts(i128::MIN, 0);
+}
+
+#[test]
+fn serializes_in_the_requested_format() {
+    let value = [d("0.00100000"), d("-2")];
+    assert_eq!(
+        OutputFormat::Json
+            .render(&value, DecimalFormat::String)
+            .unwrap(),
+        r#"["0.00100000","-2"]"#
+    );
+    assert_eq!(
+        OutputFormat::Json
+            .render(&value, DecimalFormat::MantissaExponent)
+            .unwrap(),
+        r#"[{"mantissa":100000,"exponent":-8},{"mantissa":-2,"exponent":0}]"#
+    );
+    // The format applies to one rendering only.
+    assert_eq!(
+        serde_json::to_string(&value).unwrap(),
+        r#"["0.00100000","-2"]"#
+    );
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use common::d;
+use sbe_sample_app::{
+    encode::{encode_depth, encode_error, encode_execution_report},
+    exchange_info::{decode_error, ErrorResponse},
+    market_data::{decode_depth, Depth, PriceLevel},
+    message::{decode_any, SpotMessage},
+    user_data::{decode_execution_report, ExecutionReport},
+};
+use spot_sbe::message_length::message_length;
+
+fn level(price: &str, qty: &str) -> PriceLevel {
+    PriceLevel {
+        price: d(price),
+        qty: d(qty),
+    }
+}
+
+fn round_trip_depth(buf: &[u8]) -> Depth {
+    match decode_any(buf).unwrap() {
+        SpotMessage::DepthResponse(decoder) => decode_depth(decoder).unwrap(),
+        _ => panic!("Expected a DepthResponse"),
+    }
+}
+
+fn round_trip_execution_report(buf: &[u8]) -> ExecutionReport {
+    match decode_any(buf).unwrap() {
+        SpotMessage::ExecutionReportEvent(decoder) => decode_execution_report(decoder).unwrap(),
+        _ => panic!("Expected an ExecutionReportEvent"),
+    }
+}
+
+fn execution_report() -> ExecutionReport {
+    // Sets optional fields the common report leaves empty.
+    ExecutionReport {
+        subscription_id: Some(3),
+        trailing_delta: Some(100),
+        prevented_execution_price: Some(d("29999.5")),
+        strategy_type: Some(1000000),
+        ..common::execution_report()
+    }
+}
+
+#[test]
+fn depth_round_trips_in_an_exactly_sized_buffer() {
+    let depth = Depth {
+        last_update_id: 9,
+        bids: vec![level("30000.01", "0.5"), level("29999", "1.25")],
+        asks: vec![level("30000.1", "0.00000001")],
+    };
+    let buf = encode_depth(&depth).unwrap();
+    assert_eq!(message_length(&buf), Ok(buf.len()));
+
+    let decoded = round_trip_depth(&buf);
+    assert_eq!(decoded, depth);
+    assert_eq!(decoded.bids[1].price.exponent(), -2);
+    assert_eq!(decoded.asks[0].qty.exponent(), -8);
+    assert_eq!(encode_depth(&decoded).unwrap(), buf);
+}
+
+#[test]
+fn empty_depth_round_trips() {
+    let depth = Depth {
+        last_update_id: 1,
+        bids: Vec::new(),
+        asks: Vec::new(),
+    };
+    let buf = encode_depth(&depth).unwrap();
+    assert_eq!(message_length(&buf), Ok(buf.len()));
+    assert_eq!(round_trip_depth(&buf), depth);
+}
+
+#[test]
+fn execution_report_round_trips_byte_for_byte() {
+    let report = execution_report();
+    let buf = encode_execution_report(&report).unwrap();
+    assert_eq!(message_length(&buf), Ok(buf.len()));
+
+    let decoded = round_trip_execution_report(&buf);
+    assert_eq!(decoded, report);
+    assert_eq!(encode_execution_report(&decoded).unwrap(), buf);
+}
+
+#[test]
+fn refuses_values_the_encoding_cannot_carry() {
+    let mut report = execution_report();
+    report.symbol = "X".repeat(256);
+    let error = encode_execution_report(&report).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "symbol is 256 bytes long; at most 255 fit"
+    );
+
+    let mut report = execution_report();
+    report.trade_id = Some(i64::MIN);
+    let error = encode_execution_report(&report).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "tradeId of -9223372036854775808 collides with its null value"
+    );
+
+    let depth = Depth {
+        last_update_id: 1,
+        bids: vec![level("10000000000", "1"), level("0.000000001", "1")],
+        asks: Vec::new(),
+    };
+    let error = encode_depth(&depth).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "bid price of 10000000000 overflows an Int64 mantissa at exponent -9"
+    );
+}
+
+#[test]
+fn error_response_round_trips() {
+    let response = ErrorResponse {
+        code: -1003,
+        server_time: Some(1700000000000),
+        retry_after: None,
+        msg: "Too many requests.".to_owned(),
+    };
+    let buf = encode_error(&response).unwrap();
+    assert_eq!(message_length(&buf), Ok(buf.len()));
+    match decode_any(&buf).unwrap() {
+        SpotMessage::ErrorResponse(decoder) => {
+            assert_eq!(decode_error(decoder).unwrap(), response);
+        }
+        _ => panic!("Expected an ErrorResponse"),
+    }
+}
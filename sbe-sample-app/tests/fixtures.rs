@@ -0,0 +1,469 @@
+//! Builds the payloads of `fixtures/` with the encoders and checks them, and
+//! the YAML and JSON the app prints for each, against the committed corpus.
+//! Run with `UPDATE_FIXTURES=1` to rewrite the corpus after a deliberate change.
+
+mod common;
+
+use common::d;
+use sbe_sample_app::{
+    encode::{encode_depth, encode_error, encode_execution_report, encode_websocket_response},
+    exchange_info::ErrorResponse,
+    market_data::{Depth, PriceLevel},
+    rate_limit::RateLimit,
+    schema::{spot_schema, Body, Presence, Type},
+    user_data::ExecutionReport,
+};
+use spot_sbe::{
+    allowed_self_trade_prevention_modes::AllowedSelfTradePreventionModes,
+    bool_enum::BoolEnum,
+    exchange_info_response_codec::{
+        encoder::{
+            ExchangeFiltersEncoder, FiltersEncoder, PermissionSetsEncoder, PermissionsEncoder,
+            RateLimitsEncoder, SorsEncoder, SymbolsEncoder,
+        },
+        ExchangeInfoResponseEncoder,
+    },
+    exchange_max_num_orders_filter_codec::ExchangeMaxNumOrdersFilterEncoder,
+    expiry_reason::ExpiryReason,
+    lot_size_filter_codec::LotSizeFilterEncoder,
+    message_header_codec,
+    order_types::OrderTypes,
+    peg_offset_type::PegOffsetType,
+    peg_price_type::PegPriceType,
+    price_filter_codec::PriceFilterEncoder,
+    rate_limit_interval::RateLimitInterval,
+    rate_limit_type::RateLimitType,
+    self_trade_prevention_mode::SelfTradePreventionMode,
+    symbol_status::SymbolStatus,
+    Encoder, WriteBuf,
+};
+use std::{
+    env, fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+fn fixture_path(file: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(file)
+}
+
+/// Compares `actual` with a corpus file, or rewrites the file when updating.
+fn snapshot(file: &str, actual: &[u8]) {
+    let path = fixture_path(file);
+    if env::var_os("UPDATE_FIXTURES").is_some() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = fs::read(&path).unwrap_or_else(|error| {
+        panic!(
+            "Failed to read {}: {error}; run with UPDATE_FIXTURES=1 to create it",
+            path.display()
+        )
+    });
+    assert_eq!(
+        String::from_utf8_lossy(&expected),
+        String::from_utf8_lossy(actual),
+        "{} differs; run with UPDATE_FIXTURES=1 if the change is deliberate",
+        path.display()
+    );
+    assert_eq!(expected, actual, "{} differs", path.display());
+}
+
+/// Runs the app on `payload` and returns what it printed: STDOUT when it
+/// succeeds, as expected when `success`, and STDERR otherwise.
+fn run(payload: &[u8], output: &str, success: bool) -> Vec<u8> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_sbe-sample-app"))
+        .args(["--output", output])
+        .env_remove("RUST_BACKTRACE")
+        .env_remove("RUST_LIB_BACKTRACE")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(payload).unwrap();
+    let result = child.wait_with_output().unwrap();
+    assert_eq!(
+        result.status.success(),
+        success,
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    if success {
+        result.stdout
+    } else {
+        result.stderr
+    }
+}
+
+/// Checks the payload of the fixture `name` and the app's output for it.
+fn check(name: &str, payload: Vec<u8>, success: bool) {
+    snapshot(&format!("{name}.sbe"), &payload);
+    for (output, extension) in [("yaml", "yaml"), ("json", "json")] {
+        let printed = run(&payload, output, success);
+        if output == "json" {
+            // Errors included, the output is a document other tools can read.
+            serde_json::from_slice::<serde_json::Value>(&printed).unwrap_or_else(|error| {
+                panic!("{name}.{extension} is not a JSON document: {error}")
+            });
+        }
+        snapshot(&format!("{name}.{extension}"), &printed);
+    }
+}
+
+fn depth() -> Vec<u8> {
+    encode_depth(&Depth {
+        last_update_id: 1027024,
+        bids: vec![
+            PriceLevel {
+                price: d("4.00000000"),
+                qty: d("431.00000000"),
+            },
+            PriceLevel {
+                price: d("3.99000000"),
+                qty: d("12.50000000"),
+            },
+        ],
+        asks: vec![PriceLevel {
+            price: d("4.00000200"),
+            qty: d("12.00000000"),
+        }],
+    })
+    .unwrap()
+}
+
+/// The length of the block of `body` as version `version` sends it: up to the
+/// end of its last field of that version.
+fn block_length(body: &Body, version: u16) -> usize {
+    body.fields
+        .iter()
+        .filter(|field| field.since_version <= version)
+        .map(|field| match field.presence {
+            Presence::Constant(_) => field.offset,
+            _ => field.offset + field.ty.size(),
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Reads the little-endian unsigned integer of `size` bytes at `offset`.
+fn read_unsigned(payload: &[u8], offset: usize, size: usize) -> usize {
+    payload[offset..offset + size]
+        .iter()
+        .rev()
+        .fold(0, |value, byte| value << 8 | usize::from(*byte))
+}
+
+/// Copies the groups and var-data of `body`, at `*offset` in `payload`, to
+/// `out`, with the entries of every group cut after the fields of `version`.
+fn copy_as_version(
+    body: &Body,
+    version: u16,
+    payload: &[u8],
+    offset: &mut usize,
+    out: &mut Vec<u8>,
+) {
+    for group in &body.groups {
+        let dimension = Type::Composite(group.dimension.clone()).size();
+        let (block_offset, block_type) = group.dimension.primitive_member("blockLength").unwrap();
+        let (count_offset, count_type) = group.dimension.primitive_member("numInGroup").unwrap();
+        let sent = read_unsigned(payload, *offset + block_offset, block_type.size());
+        let count = read_unsigned(payload, *offset + count_offset, count_type.size());
+        let cut = block_length(&group.body, version);
+        let start = out.len();
+        out.extend_from_slice(&payload[*offset..*offset + dimension]);
+        out[start + block_offset..start + block_offset + 2]
+            .copy_from_slice(&u16::try_from(cut).unwrap().to_le_bytes());
+        *offset += dimension;
+        for _ in 0..count {
+            out.extend_from_slice(&payload[*offset..*offset + cut]);
+            *offset += sent;
+            copy_as_version(&group.body, version, payload, offset, out);
+        }
+    }
+    for data in &body.data {
+        let (_, length_type) = data.ty.primitive_member("length").unwrap();
+        let length = length_type.size() + read_unsigned(payload, *offset, length_type.size());
+        out.extend_from_slice(&payload[*offset..*offset + length]);
+        *offset += length;
+    }
+}
+
+/// `payload`, a message of the latest schema, as version `version` of schema
+/// `schema_id` sends it: with its block and group entries cut after the fields
+/// of that version. The messages it embeds in var-data are kept as they are.
+fn as_version(payload: &[u8], schema_id: u16, version: u16) -> Vec<u8> {
+    let header = message_header_codec::ENCODED_LENGTH;
+    let template_id = u16::from_le_bytes([payload[2], payload[3]]);
+    let message = spot_schema()
+        .messages()
+        .find(|message| message.id == template_id)
+        .unwrap();
+    let block_length = block_length(&message.body, version);
+    let mut out = payload[..header + block_length].to_vec();
+    out[0..2].copy_from_slice(&u16::try_from(block_length).unwrap().to_le_bytes());
+    out[4..6].copy_from_slice(&schema_id.to_le_bytes());
+    out[6..8].copy_from_slice(&version.to_le_bytes());
+    let mut offset = header + message.block_length;
+    copy_as_version(&message.body, version, payload, &mut offset, &mut out);
+    assert_eq!(offset, payload.len());
+    out
+}
+
+/// An execution report with the fields added by every version of schema 3.
+fn pegged_execution_report() -> Vec<u8> {
+    encode_execution_report(&ExecutionReport {
+        subscription_id: Some(1),
+        peg_price_type: PegPriceType::PrimaryPeg,
+        peg_offset_type: PegOffsetType::PriceLevel,
+        peg_offset_value: Some(2),
+        pegged_price: Some(d("0.00250000")),
+        expiry_reason: ExpiryReason::Rejected,
+        ..common::execution_report()
+    })
+    .unwrap()
+}
+
+fn too_many_requests() -> Vec<u8> {
+    encode_error(&ErrorResponse {
+        code: -1003,
+        server_time: Some(1700000000000),
+        retry_after: Some(1700000060000),
+        msg: "Too many requests; current limit of IP is 6000 requests per minute.".to_owned(),
+    })
+    .unwrap()
+}
+
+fn request_weight(count: i64) -> RateLimit {
+    RateLimit {
+        rate_limit_type: RateLimitType::RequestWeight,
+        interval: RateLimitInterval::Minute,
+        interval_num: 1,
+        limit: 6000,
+        count: Some(count),
+    }
+}
+
+fn price_filter() -> Vec<u8> {
+    let mut buf = vec![0u8; 64];
+    let encoder = PriceFilterEncoder::default().wrap(
+        WriteBuf::new(&mut buf),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let mut header = encoder.header(0);
+    let mut encoder = header.parent().unwrap();
+    encoder.price_exponent(-8);
+    encoder.min_price(1);
+    encoder.max_price(100000000000000);
+    encoder.tick_size(1);
+    let limit = encoder.get_limit();
+    buf.truncate(limit);
+    buf
+}
+
+fn lot_size_filter() -> Vec<u8> {
+    let mut buf = vec![0u8; 64];
+    let encoder = LotSizeFilterEncoder::default().wrap(
+        WriteBuf::new(&mut buf),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let mut header = encoder.header(0);
+    let mut encoder = header.parent().unwrap();
+    encoder.qty_exponent(-5);
+    encoder.min_qty(1);
+    encoder.max_qty(900000000);
+    encoder.step_size(1);
+    let limit = encoder.get_limit();
+    buf.truncate(limit);
+    buf
+}
+
+fn exchange_max_num_orders_filter() -> Vec<u8> {
+    let mut buf = vec![0u8; 64];
+    let encoder = ExchangeMaxNumOrdersFilterEncoder::default().wrap(
+        WriteBuf::new(&mut buf),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let mut header = encoder.header(0);
+    let mut encoder = header.parent().unwrap();
+    encoder.max_num_orders(1000);
+    let limit = encoder.get_limit();
+    buf.truncate(limit);
+    buf
+}
+
+fn exchange_info() -> Vec<u8> {
+    let mut buf = vec![0u8; 1024];
+    let encoder = ExchangeInfoResponseEncoder::default().wrap(
+        WriteBuf::new(&mut buf),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let mut header = encoder.header(0);
+    let encoder = header.parent().unwrap();
+
+    let mut rate_limits = encoder.rate_limits_encoder(1, RateLimitsEncoder::default());
+    rate_limits.advance().unwrap();
+    rate_limits.rate_limit_type(RateLimitType::RequestWeight);
+    rate_limits.interval(RateLimitInterval::Minute);
+    rate_limits.interval_num(1);
+    rate_limits.rate_limit(6000);
+    let encoder = rate_limits.parent().unwrap();
+
+    let mut exchange_filters =
+        encoder.exchange_filters_encoder(1, ExchangeFiltersEncoder::default());
+    exchange_filters.advance().unwrap();
+    exchange_filters.filter(&exchange_max_num_orders_filter());
+    let encoder = exchange_filters.parent().unwrap();
+
+    let mut symbols = encoder.symbols_encoder(1, SymbolsEncoder::default());
+    symbols.advance().unwrap();
+    symbols.status(SymbolStatus::Trading);
+    symbols.base_asset_precision(8);
+    symbols.quote_asset_precision(8);
+    symbols.base_commission_precision(8);
+    symbols.quote_commission_precision(8);
+    let mut order_types = OrderTypes::default();
+    order_types.set_limit(true).set_market(true);
+    symbols.order_types(order_types);
+    symbols.iceberg_allowed(BoolEnum::True);
+    symbols.oco_allowed(BoolEnum::True);
+    symbols.oto_allowed(BoolEnum::False);
+    symbols.quote_order_qty_market_allowed(BoolEnum::True);
+    symbols.allow_trailing_stop(BoolEnum::True);
+    symbols.cancel_replace_allowed(BoolEnum::True);
+    symbols.amend_allowed(BoolEnum::False);
+    symbols.is_spot_trading_allowed(BoolEnum::True);
+    symbols.is_margin_trading_allowed(BoolEnum::False);
+    symbols.default_self_trade_prevention_mode(SelfTradePreventionMode::ExpireMaker);
+    let mut modes = AllowedSelfTradePreventionModes::default();
+    modes
+        .set_expire_taker(true)
+        .set_expire_maker(true)
+        .set_expire_both(true);
+    symbols.allowed_self_trade_prevention_modes(modes);
+    symbols.peg_instructions_allowed(BoolEnum::False);
+
+    let mut filters = symbols.filters_encoder(2, FiltersEncoder::default());
+    filters.advance().unwrap();
+    filters.filter(&price_filter());
+    filters.advance().unwrap();
+    filters.filter(&lot_size_filter());
+    let symbols = filters.parent().unwrap();
+
+    let mut permission_sets = symbols.permission_sets_encoder(1, PermissionSetsEncoder::default());
+    permission_sets.advance().unwrap();
+    let mut permissions = permission_sets.permissions_encoder(1, PermissionsEncoder::default());
+    permissions.advance().unwrap();
+    permissions.permission("SPOT");
+    let mut permission_sets = permissions.parent().unwrap();
+    let mut symbols = permission_sets.parent().unwrap();
+
+    symbols.symbol("BNBBTC");
+    symbols.base_asset("BNB");
+    symbols.quote_asset("BTC");
+    let encoder = symbols.parent().unwrap();
+
+    let mut sors = encoder.sors_encoder(0, SorsEncoder::default());
+    let encoder = sors.parent().unwrap();
+    let limit = encoder.get_limit();
+    buf.truncate(limit);
+    buf
+}
+
+#[test]
+fn rest_depth() {
+    check("rest_depth", depth(), true);
+}
+
+#[test]
+fn rest_exchange_info() {
+    check("rest_exchange_info", exchange_info(), true);
+}
+
+#[test]
+fn rest_error() {
+    check("rest_error", too_many_requests(), false);
+}
+
+#[test]
+fn websocket_depth() {
+    let payload = encode_websocket_response("1", 200, &[request_weight(2)], &depth()).unwrap();
+    check("websocket_depth", payload, true);
+}
+
+#[test]
+fn websocket_exchange_info() {
+    let payload =
+        encode_websocket_response("2", 200, &[request_weight(22)], &exchange_info()).unwrap();
+    check("websocket_exchange_info", payload, true);
+}
+
+#[test]
+fn websocket_error() {
+    let payload =
+        encode_websocket_response("3", 429, &[request_weight(6001)], &too_many_requests()).unwrap();
+    check("websocket_error", payload, false);
+}
+
+#[test]
+fn user_data_execution_report() {
+    check(
+        "user_data_execution_report",
+        encode_execution_report(&common::execution_report()).unwrap(),
+        true,
+    );
+}
+
+#[test]
+fn rest_exchange_info_3_0() {
+    check(
+        "rest_exchange_info_3_0",
+        as_version(&exchange_info(), 3, 0),
+        true,
+    );
+}
+
+#[test]
+fn rest_exchange_info_2_0() {
+    // Schema 2 is refused until its published schema is vendored.
+    let payload = as_version(&exchange_info(), 2, 0);
+    snapshot("rest_exchange_info_2_0.sbe", &payload);
+    for output in ["yaml", "json"] {
+        let printed = String::from_utf8(run(&payload, output, false)).unwrap();
+        assert!(
+            printed.starts_with("Error: Unexpected schema ID. Got 2; expected one of [3]\n"),
+            "{printed}"
+        );
+    }
+}
+
+#[test]
+fn upgrades_the_stand_in_schema_2() {
+    // The stand-in has the layout of 3:0, so its messages must print as such
+    // once upgraded.
+    for payload in [depth(), pegged_execution_report(), exchange_info()] {
+        let schema_2 = as_version(&payload, 2, 0);
+        let upgraded = spot_sbe_upgrade::SbeSchema::Schema2
+            .upgrade(&schema_2)
+            .unwrap();
+        assert_eq!(
+            run(&upgraded, "yaml", true),
+            run(&as_version(&payload, 3, 0), "yaml", true)
+        );
+    }
+}
+
+#[test]
+fn user_data_execution_report_3_x() {
+    let payload = pegged_execution_report();
+    for version in 0..=4 {
+        check(
+            &format!("user_data_execution_report_3_{version}"),
+            as_version(&payload, 3, version),
+            true,
+        );
+    }
+}
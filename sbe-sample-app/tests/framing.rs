@@ -0,0 +1,121 @@
+mod common;
+
+use sbe_sample_app::{
+    framing::{frames, Frame, Framing},
+    message::{decode_any, SpotMessage},
+};
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+fn length_prefixed(messages: &[Vec<u8>]) -> Vec<u8> {
+    let mut capture = Vec::new();
+    for message in messages {
+        capture.extend_from_slice(&u32::try_from(message.len()).unwrap().to_le_bytes());
+        capture.extend_from_slice(message);
+    }
+    capture
+}
+
+#[test]
+fn splits_length_prefixed_messages() {
+    let average_price = common::average_price();
+    let error = common::error(-1003, "Too many requests.", Some(1700000000000));
+    let capture = length_prefixed(&[average_price.clone(), error.clone()]);
+
+    let frames: Vec<Frame<'_>> = frames(&capture, Framing::LengthPrefixed)
+        .collect::<anyhow::Result<_>>()
+        .unwrap();
+    assert_eq!(
+        frames,
+        [
+            Frame {
+                offset: 0,
+                payload: &average_price,
+            },
+            Frame {
+                offset: 4 + average_price.len(),
+                payload: &error,
+            },
+        ]
+    );
+    assert!(matches!(
+        decode_any(frames[1].payload).unwrap(),
+        SpotMessage::ErrorResponse(_)
+    ));
+}
+
+#[test]
+fn stops_at_a_truncated_message() {
+    let average_price = common::average_price();
+    let mut capture = length_prefixed(&[average_price.clone(), average_price.clone()]);
+    capture.truncate(capture.len() - 1);
+
+    let mut frames = frames(&capture, Framing::LengthPrefixed);
+    assert!(frames.next().unwrap().is_ok());
+    let error = frames.next().unwrap().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "Truncated message at offset {}: {} bytes expected; {} available",
+            4 + average_price.len(),
+            average_price.len(),
+            average_price.len() - 1
+        )
+    );
+    assert!(frames.next().is_none());
+}
+
+#[test]
+fn splits_back_to_back_messages() {
+    let average_price = common::average_price();
+    let error = common::error(-1121, "Invalid symbol.", None);
+    let capture = [error.clone(), average_price.clone()].concat();
+
+    let offsets: Vec<usize> = frames(&capture, Framing::Sbe)
+        .map(|frame| frame.unwrap().offset)
+        .collect();
+    assert_eq!(offsets, [0, error.len()]);
+
+    let mut frames = frames(&capture[..capture.len() - 1], Framing::Sbe);
+    assert!(frames.next().unwrap().is_ok());
+    assert!(frames.next().unwrap().is_err());
+    assert!(frames.next().is_none());
+}
+
+#[test]
+fn keep_going_stops_at_a_framing_error() {
+    let average_price = common::average_price();
+    let mut unknown = common::average_price();
+    unknown[2..4].copy_from_slice(&9999u16.to_le_bytes());
+    let mut capture = length_prefixed(&[average_price.clone(), unknown, average_price.clone()]);
+    capture.truncate(capture.len() - 1);
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_sbe-sample-app"))
+        .args(["stream", "--keep-going", "--framing", "length-prefixed"])
+        .args(["--output", "json"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(&capture).unwrap();
+    let result = child.wait_with_output().unwrap();
+    assert!(!result.status.success());
+    // The first message is printed and the undecodable second one skipped.
+    assert_eq!(String::from_utf8(result.stdout).unwrap().lines().count(), 1);
+    let stderr = String::from_utf8(result.stderr).unwrap();
+    let third = 2 * (4 + average_price.len());
+    assert!(
+        stderr.contains(&format!(
+            "Skipping message at offset {}",
+            4 + average_price.len()
+        )),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains(&format!("Truncated message at offset {third}")),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use sbe_sample_app::{
+    market_data::decode_depth,
+    message::{decode_any, SpotMessage},
+};
+use spot_sbe::{
+    depth_response_codec::{
+        encoder::{AsksEncoder, BidsEncoder},
+        DepthResponseEncoder,
+    },
+    exchange_info_response_codec::{self, ExchangeInfoResponseDecoder},
+    message_header_codec,
+    message_header_codec::MessageHeaderDecoder,
+    message_length::message_length,
+    user_data_stream_subscribe_response_codec::{self, UserDataStreamSubscribeResponseDecoder},
+    Encoder, ReadBuf, SbeErr, WriteBuf, SBE_SCHEMA_ID,
+};
+
+fn depth(bids: &[(i64, i64)], asks: &[(i64, i64)]) -> Vec<u8> {
+    let mut buf = vec![0u8; 1024];
+    let encoder = DepthResponseEncoder::default().wrap(
+        WriteBuf::new(&mut buf),
+        message_header_codec::ENCODED_LENGTH,
+    );
+    let mut header = encoder.header(0);
+    let mut encoder = header.parent().unwrap();
+    encoder.last_update_id(1);
+    encoder.price_exponent(-2);
+    encoder.qty_exponent(-8);
+    let mut bids_encoder = encoder.bids_encoder(bids.len() as u32, BidsEncoder::default());
+    for &(price, qty) in bids {
+        bids_encoder.advance().unwrap();
+        bids_encoder.price(price);
+        bids_encoder.qty(qty);
+    }
+    let encoder = bids_encoder.parent().unwrap();
+    let mut asks_encoder = encoder.asks_encoder(asks.len() as u32, AsksEncoder::default());
+    for &(price, qty) in asks {
+        asks_encoder.advance().unwrap();
+        asks_encoder.price(price);
+        asks_encoder.qty(qty);
+    }
+    let encoder = asks_encoder.parent().unwrap();
+    let limit = encoder.get_limit();
+    buf.truncate(limit);
+    buf
+}
+
+#[test]
+fn measures_fixed_blocks_groups_and_var_data() {
+    let average_price = common::average_price();
+    assert_eq!(message_length(&average_price), Ok(average_price.len()));
+
+    let error = common::error(-1121, "Invalid symbol.", None);
+    assert_eq!(message_length(&error), Ok(error.len()));
+
+    let depth = depth(&[(100, 5), (99, 7)], &[(101, 3)]);
+    // Header, block, then two groups with 6-byte dimensions and 16-byte entries.
+    assert_eq!(depth.len(), 8 + 10 + 6 + 2 * 16 + 6 + 16);
+    assert_eq!(message_length(&depth), Ok(depth.len()));
+
+    let mut capture = depth.clone();
+    capture.extend_from_slice(&error);
+    assert_eq!(message_length(&capture), Ok(depth.len()));
+}
+
+#[test]
+fn rejects_truncated_and_unknown_messages() {
+    let depth = depth(&[(100, 5)], &[]);
+    assert!(matches!(
+        message_length(&depth[..depth.len() - 1]),
+        Err(SbeErr::BufferTooShort { .. })
+    ));
+    assert!(matches!(
+        message_length(&depth[..4]),
+        Err(SbeErr::BufferTooShort { .. })
+    ));
+
+    let mut unknown = common::average_price();
+    unknown[2..4].copy_from_slice(&9999u16.to_le_bytes());
+    assert_eq!(
+        message_length(&unknown),
+        Err(SbeErr::UnknownTemplateId { template_id: 9999 })
+    );
+}
+
+/// A version 0 header followed by `body`, as a 3:0 server would send it.
+fn version_0(template_id: u16, block_length: u16, body: &[u8]) -> Vec<u8> {
+    let mut payload = Vec::new();
+    for value in [block_length, template_id, SBE_SCHEMA_ID, 0] {
+        payload.extend_from_slice(&value.to_le_bytes());
+    }
+    payload.extend_from_slice(body);
+    payload
+}
+
+#[test]
+fn checks_version_0_blocks_against_version_0_fields() {
+    // Version 0 symbols hold 18 bytes of fields, one short of the 19 of
+    // version 1: an entry cut after 17 bytes must fail before its accessors run.
+    let mut body = Vec::new();
+    for (block_length, count) in [(11u16, 0u32), (0, 0), (18, 1)] {
+        body.extend_from_slice(&block_length.to_le_bytes());
+        body.extend_from_slice(&count.to_le_bytes());
+    }
+    body.extend_from_slice(&[0; 17]);
+    let payload = version_0(exchange_info_response_codec::SBE_TEMPLATE_ID, 0, &body);
+    let header = MessageHeaderDecoder::default()
+        .try_wrap(ReadBuf::new(&payload), 0)
+        .unwrap();
+    let decoder = ExchangeInfoResponseDecoder::default()
+        .try_header(header, 0)
+        .unwrap();
+    let decoder = decoder.try_rate_limits_decoder().unwrap().parent().unwrap();
+    let decoder = decoder
+        .try_exchange_filters_decoder()
+        .unwrap()
+        .parent()
+        .unwrap();
+    let mut symbols = decoder.try_symbols_decoder().unwrap();
+    assert!(matches!(
+        symbols.try_advance(),
+        Err(SbeErr::BufferTooShort { needed: 18, .. })
+    ));
+
+    // Version 0 of the subscribe response has no fields, so an empty block is
+    // complete rather than truncated.
+    let payload = version_0(
+        user_data_stream_subscribe_response_codec::SBE_TEMPLATE_ID,
+        0,
+        &[],
+    );
+    let header = MessageHeaderDecoder::default()
+        .try_wrap(ReadBuf::new(&payload), 0)
+        .unwrap();
+    let decoder = UserDataStreamSubscribeResponseDecoder::default()
+        .try_header(header, 0)
+        .unwrap();
+    assert_eq!(decoder.subscription_id(), None);
+}
+
+#[test]
+fn rejects_group_entries_shorter_than_their_fields() {
+    // Entries of no bytes would let a huge count spin over the same bytes.
+    let mut depth = depth(&[(100, 5)], &[]);
+    depth[18..20].copy_from_slice(&0u16.to_le_bytes());
+    depth[20..24].copy_from_slice(&u32::MAX.to_le_bytes());
+    let SpotMessage::DepthResponse(decoder) = decode_any(&depth).unwrap() else {
+        panic!("Expected a DepthResponse");
+    };
+    let error = decode_depth(decoder).unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<SbeErr>(),
+        Some(&SbeErr::BlockLengthTooShort {
+            block_length: 0,
+            needed: 16
+        })
+    );
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::d;
+use sbe_sample_app::{
+    decimal::Decimal,
+    market_data::{Depth, PriceLevel},
+    order_book::{
+        BookSide, DepthUpdate, DepthUpdateError, OrderBook, SequenceExhausted, SequenceGap,
+    },
+};
+
+fn level(price: &str, qty: &str) -> PriceLevel {
+    PriceLevel {
+        price: d(price),
+        qty: d(qty),
+    }
+}
+
+fn book() -> OrderBook {
+    OrderBook::from_depth(&Depth {
+        last_update_id: 100,
+        bids: vec![level("99.00", "1.0"), level("98.50", "2.0")],
+        asks: vec![level("100.00", "0.5"), level("100.50", "1.5")],
+    })
+}
+
+fn update(first_update_id: i64, last_update_id: i64) -> DepthUpdate {
+    DepthUpdate {
+        first_update_id,
+        last_update_id,
+        bids: vec![],
+        asks: vec![],
+    }
+}
+
+#[test]
+fn queries_snapshot() {
+    let book = book();
+    assert_eq!(book.best_bid(), Some(level("99.00", "1.0")));
+    assert_eq!(book.best_ask(), Some(level("100.00", "0.5")));
+    assert_eq!(book.spread(), Some(d("1.00")));
+    assert_eq!(book.depth_at(BookSide::Bid, d("98.5")), d("2"));
+    assert_eq!(book.depth_at(BookSide::Ask, d("99")), Decimal::ZERO);
+    assert_eq!(
+        book.top(BookSide::Bid, 5),
+        [level("99.00", "1.0"), level("98.50", "2.0")]
+    );
+    // 0.5 @ 100.00 + 1.0 @ 100.50
+    assert_eq!(
+        book.notional_to_size(BookSide::Ask, d("1.5")),
+        Some(d("150.50"))
+    );
+    assert_eq!(
+        book.vwap_to_size(BookSide::Ask, d("1.5"), -4),
+        Some(d("100.3333"))
+    );
+    assert_eq!(book.notional_to_size(BookSide::Ask, d("2.1")), None);
+}
+
+#[test]
+fn applies_updates_in_sequence() {
+    let mut book = book();
+    assert_eq!(book.apply(&update(90, 100)), Ok(false));
+
+    let mut first = update(95, 105);
+    first.bids = vec![level("99.00", "0"), level("99.50", "3")];
+    first.asks = vec![level("100.0", "0.25")];
+    assert_eq!(book.apply(&first), Ok(true));
+    assert_eq!(book.last_update_id(), 105);
+    assert_eq!(book.best_bid(), Some(level("99.50", "3")));
+    assert_eq!(book.depth_at(BookSide::Bid, d("99")), Decimal::ZERO);
+    assert_eq!(book.best_ask(), Some(level("100.0", "0.25")));
+
+    assert_eq!(book.apply(&update(106, 110)), Ok(true));
+    assert_eq!(book.last_update_id(), 110);
+}
+
+#[test]
+fn detects_gaps() {
+    let mut book = book();
+    let gap = SequenceGap {
+        expected_update_id: 101,
+        first_update_id: 102,
+    };
+    assert_eq!(book.apply(&update(102, 105)), Err(gap.into()));
+    assert!(!book.is_in_sync());
+
+    let mut book = self::book();
+    assert_eq!(book.apply(&update(101, 105)), Ok(true));
+    assert!(book.apply(&update(107, 110)).is_err());
+    // Stays out of sync until seeded again.
+    assert!(book.apply(&update(106, 110)).is_err());
+}
+
+#[test]
+fn rejects_updates_after_the_largest_update_id() {
+    let mut book = book();
+    assert_eq!(book.apply(&update(101, i64::MAX)), Ok(true));
+    let exhausted = SequenceExhausted {
+        last_update_id: i64::MAX,
+    };
+    assert_eq!(
+        book.apply(&update(i64::MAX, i64::MAX)),
+        Err(DepthUpdateError::SequenceExhausted(exhausted))
+    );
+    assert_eq!(book.last_update_id(), i64::MAX);
+}
@@ -0,0 +1,210 @@
+mod common;
+
+use common::{d, execution_report};
+use sbe_sample_app::{
+    order::{OrderList, OrderListOrder},
+    order_list_tracker::OrderListTracker,
+    order_tracker::IllegalTransition,
+    user_data::{ExecutionReport, ListStatus, UserDataEvent},
+};
+use spot_sbe::{
+    contingency_type::ContingencyType, execution_type::ExecutionType,
+    list_order_status::ListOrderStatus, list_status_type::ListStatusType,
+    order_status::OrderStatus,
+};
+
+fn legs() -> Vec<OrderListOrder> {
+    [(11, "take-profit"), (12, "stop-loss")]
+        .into_iter()
+        .map(|(order_id, client_order_id)| OrderListOrder {
+            symbol: "BTCUSDT".to_owned(),
+            order_id,
+            client_order_id: client_order_id.to_owned(),
+        })
+        .collect()
+}
+
+fn list_status(
+    transact_time: i64,
+    list_status_type: ListStatusType,
+    list_order_status: ListOrderStatus,
+) -> ListStatus {
+    ListStatus {
+        subscription_id: Some(0),
+        event_time: transact_time,
+        transact_time,
+        symbol: "BTCUSDT".to_owned(),
+        order_list_id: 1,
+        contingency_type: ContingencyType::Oco,
+        list_status_type,
+        list_order_status,
+        list_client_order_id: "bracket".to_owned(),
+        reject_reason: "NONE".to_owned(),
+        orders: legs(),
+    }
+}
+
+fn leg_report(
+    order_id: i64,
+    execution_id: i64,
+    execution_type: ExecutionType,
+    order_status: OrderStatus,
+    executed_qty: &str,
+) -> ExecutionReport {
+    ExecutionReport {
+        event_time: 1000 + execution_id,
+        transact_time: 1000 + execution_id,
+        symbol: "BTCUSDT".to_owned(),
+        client_order_id: if order_id == 11 {
+            "take-profit"
+        } else {
+            "stop-loss"
+        }
+        .to_owned(),
+        order_id,
+        order_list_id: Some(1),
+        execution_id,
+        execution_type,
+        order_status,
+        executed_qty: d(executed_qty),
+        ..execution_report()
+    }
+}
+
+#[test]
+fn links_leg_reports_to_their_list() {
+    let mut tracker = OrderListTracker::default();
+    // The legs' execution reports precede the list's first `listStatus`.
+    let new_leg = UserDataEvent::ExecutionReport(leg_report(
+        11,
+        1,
+        ExecutionType::New,
+        OrderStatus::New,
+        "0.000",
+    ));
+    assert_eq!(tracker.apply(&new_leg), Ok(true));
+    assert_eq!(tracker.apply(&new_leg), Ok(false));
+    let order_list = tracker.order_list("BTCUSDT", 1).unwrap();
+    assert_eq!(order_list.contingency_type, ContingencyType::NullVal);
+    assert!(!order_list.is_done());
+
+    let started = list_status(
+        1001,
+        ListStatusType::ExecStarted,
+        ListOrderStatus::Executing,
+    );
+    assert_eq!(tracker.apply(&UserDataEvent::ListStatus(started)), Ok(true));
+    let order_list = tracker.order_list("BTCUSDT", 1).unwrap();
+    assert_eq!(order_list.contingency_type, ContingencyType::Oco);
+    assert_eq!(order_list.list_client_order_id, "bracket");
+    assert_eq!(order_list.legs.len(), 2);
+    assert_eq!(order_list.leg(11).unwrap().status, Some(OrderStatus::New));
+    assert_eq!(order_list.leg(12).unwrap().status, None);
+    assert_eq!(tracker.active_order_lists().count(), 1);
+
+    let filled = leg_report(11, 2, ExecutionType::Trade, OrderStatus::Filled, "1.000");
+    assert_eq!(tracker.apply_execution_report(&filled), Ok(true));
+    let expired = leg_report(12, 3, ExecutionType::Expired, OrderStatus::Expired, "0.000");
+    assert_eq!(tracker.apply_execution_report(&expired), Ok(true));
+    let done = list_status(1003, ListStatusType::AllDone, ListOrderStatus::AllDone);
+    assert!(tracker.apply_list_status(&done));
+    let stale = list_status(
+        1001,
+        ListStatusType::ExecStarted,
+        ListOrderStatus::Executing,
+    );
+    assert!(!tracker.apply_list_status(&stale));
+
+    let order_list = tracker.order_list_of("BTCUSDT", 12).unwrap();
+    assert!(order_list.is_done());
+    let take_profit = order_list.leg(11).unwrap();
+    assert_eq!(take_profit.status, Some(OrderStatus::Filled));
+    assert_eq!(take_profit.updates.len(), 2);
+    assert_eq!(take_profit.updates[1].executed_qty, d("1"));
+    assert_eq!(
+        order_list.leg(12).unwrap().updates[0].execution_type,
+        ExecutionType::Expired
+    );
+
+    tracker.remove_done();
+    assert_eq!(tracker.order_lists().count(), 0);
+}
+
+#[test]
+fn seeds_from_open_order_lists() {
+    let mut tracker = OrderListTracker::from_order_lists(&[OrderList {
+        order_list_id: 1,
+        contingency_type: ContingencyType::Oco,
+        list_status_type: ListStatusType::ExecStarted,
+        list_order_status: ListOrderStatus::Executing,
+        list_client_order_id: "bracket".to_owned(),
+        transaction_time: 1000,
+        symbol: "BTCUSDT".to_owned(),
+        orders: legs(),
+    }]);
+    assert_eq!(tracker.active_order_lists().count(), 1);
+    assert!(tracker.order_list_of("BTCUSDT", 12).is_some());
+    assert!(tracker.order_list_of("ETHUSDT", 12).is_none());
+
+    let mut rejected = list_status(1001, ListStatusType::AllDone, ListOrderStatus::Reject);
+    rejected.reject_reason = "INSUFFICIENT_BALANCES".to_owned();
+    assert!(tracker.apply_list_status(&rejected));
+    let order_list = tracker.order_list("BTCUSDT", 1).unwrap();
+    assert!(order_list.is_done());
+    assert_eq!(
+        order_list.reject_reason.as_deref(),
+        Some("INSUFFICIENT_BALANCES")
+    );
+
+    let mut not_in_list = leg_report(20, 4, ExecutionType::New, OrderStatus::New, "0.000");
+    not_in_list.order_list_id = None;
+    assert_eq!(tracker.apply_execution_report(&not_in_list), Ok(false));
+}
+
+#[test]
+fn ignores_leg_reports_older_than_the_last() {
+    let mut tracker = OrderListTracker::default();
+    let new = leg_report(11, 1, ExecutionType::New, OrderStatus::New, "0.000");
+    let partial = leg_report(
+        11,
+        2,
+        ExecutionType::Trade,
+        OrderStatus::PartiallyFilled,
+        "0.500",
+    );
+    let filled = leg_report(11, 3, ExecutionType::Trade, OrderStatus::Filled, "1.000");
+    assert_eq!(tracker.apply_execution_report(&new), Ok(true));
+    assert_eq!(tracker.apply_execution_report(&filled), Ok(true));
+    // A report delivered late does not move the leg back.
+    assert_eq!(tracker.apply_execution_report(&partial), Ok(false));
+    let leg = tracker.order_list("BTCUSDT", 1).unwrap().leg(11).unwrap();
+    assert_eq!(leg.status, Some(OrderStatus::Filled));
+    assert_eq!(leg.updates.len(), 2);
+}
+
+#[test]
+fn rejects_illegal_leg_transitions() {
+    let mut tracker = OrderListTracker::default();
+    let canceled = leg_report(
+        11,
+        1,
+        ExecutionType::Canceled,
+        OrderStatus::Canceled,
+        "0.000",
+    );
+    let filled = leg_report(11, 2, ExecutionType::Trade, OrderStatus::Filled, "1.000");
+    assert_eq!(tracker.apply_execution_report(&canceled), Ok(true));
+    assert_eq!(
+        tracker.apply(&UserDataEvent::ExecutionReport(filled)),
+        Err(IllegalTransition {
+            symbol: "BTCUSDT".to_owned(),
+            order_id: 11,
+            execution_type: ExecutionType::Trade,
+            from: OrderStatus::Canceled,
+            to: OrderStatus::Filled,
+        })
+    );
+    let leg = tracker.order_list("BTCUSDT", 1).unwrap().leg(11).unwrap();
+    assert_eq!(leg.status, Some(OrderStatus::Canceled));
+    assert_eq!(leg.updates.len(), 1);
+}
@@ -0,0 +1,348 @@
+mod common;
+
+use common::{d, execution_report};
+use sbe_sample_app::{
+    decimal::Decimal,
+    order::Order,
+    order_tracker::{CommissionOverflow, ExecutionReportError, IllegalTransition, OrderTracker},
+    user_data::{ExecutionReport, UserDataEvent},
+};
+use spot_sbe::{
+    execution_type::ExecutionType, expiry_reason::ExpiryReason, floor::Floor,
+    order_capacity::OrderCapacity, order_side::OrderSide, order_status::OrderStatus,
+    order_type::OrderType, peg_offset_type::PegOffsetType, peg_price_type::PegPriceType,
+    time_in_force::TimeInForce,
+};
+
+fn open_order(order_id: i64, client_order_id: &str) -> Order {
+    Order {
+        symbol: "BTCUSDT".to_owned(),
+        order_id,
+        order_list_id: None,
+        client_order_id: client_order_id.to_owned(),
+        price: d("30000.00"),
+        orig_qty: d("1.000"),
+        executed_qty: d("0.000"),
+        cummulative_quote_qty: d("0.00"),
+        status: OrderStatus::New,
+        time_in_force: TimeInForce::Gtc,
+        order_type: OrderType::Limit,
+        side: OrderSide::Buy,
+        stop_price: None,
+        trailing_delta: None,
+        trailing_time: None,
+        iceberg_qty: None,
+        time: 1000,
+        update_time: 1000,
+        is_working: true,
+        working_time: Some(1000),
+        orig_quote_order_qty: d("0.00"),
+        strategy_id: None,
+        strategy_type: None,
+        order_capacity: OrderCapacity::NullVal,
+        working_floor: Floor::Exchange,
+        prevented_match_id: None,
+        prevented_quantity: d("0.000"),
+        used_sor: false,
+        peg_price_type: PegPriceType::NullVal,
+        peg_offset_type: PegOffsetType::NullVal,
+        peg_offset_value: None,
+        pegged_price: None,
+        expiry_reason: ExpiryReason::NullVal,
+    }
+}
+
+struct Report {
+    order_id: i64,
+    execution_id: i64,
+    execution_type: ExecutionType,
+    order_status: OrderStatus,
+    orig_qty: &'static str,
+    executed_qty: &'static str,
+    cummulative_quote_qty: &'static str,
+}
+
+impl Report {
+    fn new(
+        order_id: i64,
+        execution_id: i64,
+        execution_type: ExecutionType,
+        order_status: OrderStatus,
+    ) -> Self {
+        Self {
+            order_id,
+            execution_id,
+            execution_type,
+            order_status,
+            orig_qty: "1.000",
+            executed_qty: "0.000",
+            cummulative_quote_qty: "0.00",
+        }
+    }
+
+    fn executed(mut self, executed_qty: &'static str, cummulative_quote_qty: &'static str) -> Self {
+        self.executed_qty = executed_qty;
+        self.cummulative_quote_qty = cummulative_quote_qty;
+        self
+    }
+
+    fn build(self) -> ExecutionReport {
+        ExecutionReport {
+            event_time: 1000 + self.execution_id,
+            transact_time: 1000 + self.execution_id,
+            symbol: "BTCUSDT".to_owned(),
+            client_order_id: format!("client-{}", self.order_id),
+            order_id: self.order_id,
+            execution_type: self.execution_type,
+            order_status: self.order_status,
+            orig_qty: d(self.orig_qty),
+            price: d("30000.00"),
+            trade_id: None,
+            execution_id: self.execution_id,
+            executed_qty: d(self.executed_qty),
+            cummulative_quote_qty: d(self.cummulative_quote_qty),
+            commission: d("0"),
+            commission_asset: String::new(),
+            ..execution_report()
+        }
+    }
+}
+
+fn fill(
+    order_id: i64,
+    execution_id: i64,
+    order_status: OrderStatus,
+    executed_qty: &'static str,
+    cummulative_quote_qty: &'static str,
+    commission: &str,
+    commission_asset: &str,
+) -> ExecutionReport {
+    let mut report = Report::new(order_id, execution_id, ExecutionType::Trade, order_status)
+        .executed(executed_qty, cummulative_quote_qty)
+        .build();
+    report.commission = d(commission);
+    report.commission_asset = commission_asset.to_owned();
+    report
+}
+
+#[test]
+fn follows_fills_of_a_seeded_order() {
+    let mut tracker = OrderTracker::from_orders(&[open_order(1, "client-1")]);
+    assert_eq!(tracker.open_orders().count(), 1);
+
+    let partial = fill(
+        1,
+        10,
+        OrderStatus::PartiallyFilled,
+        "0.400",
+        "12000.00",
+        "0.0004",
+        "BTC",
+    );
+    assert_eq!(tracker.apply_execution_report(&partial), Ok(true));
+    assert_eq!(tracker.apply_execution_report(&partial), Ok(false));
+    let filled = UserDataEvent::ExecutionReport(fill(
+        1,
+        11,
+        OrderStatus::Filled,
+        "1.000",
+        "30000.00",
+        "6.00",
+        "BNB",
+    ));
+    assert_eq!(tracker.apply(&filled), Ok(true));
+
+    let order = tracker.order("BTCUSDT", 1).unwrap();
+    assert_eq!(order.status, OrderStatus::Filled);
+    assert_eq!(order.executed_qty, d("1"));
+    assert_eq!(order.cummulative_quote_qty, d("30000"));
+    assert_eq!(order.commissions.get("BTC"), Some(&d("0.0004")));
+    assert_eq!(order.commissions.get("BNB"), Some(&d("6")));
+    assert!(!order.is_open());
+    assert_eq!(tracker.open_orders().count(), 0);
+
+    tracker.remove_closed();
+    assert!(tracker.order("BTCUSDT", 1).is_none());
+    assert!(tracker
+        .order_by_client_order_id("BTCUSDT", "client-1")
+        .is_none());
+}
+
+#[test]
+fn tracks_orders_placed_after_seeding() {
+    let mut tracker = OrderTracker::default();
+    let new = Report::new(2, 1, ExecutionType::New, OrderStatus::New).build();
+    assert_eq!(tracker.apply_execution_report(&new), Ok(true));
+
+    let order = tracker
+        .order_by_client_order_id("BTCUSDT", "client-2")
+        .unwrap();
+    assert_eq!(order.order_id, 2);
+    assert_eq!(order.status, OrderStatus::New);
+
+    let mut cancel = Report::new(2, 2, ExecutionType::Canceled, OrderStatus::Canceled).build();
+    cancel.client_order_id = "cancel-2".to_owned();
+    cancel.orig_client_order_id = "client-2".to_owned();
+    assert_eq!(tracker.apply_execution_report(&cancel), Ok(true));
+
+    let order = tracker
+        .order_by_client_order_id("BTCUSDT", "client-2")
+        .unwrap();
+    assert_eq!(order.status, OrderStatus::Canceled);
+    assert!(tracker
+        .order_by_client_order_id("BTCUSDT", "cancel-2")
+        .is_none());
+}
+
+#[test]
+fn records_amendments() {
+    let mut tracker = OrderTracker::from_orders(&[open_order(3, "client-3")]);
+    let mut amend = Report::new(3, 5, ExecutionType::Replaced, OrderStatus::New).build();
+    amend.client_order_id = "amended-3".to_owned();
+    amend.orig_client_order_id = "client-3".to_owned();
+    amend.orig_qty = d("0.500");
+    assert_eq!(tracker.apply_execution_report(&amend), Ok(true));
+
+    let order = tracker
+        .order_by_client_order_id("BTCUSDT", "amended-3")
+        .unwrap();
+    assert_eq!(order.orig_qty, d("0.5"));
+    assert_eq!(order.client_order_id, "amended-3");
+    assert_eq!(order.amendments.len(), 1);
+    assert_eq!(order.amendments[0].orig_client_order_id, "client-3");
+}
+
+#[test]
+fn accumulates_prevented_quantities() {
+    let mut tracker = OrderTracker::from_orders(&[open_order(4, "client-4")]);
+    let mut prevented = Report::new(
+        4,
+        6,
+        ExecutionType::TradePrevention,
+        OrderStatus::PartiallyFilled,
+    )
+    .build();
+    prevented.prevented_qty = d("0.200");
+    prevented.last_prevented_qty = Some(d("0.200"));
+    assert_eq!(tracker.apply_execution_report(&prevented), Ok(true));
+    assert_eq!(tracker.order("BTCUSDT", 4).unwrap().prevented_qty, d("0.2"));
+}
+
+#[test]
+fn rejects_illegal_transitions() {
+    let mut tracker = OrderTracker::from_orders(&[open_order(5, "client-5")]);
+    let cancel = Report::new(5, 7, ExecutionType::Canceled, OrderStatus::Canceled).build();
+    assert_eq!(tracker.apply_execution_report(&cancel), Ok(true));
+
+    let late_fill = fill(5, 8, OrderStatus::Filled, "1.000", "30000.00", "0", "");
+    let error = tracker.apply_execution_report(&late_fill).unwrap_err();
+    assert_eq!(
+        error,
+        ExecutionReportError::IllegalTransition(IllegalTransition {
+            symbol: "BTCUSDT".to_owned(),
+            order_id: 5,
+            execution_type: ExecutionType::Trade,
+            from: OrderStatus::Canceled,
+            to: OrderStatus::Filled,
+        })
+    );
+    assert_eq!(
+        tracker.order("BTCUSDT", 5).unwrap().status,
+        OrderStatus::Canceled
+    );
+
+    let mut tracker = OrderTracker::from_orders(&[open_order(6, "client-6")]);
+    let partial = fill(
+        6,
+        9,
+        OrderStatus::PartiallyFilled,
+        "0.500",
+        "15000.00",
+        "0",
+        "",
+    );
+    assert_eq!(tracker.apply_execution_report(&partial), Ok(true));
+    let shrunk = fill(
+        6,
+        10,
+        OrderStatus::PartiallyFilled,
+        "0.300",
+        "9000.00",
+        "0",
+        "",
+    );
+    assert!(tracker.apply_execution_report(&shrunk).is_err());
+    let renewed = Report::new(6, 11, ExecutionType::New, OrderStatus::New)
+        .executed("0.500", "15000.00")
+        .build();
+    assert!(tracker.apply_execution_report(&renewed).is_err());
+}
+
+#[test]
+fn rejects_commissions_overflowing_the_total() {
+    let mut tracker = OrderTracker::from_orders(&[open_order(7, "client-7")]);
+    let partial = fill(
+        7,
+        12,
+        OrderStatus::PartiallyFilled,
+        "0.400",
+        "12000.00",
+        "0.0004",
+        "BTC",
+    );
+    assert_eq!(tracker.apply_execution_report(&partial), Ok(true));
+    // Aligning 10^127 with the total's exponent of -4 overflows.
+    let mut huge = fill(7, 13, OrderStatus::Filled, "1.000", "30000.00", "0", "BTC");
+    huge.commission = Decimal::new(1, 127);
+    assert_eq!(
+        tracker.apply_execution_report(&huge),
+        Err(ExecutionReportError::CommissionOverflow(
+            CommissionOverflow {
+                symbol: "BTCUSDT".to_owned(),
+                order_id: 7,
+                commission_asset: "BTC".to_owned(),
+            }
+        ))
+    );
+    let order = tracker.order("BTCUSDT", 7).unwrap();
+    assert_eq!(order.status, OrderStatus::PartiallyFilled);
+    assert_eq!(order.commissions.get("BTC"), Some(&d("0.0004")));
+}
+
+#[test]
+fn does_not_track_orders_whose_first_report_is_rejected() {
+    let mut tracker = OrderTracker::default();
+    let mut huge = fill(
+        8,
+        14,
+        OrderStatus::PartiallyFilled,
+        "0.400",
+        "12000.00",
+        "0",
+        "BTC",
+    );
+    huge.commission = Decimal::new(1, 127);
+    assert_eq!(
+        tracker.apply_execution_report(&huge),
+        Err(ExecutionReportError::CommissionOverflow(
+            CommissionOverflow {
+                symbol: "BTCUSDT".to_owned(),
+                order_id: 8,
+                commission_asset: "BTC".to_owned(),
+            }
+        ))
+    );
+    let shrunk = fill(
+        8,
+        15,
+        OrderStatus::PartiallyFilled,
+        "-0.400",
+        "-12000.00",
+        "0",
+        "",
+    );
+    assert!(tracker.apply_execution_report(&shrunk).is_err());
+    assert!(tracker.order("BTCUSDT", 8).is_none());
+    assert_eq!(tracker.orders().count(), 0);
+    assert_eq!(tracker.open_orders().count(), 0);
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use common::d;
+use sbe_sample_app::{
+    exchange_info::SymbolFilter,
+    order_validation::{
+        round_price, round_qty, validate_filters, MarketState, OrderRequest, Rounding, Violation,
+    },
+};
+use spot_sbe::{order_side::OrderSide, order_type::OrderType};
+
+fn filters() -> Vec<SymbolFilter> {
+    vec![
+        SymbolFilter::Price {
+            min_price: d("0.01"),
+            max_price: d("1000000.00"),
+            tick_size: d("0.01"),
+        },
+        SymbolFilter::PercentPriceBySide {
+            bid_multiplier_up: d("5"),
+            bid_multiplier_down: d("0.2"),
+            ask_multiplier_up: d("5"),
+            ask_multiplier_down: d("0.8"),
+            avg_price_mins: 5,
+        },
+        SymbolFilter::LotSize {
+            min_qty: d("0.00001000"),
+            max_qty: d("9000.00000000"),
+            step_size: d("0.00001000"),
+        },
+        SymbolFilter::Notional {
+            min_notional: d("5.00000000"),
+            apply_min_to_market: true,
+            max_notional: d("9000000.00000000"),
+            apply_max_to_market: false,
+            avg_price_mins: 5,
+        },
+        SymbolFilter::IcebergParts { filter_limit: 10 },
+        SymbolFilter::TrailingDelta {
+            min_trailing_above_delta: 10,
+            max_trailing_above_delta: 2000,
+            min_trailing_below_delta: 10,
+            max_trailing_below_delta: 2000,
+        },
+        SymbolFilter::MaxNumOrders {
+            max_num_orders: 200,
+        },
+    ]
+}
+
+fn limit_order(price: &str, qty: &str) -> OrderRequest {
+    OrderRequest {
+        symbol: "BTCUSDT".to_owned(),
+        side: OrderSide::Sell,
+        order_type: OrderType::Limit,
+        price: Some(d(price)),
+        qty: d(qty),
+        trailing_delta: None,
+        iceberg_qty: None,
+    }
+}
+
+fn filter_names(violations: &[Violation]) -> Vec<&'static str> {
+    violations
+        .iter()
+        .map(|violation| violation.filter)
+        .collect()
+}
+
+#[test]
+fn accepts_valid_order() {
+    let state = MarketState {
+        avg_price: Some(d("65000.00")),
+        open_orders: Some(3),
+        ..MarketState::default()
+    };
+    let order = limit_order("65000.10", "0.00100");
+    assert_eq!(validate_filters(&filters(), &order, &state), []);
+}
+
+#[test]
+fn reports_each_violation() {
+    let state = MarketState {
+        avg_price: Some(d("65000.00")),
+        open_orders: Some(200),
+        ..MarketState::default()
+    };
+    let mut order = limit_order("50000.005", "0.0000005");
+    order.iceberg_qty = Some(d("0.00000001"));
+    order.trailing_delta = Some(5);
+    let violations = validate_filters(&filters(), &order, &state);
+    assert_eq!(
+        filter_names(&violations),
+        [
+            "PRICE_FILTER",
+            "PERCENT_PRICE_BY_SIDE",
+            "LOT_SIZE",
+            "LOT_SIZE",
+            "LOT_SIZE",
+            "LOT_SIZE",
+            "NOTIONAL",
+            "ICEBERG_PARTS",
+            "TRAILING_DELTA",
+            "MAX_NUM_ORDERS",
+        ]
+    );
+    assert_eq!(
+        violations[0].to_string(),
+        "PRICE_FILTER: price 50000.005 minus 0.01 is not a multiple of 0.01"
+    );
+    assert_eq!(
+        violations[1].rule,
+        "price 50000.005 is below 0.8 x average price 65000.00"
+    );
+    assert_eq!(violations[7].rule, "50 iceberg parts exceed the limit 10");
+}
+
+#[test]
+fn market_orders_use_average_price() {
+    let mut order = limit_order("0", "0.00005");
+    order.order_type = OrderType::Market;
+    order.price = None;
+    let state = MarketState {
+        avg_price: Some(d("65000.00")),
+        ..MarketState::default()
+    };
+    // 0.00005 x 65000.00 = 3.25 is below the 5.00 minimum.
+    let violations = validate_filters(&filters(), &order, &state);
+    assert_eq!(filter_names(&violations), ["NOTIONAL"]);
+    // Without an average price the notional is not checked.
+    assert_eq!(
+        validate_filters(&filters(), &order, &MarketState::default()),
+        []
+    );
+}
+
+#[test]
+fn rounds_to_tick_and_step() {
+    let filters = filters();
+    assert_eq!(
+        round_price(&filters, d("65000.127"), Rounding::Down),
+        Some(d("65000.12"))
+    );
+    assert_eq!(
+        round_price(&filters, d("65000.121"), Rounding::Up),
+        Some(d("65000.13"))
+    );
+    assert_eq!(
+        round_price(&filters, d("65000.12"), Rounding::Up),
+        Some(d("65000.12"))
+    );
+    assert_eq!(
+        round_qty(&filters, d("0.123456789"), OrderType::Limit),
+        Some(d("0.12345"))
+    );
+}
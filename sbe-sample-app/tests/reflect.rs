@@ -0,0 +1,138 @@
+mod common;
+
+use sbe_sample_app::{
+    reflect::{decode, Value},
+    schema::{spot_schema, spot_schema_for, Type},
+};
+use spot_sbe::{
+    message_length::message_length, SbeErr, SbeSchema, SBE_SCHEMA_ID, SBE_SCHEMA_VERSION,
+};
+
+fn int(value: i64) -> Value {
+    Value::Int(value)
+}
+
+#[test]
+fn embedded_schema_matches_generated_codecs() {
+    let schema = spot_schema();
+    assert_eq!(schema.id, SBE_SCHEMA_ID);
+    assert_eq!(schema.version, SBE_SCHEMA_VERSION);
+    assert_eq!(
+        schema.message(204).unwrap().block_length,
+        usize::from(spot_sbe::average_price_response_codec::SBE_BLOCK_LENGTH)
+    );
+    assert_eq!(schema.messages().count(), 92);
+
+    // An empty instance of every template, with zeroed fields, group counts
+    // and var-data lengths, is walked the same way as the generated layouts.
+    for message in schema.messages() {
+        let mut buffer = Vec::new();
+        for field in [message.block_length as u16, message.id, SBE_SCHEMA_ID, 0] {
+            buffer.extend_from_slice(&field.to_le_bytes());
+        }
+        buffer.resize(buffer.len() + message.block_length, 0);
+        for group in &message.body.groups {
+            let dimension = Type::Composite(group.dimension.clone());
+            buffer.resize(buffer.len() + dimension.size(), 0);
+        }
+        for data in &message.body.data {
+            let (_, length) = data.ty.primitive_member("length").unwrap();
+            buffer.resize(buffer.len() + length.size(), 0);
+        }
+        let reflected = decode(schema, &buffer).unwrap();
+        assert_eq!(reflected.name, message.name);
+        assert_eq!(reflected.length, buffer.len(), "{}", message.name);
+        assert_eq!(
+            message_length(&buffer),
+            Ok(buffer.len()),
+            "{}",
+            message.name
+        );
+    }
+}
+
+#[test]
+fn decodes_fields_nulls_and_var_data() {
+    let message = decode(spot_schema(), &common::average_price()).unwrap();
+    assert_eq!(message.name, "AveragePriceResponse");
+    assert_eq!(message.value.get("mins"), Some(&int(5)));
+    assert_eq!(message.value.get("priceExponent"), Some(&int(-2)));
+    assert_eq!(message.value.get("price"), Some(&int(6500012)));
+    assert_eq!(
+        serde_json::to_string(&message).unwrap(),
+        r#"{"AveragePriceResponse":{"mins":5,"priceExponent":-2,"price":6500012,"closeTime":1700000000000}}"#
+    );
+
+    let error = common::error(-1121, "Invalid symbol.", None);
+    let message = decode(spot_schema(), &error).unwrap();
+    assert_eq!(message.length, error.len());
+    assert_eq!(message.value.get("code"), Some(&int(-1121)));
+    assert_eq!(message.value.get("retryAfter"), Some(&Value::Null));
+    assert_eq!(
+        message.value.get("msg"),
+        Some(&Value::String("Invalid symbol.".to_owned()))
+    );
+    assert_eq!(message.value.get("data"), Some(&Value::Bytes(Vec::new())));
+}
+
+#[test]
+fn decodes_other_versions_by_their_block_length() {
+    // A newer version with a longer block: the unknown trailing field is skipped.
+    let mut newer = common::average_price();
+    let block_length = u16::from_le_bytes([newer[0], newer[1]]) + 8;
+    newer[0..2].copy_from_slice(&block_length.to_le_bytes());
+    newer[6..8].copy_from_slice(&(SBE_SCHEMA_VERSION + 1).to_le_bytes());
+    newer.extend_from_slice(&42i64.to_le_bytes());
+    let message = decode(spot_schema(), &newer).unwrap();
+    assert_eq!(message.version, SBE_SCHEMA_VERSION + 1);
+    assert_eq!(message.length, newer.len());
+    assert_eq!(message.value.get("closeTime"), Some(&int(1700000000000)));
+
+    // An older version whose block ends before the last field.
+    let mut older = common::average_price();
+    let block_length = u16::from_le_bytes([older[0], older[1]]) - 8;
+    older[0..2].copy_from_slice(&block_length.to_le_bytes());
+    older.truncate(older.len() - 8);
+    let message = decode(spot_schema(), &older).unwrap();
+    assert_eq!(message.value.get("price"), Some(&int(6500012)));
+    assert_eq!(message.value.get("closeTime"), Some(&Value::Null));
+}
+
+#[test]
+fn rejects_truncated_and_unknown_messages() {
+    let average_price = common::average_price();
+    let error = decode(spot_schema(), &average_price[..average_price.len() - 1]).unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<SbeErr>(),
+        Some(SbeErr::BufferTooShort { .. })
+    ));
+
+    let mut unknown = average_price;
+    unknown[2..4].copy_from_slice(&9999u16.to_le_bytes());
+    let error = decode(spot_schema(), &unknown).unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<SbeErr>(),
+        Some(&SbeErr::UnknownTemplateId { template_id: 9999 })
+    );
+}
+
+#[test]
+fn selects_embedded_schema_by_id() {
+    assert_eq!(
+        SbeSchema::ALL[0],
+        SbeSchema::from_id(SBE_SCHEMA_ID).unwrap()
+    );
+    assert_eq!(SbeSchema::ALL[0].version(), SBE_SCHEMA_VERSION);
+    assert_eq!(SbeSchema::from_id(SBE_SCHEMA_ID + 100), None);
+
+    for &id in SbeSchema::ALL {
+        let schema = spot_schema_for(id.id()).unwrap();
+        assert_eq!(schema.version, id.version());
+        assert_eq!(schema.semantic_version, id.semantic_version());
+    }
+    assert!(std::ptr::eq(
+        spot_schema_for(SBE_SCHEMA_ID).unwrap(),
+        spot_schema()
+    ));
+    assert!(spot_schema_for(SBE_SCHEMA_ID + 100).is_none());
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use common::{average_price, error};
+use sbe_sample_app::rest_client::{RestClient, RestError};
+use std::thread::{self, JoinHandle};
+use tiny_http::{Header, Server};
+
+/// Answers one request with a canned SBE body. Returns the base URL and a
+/// handle yielding the request URL and its `Accept` and `X-MBX-SBE` headers.
+fn stub_server(status: u16, body: Vec<u8>) -> (String, JoinHandle<(String, String, String)>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+    let handle = thread::spawn(move || {
+        let request = server.recv().unwrap();
+        let header = |name: &'static str| {
+            request
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv(name))
+                .map(|header| header.value.to_string())
+                .unwrap_or_default()
+        };
+        let seen = (
+            request.url().to_owned(),
+            header("Accept"),
+            header("X-MBX-SBE"),
+        );
+        let response = tiny_http::Response::from_data(body)
+            .with_status_code(status)
+            .with_header(Header::from_bytes("Content-Type", "application/sbe").unwrap());
+        request.respond(response).unwrap();
+        seen
+    });
+    (base_url, handle)
+}
+
+#[test]
+fn decodes_sbe_response() {
+    let (base_url, server) = stub_server(200, average_price());
+    let response = RestClient::new()
+        .unwrap()
+        .get(&format!("{base_url}/api/v3/avgPrice?symbol=BTCUSDT"))
+        .unwrap();
+    assert_eq!(
+        serde_json::to_string(&response).unwrap(),
+        r#"{"mins":5,"price":"65000.12","closeTime":1700000000000}"#
+    );
+
+    let (url, accept, sbe) = server.join().unwrap();
+    assert_eq!(url, "/api/v3/avgPrice?symbol=BTCUSDT");
+    assert_eq!(accept, "application/sbe");
+    assert_eq!(sbe, "3:4");
+}
+
+#[test]
+fn maps_error_response() {
+    let body = error(-1003, "Too many requests.", Some(1700000060000));
+    let (base_url, server) = stub_server(429, body);
+    let result = RestClient::new()
+        .unwrap()
+        .get(&format!("{base_url}/api/v3/avgPrice?symbol=BTCUSDT"));
+    server.join().unwrap();
+
+    let Err(error) = result else {
+        panic!("expected an error");
+    };
+    assert_eq!(error.retry_after(), Some(1700000060000));
+    let RestError::Api { status, error } = error else {
+        panic!("expected an API error; got {error}");
+    };
+    assert_eq!(status, 429);
+    assert_eq!(error.code, -1003);
+    assert_eq!(error.server_time, Some(1700000000000));
+    assert_eq!(error.msg, "Too many requests.");
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use common::{average_price, error};
+use sbe_sample_app::{
+    encode::encode_websocket_response,
+    rate_limit::RateLimit,
+    websocket::decode_websocket_response,
+    websocket_client::{sbe_url, WebSocketClient},
+};
+use spot_sbe::{rate_limit_interval::RateLimitInterval, rate_limit_type::RateLimitType};
+use std::{
+    net::TcpListener,
+    sync::mpsc,
+    thread::{self, JoinHandle},
+};
+use tungstenite::{
+    handshake::server::{Request, Response},
+    Message,
+};
+
+fn envelope(id: &str, status: u16, result: &[u8]) -> Vec<u8> {
+    let rate_limit = RateLimit {
+        rate_limit_type: RateLimitType::RequestWeight,
+        interval: RateLimitInterval::Minute,
+        interval_num: 1,
+        limit: 6000,
+        count: Some(2),
+    };
+    encode_websocket_response(id, status, &[rate_limit], result).unwrap()
+}
+
+/// Serves one connection, answering each text request with the next canned
+/// frame. Returns the URL to connect to and a handle yielding the request URI
+/// and the requests received.
+fn mock_server(frames: Vec<Vec<u8>>) -> (String, JoinHandle<(String, Vec<String>)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("ws://{}/ws-api/v3", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let (uri_sender, uri_receiver) = mpsc::channel();
+        let callback = |request: &Request, response: Response| {
+            uri_sender.send(request.uri().to_string()).unwrap();
+            Ok(response)
+        };
+        let mut socket = tungstenite::accept_hdr(stream, callback).unwrap();
+        let mut frames = frames.into_iter();
+        let mut requests = Vec::new();
+        loop {
+            match socket.read() {
+                Ok(Message::Text(request)) => {
+                    requests.push(request);
+                    let frame = frames.next().expect("no canned frame left");
+                    socket.send(Message::Binary(frame)).unwrap();
+                }
+                Ok(_) => {}
+                Err(tungstenite::Error::ConnectionClosed) => break,
+                Err(error) => panic!("{error}"),
+            }
+        }
+        (uri_receiver.recv().unwrap(), requests)
+    });
+    (url, handle)
+}
+
+#[test]
+fn decodes_replayed_response() {
+    let (url, server) = mock_server(vec![envelope("1", 200, &average_price())]);
+    let mut client = WebSocketClient::connect(&sbe_url(&url)).unwrap();
+    let request = r#"{"id":"1","method":"avgPrice","params":{"symbol":"BTCUSDT"}}"#;
+    let frame = client.request(request).unwrap();
+    client.close().unwrap();
+
+    let response = decode_websocket_response(&frame).unwrap();
+    assert!(!response.is_error());
+    assert_eq!(
+        serde_json::to_string(&response).unwrap(),
+        concat!(
+            r#"{"id":"1","status":200,"#,
+            r#""result":{"mins":5,"price":"65000.12","closeTime":1700000000000},"#,
+            r#""rateLimits":[{"rateLimitType":"REQUEST_WEIGHT","interval":"MINUTE","intervalNum":1,"limit":6000,"count":2}]}"#,
+        )
+    );
+
+    let (uri, requests) = server.join().unwrap();
+    assert_eq!(
+        uri,
+        "/ws-api/v3?responseFormat=sbe&sbeSchemaId=3&sbeSchemaVersion=4"
+    );
+    assert_eq!(requests, [request]);
+}
+
+#[test]
+fn decodes_replayed_error() {
+    let (url, server) = mock_server(vec![envelope(
+        "2",
+        400,
+        &error(-1121, "Invalid symbol.", None),
+    )]);
+    let mut client = WebSocketClient::connect(&url).unwrap();
+    let frame = client
+        .request(r#"{"id":"2","method":"avgPrice","params":{"symbol":"?"}}"#)
+        .unwrap();
+    client.close().unwrap();
+    server.join().unwrap();
+
+    let response = decode_websocket_response(&frame).unwrap();
+    assert!(response.is_error());
+    let rendered = serde_json::to_string(&response).unwrap();
+    assert!(rendered.contains(r#""status":400,"error":{"code":-1121,"#));
+    assert!(rendered.contains(r#""msg":"Invalid symbol.""#));
+}
@@ -0,0 +1,9 @@
+/// Schemas of other IDs, kept to decode their messages while Binance migrates
+/// from one schema ID to the next.
+const OTHER_SCHEMAS: &str = "schemas";
+
+fn main() {
+    let schemas =
+        sbe_codegen::schemas("spot_latest.xml", OTHER_SCHEMAS).expect("Failed to list the schemas");
+    sbe_codegen::build(&schemas, "spot_sbe.rs").expect("Failed to generate the Spot codecs");
+}
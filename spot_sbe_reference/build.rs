@@ -0,0 +1,300 @@
+//! Generates, for every message of the schema, an encoder filling each field
+//! with deterministic values and decoders reading every accessor back, written
+//! against an `sbe` crate alias so that `tests/conformance.rs` can run them
+//! against both these codecs and the generated `spot_sbe` ones, and a rewrite
+//! of each encoded message as the schema's first version would have sent it.
+
+use sbe_codegen::{
+    naming::{codec_module, snake_case, type_name},
+    schema::{Body, Field, Presence, Primitive, Schema, Type},
+};
+use std::{env, fmt::Write, fs, path::Path};
+
+const SCHEMA: &str = "../spot_sbe/spot_latest.xml";
+
+fn main() {
+    println!("cargo:rerun-if-changed={SCHEMA}");
+    let schema = Schema::parse(&fs::read_to_string(SCHEMA).expect("Failed to read the schema"))
+        .expect("Invalid schema");
+    let mut codecs = String::new();
+    let mut first_versions = String::new();
+    let mut messages = String::from("conformance! {\n");
+    for message in schema.messages() {
+        let name = snake_case(&message.name);
+        let module = codec_module(&message.name);
+        let ty = type_name(&message.name);
+        let mut seed = 0;
+        let mut encode = String::new();
+        encode_body(&mut encode, &message.body, "encoder", &mut seed);
+        let mut decode = String::new();
+        decode_body(&mut decode, &message.body, "decoder", false);
+        let mut try_decode = String::new();
+        decode_body(&mut try_decode, &message.body, "decoder", true);
+        writeln!(
+            codecs,
+            r#"
+pub mod {name} {{
+    use super::*;
+
+    pub fn encode(buf: &mut [u8]) -> usize {{
+        let encoder = sbe::{module}::{ty}Encoder::default()
+            .wrap(sbe::WriteBuf::new(buf), sbe::message_header_codec::ENCODED_LENGTH);
+        let mut header = encoder.header(0);
+        let mut encoder = header.parent().unwrap();
+        let entry = 0_usize;
+{encode}        encoder.get_limit()
+    }}
+
+    pub fn decode(buf: &[u8]) -> Vec<String> {{
+        let mut out = Vec::new();
+        let header = sbe::message_header_codec::MessageHeaderDecoder::default()
+            .wrap(sbe::ReadBuf::new(buf), 0);
+        let mut decoder = sbe::{module}::{ty}Decoder::default().header(header, 0);
+{decode}        out.push(format!("{{}}", decoder.get_limit()));
+        out
+    }}
+
+    pub fn try_decode(buf: &[u8]) -> Vec<String> {{
+        let mut out = Vec::new();
+        if let Err(error) = try_decode_into(buf, &mut out) {{
+            out.push(format!("{{error:?}}"));
+        }}
+        out
+    }}
+
+    fn try_decode_into(buf: &[u8], out: &mut Vec<String>) -> sbe::SbeResult<()> {{
+        let header = sbe::message_header_codec::MessageHeaderDecoder::default()
+            .try_wrap(sbe::ReadBuf::new(buf), 0)?;
+        let mut decoder = sbe::{module}::{ty}Decoder::default().try_header(header, 0)?;
+{try_decode}        out.push(format!("{{}}", decoder.get_limit()));
+        Ok(())
+    }}
+}}"#
+        )
+        .unwrap();
+        let mut first_version = String::new();
+        first_version_body(&mut first_version, &message.body);
+        writeln!(
+            first_versions,
+            "
+pub(super) fn {name}(buf: &[u8], shrink: usize) -> FirstVersion<'_> {{
+    let mut message = FirstVersion::new(buf, {length}, shrink);
+{first_version}    message
+}}",
+            length = first_version_length(&message.body.fields)
+        )
+        .unwrap();
+        writeln!(messages, "    {name},").unwrap();
+    }
+    messages.push_str("}\n");
+
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("codecs.rs"), codecs).unwrap();
+    fs::write(
+        Path::new(&out_dir).join("first_versions.rs"),
+        first_versions,
+    )
+    .unwrap();
+    fs::write(Path::new(&out_dir).join("messages.rs"), messages).unwrap();
+}
+
+fn rust_type(primitive: Primitive) -> &'static str {
+    match primitive {
+        Primitive::Char | Primitive::UInt8 => "u8",
+        Primitive::Int8 => "i8",
+        Primitive::Int16 => "i16",
+        Primitive::Int32 => "i32",
+        Primitive::Int64 => "i64",
+        Primitive::UInt16 => "u16",
+        Primitive::UInt32 => "u32",
+        Primitive::UInt64 => "u64",
+        Primitive::Float => "f32",
+        Primitive::Double => "f64",
+    }
+}
+
+/// Encodes every field, then two entries of every group, then every var-data,
+/// from values derived from a per-field seed and the index of the group entry.
+/// Optional fields alternate between present and null.
+fn encode_body(out: &mut String, body: &Body, encoder: &str, seed: &mut usize) {
+    for field in &body.fields {
+        *seed += 1;
+        let method = snake_case(&field.name);
+        let value = match (&field.ty, &field.presence) {
+            (_, Presence::Constant(_)) => continue,
+            (Type::Encoded(encoded), _) if encoded.length > 1 => {
+                format!("&[({seed} + entry * 7) as u8; {}]", encoded.length)
+            }
+            (Type::Encoded(encoded), presence) => {
+                let ty = rust_type(encoded.primitive);
+                let value = format!("({seed} + entry * 7) as {ty}");
+                if *presence != Presence::Optional {
+                    value
+                } else {
+                    let null = match (&encoded.null_value, encoded.primitive) {
+                        (Some(null), _) => format!("{null} as {ty}"),
+                        (None, Primitive::Float | Primitive::Double) => format!("{ty}::NAN"),
+                        (None, primitive) if primitive.is_signed() => format!("{ty}::MIN"),
+                        (None, _) => format!("{ty}::MAX"),
+                    };
+                    format!("if ({seed} + entry) % 2 == 1 {{ {null} }} else {{ {value} }}")
+                }
+            }
+            (Type::Enum(enumeration), _) => {
+                let path = format!(
+                    "sbe::{}::{}",
+                    snake_case(&enumeration.name),
+                    type_name(&enumeration.name)
+                );
+                let variants: Vec<String> = enumeration
+                    .values
+                    .iter()
+                    .map(|(name, _)| format!("{path}::{}", type_name(name)))
+                    .collect();
+                format!(
+                    "[{}][({seed} + entry) % {}]",
+                    variants.join(", "),
+                    variants.len()
+                )
+            }
+            (Type::Set(set), _) => {
+                let mask: u64 = set.choices.iter().map(|(_, bit)| 1 << bit).sum();
+                format!(
+                    "sbe::{}::{}::new(({seed} + entry * 7) as {} & {mask})",
+                    snake_case(&set.name),
+                    type_name(&set.name),
+                    rust_type(set.encoding)
+                )
+            }
+            (Type::Composite(composite), _) => {
+                panic!("Unsupported composite field {}", composite.name)
+            }
+        };
+        writeln!(out, "        {encoder}.{method}({value});").unwrap();
+    }
+    for group in &body.groups {
+        let method = snake_case(&group.name);
+        let mut entries = String::new();
+        encode_body(&mut entries, &group.body, &method, seed);
+        writeln!(
+            out,
+            "        let mut {method} = {encoder}.{method}_encoder(2, Default::default());
+        for entry in 0..2_usize {{
+            {method}.advance().unwrap();
+{entries}        }}
+        {encoder} = {method}.parent().unwrap();"
+        )
+        .unwrap();
+    }
+    for data in &body.data {
+        *seed += 1;
+        let method = snake_case(&data.name);
+        let text = format!("&ALPHABET[..({seed} + entry * 5) % (ALPHABET.len() + 1)]");
+        let value = match data.character_encoding() {
+            Some(_) => text,
+            None => format!("{text}.as_bytes()"),
+        };
+        writeln!(out, "        {encoder}.{method}({value});").unwrap();
+    }
+}
+
+/// Reads back every field, group count and var-data, through the checked
+/// `try_*` API when `checked`.
+fn decode_body(out: &mut String, body: &Body, decoder: &str, checked: bool) {
+    let (try_, question) = if checked { ("try_", "?") } else { ("", "") };
+    for field in &body.fields {
+        writeln!(
+            out,
+            "        out.push(format!(\"{{:?}}\", {decoder}.{}()));",
+            snake_case(&field.name)
+        )
+        .unwrap();
+    }
+    for group in &body.groups {
+        let method = snake_case(&group.name);
+        let mut entries = String::new();
+        decode_body(&mut entries, &group.body, &method, checked);
+        let advance = if checked {
+            format!("{method}.try_advance()?;")
+        } else {
+            format!("{method}.advance().unwrap();")
+        };
+        writeln!(
+            out,
+            "        let mut {method} = {decoder}.{try_}{method}_decoder(){question};
+        out.push(format!(\"{{}}\", {method}.count()));
+        for _ in 0..{method}.count() {{
+            {advance}
+{entries}        }}
+        {decoder} = {method}.parent().unwrap();"
+        )
+        .unwrap();
+    }
+    for data in &body.data {
+        let method = snake_case(&data.name);
+        writeln!(
+            out,
+            "        let coordinates = {decoder}.{try_}{method}_decoder(){question};
+        out.push(format!(\"{{:?}}\", {decoder}.{method}_slice(coordinates)));"
+        )
+        .unwrap();
+    }
+}
+
+/// The length of a block holding the fields of version 0, as the checked
+/// decoders require at that version.
+fn first_version_length(fields: &[Field]) -> usize {
+    fields
+        .iter()
+        .filter(|field| field.since_version == 0)
+        .map(|field| match field.presence {
+            Presence::Constant(_) => field.offset,
+            _ => field.offset + field.ty.size(),
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Copies every block of `body` cut after its version 0 fields, and every group
+/// dimension with its block length shrunk to match.
+fn first_version_body(out: &mut String, body: &Body) {
+    for group in &body.groups {
+        assert_eq!(group.since_version, 0, "Unsupported versioned group");
+        let method = snake_case(&group.name);
+        let (block_length_offset, block_length) = group
+            .dimension
+            .primitive_member("blockLength")
+            .expect("Invalid group dimension");
+        let (count_offset, count) = group
+            .dimension
+            .primitive_member("numInGroup")
+            .expect("Invalid group dimension");
+        let length = first_version_length(&group.body.fields);
+        let mut entries = String::new();
+        first_version_body(&mut entries, &group.body);
+        writeln!(
+            out,
+            "    let ({method}_block_length, {method}_length, {method}_count) = message.dimension(
+        {size},
+        ({block_length_offset}, {block_length_size}),
+        ({count_offset}, {count_size}),
+        {length},
+    );
+    for _ in 0..{method}_count {{
+        message.block({method}_block_length, {method}_length);
+{entries}    }}",
+            size = Type::Composite(group.dimension.clone()).size(),
+            block_length_size = block_length.size(),
+            count_size = count.size(),
+        )
+        .unwrap();
+    }
+    for data in &body.data {
+        assert_eq!(data.since_version, 0, "Unsupported versioned var-data");
+        let (_, length) = data
+            .ty
+            .primitive_member("length")
+            .expect("Invalid var-data");
+        writeln!(out, "    message.data({});", length.size()).unwrap();
+    }
+}
@@ -684,6 +684,32 @@ pub mod decoder {
             )
         }
 
+        /// Checked variant of `header` that fails with `SbeErr::BufferTooShort`
+        /// instead of panicking when the fixed block does not fit in the buffer.
+        pub fn try_header(
+            self,
+            mut header: MessageHeaderDecoder<ReadBuf<'a>>,
+            offset: usize,
+        ) -> SbeResult<Self> {
+            header
+                .get_buf()
+                .check_bounds(offset, message_header_codec::ENCODED_LENGTH)?;
+            let acting_block_length = header.block_length();
+            let acting_version = header.version();
+            let buf = header.parent()?;
+            let offset = offset + message_header_codec::ENCODED_LENGTH;
+            let needed = Self::min_block_length(acting_version).max(acting_block_length as usize);
+            buf.check_bounds(offset, needed)?;
+
+            Ok(self.wrap(buf, offset, acting_block_length, acting_version))
+        }
+
+        /// Number of bytes the fixed block must hold for the fields present in `acting_version`.
+        #[inline]
+        fn min_block_length(_acting_version: u16) -> usize {
+            64
+        }
+
         /// primitive field - 'REQUIRED'
         #[inline]
         pub fn commission_exponent(&self) -> i8 {
@@ -785,17 +811,35 @@ pub mod decoder {
             BalancesDecoder::default().wrap(self)
         }
 
+        /// GROUP DECODER (id=100)
+        #[inline]
+        pub fn try_balances_decoder(self) -> SbeResult<BalancesDecoder<Self>> {
+            BalancesDecoder::default().try_wrap(self)
+        }
+
         /// GROUP DECODER (id=101)
         #[inline]
         pub fn permissions_decoder(self) -> PermissionsDecoder<Self> {
             PermissionsDecoder::default().wrap(self)
         }
 
+        /// GROUP DECODER (id=101)
+        #[inline]
+        pub fn try_permissions_decoder(self) -> SbeResult<PermissionsDecoder<Self>> {
+            PermissionsDecoder::default().try_wrap(self)
+        }
+
         /// GROUP DECODER (id=102)
         #[inline]
         pub fn reduce_only_assets_decoder(self) -> ReduceOnlyAssetsDecoder<Self> {
             ReduceOnlyAssetsDecoder::default().wrap(self)
         }
+
+        /// GROUP DECODER (id=102)
+        #[inline]
+        pub fn try_reduce_only_assets_decoder(self) -> SbeResult<ReduceOnlyAssetsDecoder<Self>> {
+            ReduceOnlyAssetsDecoder::default().try_wrap(self)
+        }
     }
 
     #[derive(Debug, Default)]
@@ -862,6 +906,19 @@ pub mod decoder {
             self
         }
 
+        /// Checked variant of `wrap` that fails with `SbeErr::BufferTooShort`
+        /// instead of panicking when the group dimensions do not fit in the buffer.
+        pub fn try_wrap(self, parent: P) -> SbeResult<Self> {
+            parent.get_buf().check_bounds(parent.get_limit(), 6)?;
+            Ok(self.wrap(parent))
+        }
+
+        /// Number of bytes the fixed block must hold for the fields present in `acting_version`.
+        #[inline]
+        fn min_block_length(_acting_version: u16) -> usize {
+            17
+        }
+
         /// group token - Token{signal=BEGIN_GROUP, name='balances', referencedName='null', description='null', packageName='null', id=100, version=0, deprecated=0, encodedLength=17, offset=64, componentTokenCount=21, encoding=Encoding{presence=REQUIRED, primitiveType=null, byteOrder=LITTLE_ENDIAN, minValue=null, maxValue=null, nullValue=null, constValue=null, characterEncoding='null', epoch='null', timeUnit=null, semanticType='null'}}
         #[inline]
         pub fn parent(&mut self) -> SbeResult<P> {
@@ -894,6 +951,19 @@ pub mod decoder {
             }
         }
 
+        /// Checked variant of `advance` that fails with `SbeErr::BufferTooShort`
+        /// instead of letting field accessors read past the end of the buffer.
+        pub fn try_advance(&mut self) -> SbeResult<Option<usize>> {
+            if self.index.wrapping_add(1) >= self.count as usize {
+                return Ok(None);
+            }
+            let parent = self.parent.as_ref().ok_or(SbeErr::ParentNotSet)?;
+            let needed =
+                Self::min_block_length(parent.acting_version()).max(self.block_length as usize);
+            parent.get_buf().check_bounds(parent.get_limit(), needed)?;
+            self.advance()
+        }
+
         /// primitive field - 'REQUIRED'
         #[inline]
         pub fn exponent(&self) -> i8 {
@@ -924,6 +994,23 @@ pub mod decoder {
             (offset + 1, data_length)
         }
 
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'
+        #[inline]
+        pub fn try_asset_decoder(&mut self) -> SbeResult<(usize, usize)> {
+            let offset = self
+                .parent
+                .as_ref()
+                .ok_or(SbeErr::ParentNotSet)?
+                .get_limit();
+            let data_length = self.get_buf().try_get_u8_at(offset)? as usize;
+            self.get_buf().check_bounds(offset + 1, data_length)?;
+            self.parent
+                .as_mut()
+                .ok_or(SbeErr::ParentNotSet)?
+                .set_limit(offset + 1 + data_length);
+            Ok((offset + 1, data_length))
+        }
+
         #[inline]
         pub fn asset_slice(&'a self, coordinates: (usize, usize)) -> &'a [u8] {
             debug_assert!(self.get_limit() >= coordinates.0 + coordinates.1);
@@ -995,6 +1082,19 @@ pub mod decoder {
             self
         }
 
+        /// Checked variant of `wrap` that fails with `SbeErr::BufferTooShort`
+        /// instead of panicking when the group dimensions do not fit in the buffer.
+        pub fn try_wrap(self, parent: P) -> SbeResult<Self> {
+            parent.get_buf().check_bounds(parent.get_limit(), 6)?;
+            Ok(self.wrap(parent))
+        }
+
+        /// Number of bytes the fixed block must hold for the fields present in `acting_version`.
+        #[inline]
+        fn min_block_length(_acting_version: u16) -> usize {
+            0
+        }
+
         /// group token - Token{signal=BEGIN_GROUP, name='permissions', referencedName='null', description='null', packageName='null', id=101, version=0, deprecated=0, encodedLength=0, offset=-1, componentTokenCount=12, encoding=Encoding{presence=REQUIRED, primitiveType=null, byteOrder=LITTLE_ENDIAN, minValue=null, maxValue=null, nullValue=null, constValue=null, characterEncoding='null', epoch='null', timeUnit=null, semanticType='null'}}
         #[inline]
         pub fn parent(&mut self) -> SbeResult<P> {
@@ -1027,6 +1127,19 @@ pub mod decoder {
             }
         }
 
+        /// Checked variant of `advance` that fails with `SbeErr::BufferTooShort`
+        /// instead of letting field accessors read past the end of the buffer.
+        pub fn try_advance(&mut self) -> SbeResult<Option<usize>> {
+            if self.index.wrapping_add(1) >= self.count as usize {
+                return Ok(None);
+            }
+            let parent = self.parent.as_ref().ok_or(SbeErr::ParentNotSet)?;
+            let needed =
+                Self::min_block_length(parent.acting_version()).max(self.block_length as usize);
+            parent.get_buf().check_bounds(parent.get_limit(), needed)?;
+            self.advance()
+        }
+
         /// VAR_DATA DECODER - character encoding: 'UTF-8'
         #[inline]
         pub fn permission_decoder(&mut self) -> (usize, usize) {
@@ -1039,6 +1152,23 @@ pub mod decoder {
             (offset + 1, data_length)
         }
 
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'
+        #[inline]
+        pub fn try_permission_decoder(&mut self) -> SbeResult<(usize, usize)> {
+            let offset = self
+                .parent
+                .as_ref()
+                .ok_or(SbeErr::ParentNotSet)?
+                .get_limit();
+            let data_length = self.get_buf().try_get_u8_at(offset)? as usize;
+            self.get_buf().check_bounds(offset + 1, data_length)?;
+            self.parent
+                .as_mut()
+                .ok_or(SbeErr::ParentNotSet)?
+                .set_limit(offset + 1 + data_length);
+            Ok((offset + 1, data_length))
+        }
+
         #[inline]
         pub fn permission_slice(&'a self, coordinates: (usize, usize)) -> &'a [u8] {
             debug_assert!(self.get_limit() >= coordinates.0 + coordinates.1);
@@ -1110,6 +1240,19 @@ pub mod decoder {
             self
         }
 
+        /// Checked variant of `wrap` that fails with `SbeErr::BufferTooShort`
+        /// instead of panicking when the group dimensions do not fit in the buffer.
+        pub fn try_wrap(self, parent: P) -> SbeResult<Self> {
+            parent.get_buf().check_bounds(parent.get_limit(), 6)?;
+            Ok(self.wrap(parent))
+        }
+
+        /// Number of bytes the fixed block must hold for the fields present in `acting_version`.
+        #[inline]
+        fn min_block_length(_acting_version: u16) -> usize {
+            0
+        }
+
         /// group token - Token{signal=BEGIN_GROUP, name='reduceOnlyAssets', referencedName='null', description='null', packageName='null', id=102, version=0, deprecated=0, encodedLength=0, offset=-1, componentTokenCount=12, encoding=Encoding{presence=REQUIRED, primitiveType=null, byteOrder=LITTLE_ENDIAN, minValue=null, maxValue=null, nullValue=null, constValue=null, characterEncoding='null', epoch='null', timeUnit=null, semanticType='null'}}
         #[inline]
         pub fn parent(&mut self) -> SbeResult<P> {
@@ -1142,6 +1285,19 @@ pub mod decoder {
             }
         }
 
+        /// Checked variant of `advance` that fails with `SbeErr::BufferTooShort`
+        /// instead of letting field accessors read past the end of the buffer.
+        pub fn try_advance(&mut self) -> SbeResult<Option<usize>> {
+            if self.index.wrapping_add(1) >= self.count as usize {
+                return Ok(None);
+            }
+            let parent = self.parent.as_ref().ok_or(SbeErr::ParentNotSet)?;
+            let needed =
+                Self::min_block_length(parent.acting_version()).max(self.block_length as usize);
+            parent.get_buf().check_bounds(parent.get_limit(), needed)?;
+            self.advance()
+        }
+
         /// VAR_DATA DECODER - character encoding: 'UTF-8'
         #[inline]
         pub fn asset_decoder(&mut self) -> (usize, usize) {
@@ -1154,6 +1310,23 @@ pub mod decoder {
             (offset + 1, data_length)
         }
 
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'
+        #[inline]
+        pub fn try_asset_decoder(&mut self) -> SbeResult<(usize, usize)> {
+            let offset = self
+                .parent
+                .as_ref()
+                .ok_or(SbeErr::ParentNotSet)?
+                .get_limit();
+            let data_length = self.get_buf().try_get_u8_at(offset)? as usize;
+            self.get_buf().check_bounds(offset + 1, data_length)?;
+            self.parent
+                .as_mut()
+                .ok_or(SbeErr::ParentNotSet)?
+                .set_limit(offset + 1 + data_length);
+            Ok((offset + 1, data_length))
+        }
+
         #[inline]
         pub fn asset_slice(&'a self, coordinates: (usize, usize)) -> &'a [u8] {
             debug_assert!(self.get_limit() >= coordinates.0 + coordinates.1);
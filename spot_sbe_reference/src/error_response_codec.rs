@@ -208,6 +208,32 @@ pub mod decoder {
             )
         }
 
+        /// Checked variant of `header` that fails with `SbeErr::BufferTooShort`
+        /// instead of panicking when the fixed block does not fit in the buffer.
+        pub fn try_header(
+            self,
+            mut header: MessageHeaderDecoder<ReadBuf<'a>>,
+            offset: usize,
+        ) -> SbeResult<Self> {
+            header
+                .get_buf()
+                .check_bounds(offset, message_header_codec::ENCODED_LENGTH)?;
+            let acting_block_length = header.block_length();
+            let acting_version = header.version();
+            let buf = header.parent()?;
+            let offset = offset + message_header_codec::ENCODED_LENGTH;
+            let needed = Self::min_block_length(acting_version).max(acting_block_length as usize);
+            buf.check_bounds(offset, needed)?;
+
+            Ok(self.wrap(buf, offset, acting_block_length, acting_version))
+        }
+
+        /// Number of bytes the fixed block must hold for the fields present in `acting_version`.
+        #[inline]
+        fn min_block_length(_acting_version: u16) -> usize {
+            18
+        }
+
         /// primitive field - 'REQUIRED'
         #[inline]
         pub fn code(&self) -> i16 {
@@ -245,6 +271,16 @@ pub mod decoder {
             (offset + 2, data_length)
         }
 
+        /// VAR_DATA DECODER - character encoding: 'UTF-8'
+        #[inline]
+        pub fn try_msg_decoder(&mut self) -> SbeResult<(usize, usize)> {
+            let offset = self.get_limit();
+            let data_length = self.get_buf().try_get_u16_at(offset)? as usize;
+            self.get_buf().check_bounds(offset + 2, data_length)?;
+            self.set_limit(offset + 2 + data_length);
+            Ok((offset + 2, data_length))
+        }
+
         #[inline]
         pub fn msg_slice(&'a self, coordinates: (usize, usize)) -> &'a [u8] {
             debug_assert!(self.get_limit() >= coordinates.0 + coordinates.1);
@@ -260,6 +296,16 @@ pub mod decoder {
             (offset + 4, data_length)
         }
 
+        /// VAR_DATA DECODER - character encoding: 'None'
+        #[inline]
+        pub fn try_data_decoder(&mut self) -> SbeResult<(usize, usize)> {
+            let offset = self.get_limit();
+            let data_length = self.get_buf().try_get_u32_at(offset)? as usize;
+            self.get_buf().check_bounds(offset + 4, data_length)?;
+            self.set_limit(offset + 4 + data_length);
+            Ok((offset + 4, data_length))
+        }
+
         #[inline]
         pub fn data_slice(&'a self, coordinates: (usize, usize)) -> &'a [u8] {
             debug_assert!(self.get_limit() >= coordinates.0 + coordinates.1);
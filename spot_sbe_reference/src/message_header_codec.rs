@@ -144,6 +144,13 @@ pub mod decoder {
             self
         }
 
+        /// Checked variant of `wrap` that fails with `SbeErr::BufferTooShort`
+        /// instead of panicking when the header does not fit in the buffer.
+        pub fn try_wrap(self, parent: P, offset: usize) -> SbeResult<Self> {
+            parent.get_buf().check_bounds(offset, ENCODED_LENGTH)?;
+            Ok(self.wrap(parent, offset))
+        }
+
         #[inline]
         pub fn parent(&mut self) -> SbeResult<P> {
             self.parent.take().ok_or(SbeErr::ParentNotSet)
@@ -0,0 +1,289 @@
+//! Message lengths computed from the schema's layout of repeating groups and
+//! variable-length data, without decoding any field.
+
+use crate::*;
+
+/// The width of a group's `numInGroup` or of a var-data length prefix.
+#[derive(Clone, Copy, Debug)]
+enum Width {
+    U8,
+    U16,
+    U32,
+}
+
+impl Width {
+    fn size(self) -> usize {
+        match self {
+            Self::U8 => 1,
+            Self::U16 => 2,
+            Self::U32 => 4,
+        }
+    }
+
+    fn read(self, buf: &ReadBuf<'_>, offset: usize) -> SbeResult<usize> {
+        Ok(match self {
+            Self::U8 => buf.try_get_u8_at(offset)? as usize,
+            Self::U16 => buf.try_get_u16_at(offset)? as usize,
+            Self::U32 => buf.try_get_u32_at(offset)? as usize,
+        })
+    }
+}
+
+/// What follows the fixed block of a message or group, in schema order.
+#[derive(Clone, Copy, Debug)]
+enum Part {
+    /// A repeating group: `GroupSize16Encoding` (`U16`) or `GroupSizeEncoding`
+    /// (`U32`) dimensions, then each entry's block and parts.
+    Group(Width, &'static [Part]),
+    /// `VarString8`/`MessageData8` (`U8`), `VarString`/`MessageData16` (`U16`)
+    /// or `MessageData` (`U32`).
+    VarData(Width),
+}
+
+fn layout(template_id: u16) -> Option<&'static [Part]> {
+    use Part::*;
+    use Width::*;
+    Some(match template_id {
+        price_filter_codec::SBE_TEMPLATE_ID => &[],
+        percent_price_filter_codec::SBE_TEMPLATE_ID => &[],
+        percent_price_by_side_filter_codec::SBE_TEMPLATE_ID => &[],
+        lot_size_filter_codec::SBE_TEMPLATE_ID => &[],
+        min_notional_filter_codec::SBE_TEMPLATE_ID => &[],
+        notional_filter_codec::SBE_TEMPLATE_ID => &[],
+        iceberg_parts_filter_codec::SBE_TEMPLATE_ID => &[],
+        market_lot_size_filter_codec::SBE_TEMPLATE_ID => &[],
+        max_num_orders_filter_codec::SBE_TEMPLATE_ID => &[],
+        max_num_algo_orders_filter_codec::SBE_TEMPLATE_ID => &[],
+        max_num_iceberg_orders_filter_codec::SBE_TEMPLATE_ID => &[],
+        max_position_filter_codec::SBE_TEMPLATE_ID => &[],
+        trailing_delta_filter_codec::SBE_TEMPLATE_ID => &[],
+        tp_lus_sell_filter_codec::SBE_TEMPLATE_ID => &[],
+        exchange_max_num_orders_filter_codec::SBE_TEMPLATE_ID => &[],
+        exchange_max_num_algo_orders_filter_codec::SBE_TEMPLATE_ID => &[],
+        exchange_max_num_iceberg_orders_filter_codec::SBE_TEMPLATE_ID => &[],
+        max_num_order_lists_filter_codec::SBE_TEMPLATE_ID => &[],
+        exchange_max_num_order_lists_filter_codec::SBE_TEMPLATE_ID => &[],
+        max_num_order_amends_filter_codec::SBE_TEMPLATE_ID => &[],
+        max_asset_filter_codec::SBE_TEMPLATE_ID => &[VarData(U8)],
+        price_range_execution_rule_codec::SBE_TEMPLATE_ID => &[],
+        web_socket_response_codec::SBE_TEMPLATE_ID => &[Group(U16, &[]), VarData(U8), VarData(U32)],
+        web_socket_session_logon_response_codec::SBE_TEMPLATE_ID => &[VarData(U16)],
+        web_socket_session_status_response_codec::SBE_TEMPLATE_ID => &[VarData(U16)],
+        web_socket_session_logout_response_codec::SBE_TEMPLATE_ID => &[VarData(U16)],
+        web_socket_session_subscriptions_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[])],
+        error_response_codec::SBE_TEMPLATE_ID => &[VarData(U16), VarData(U32)],
+        ping_response_codec::SBE_TEMPLATE_ID => &[],
+        server_time_response_codec::SBE_TEMPLATE_ID => &[],
+        exchange_info_response_codec::SBE_TEMPLATE_ID => &[
+            Group(U32, &[]),
+            Group(U32, &[VarData(U8)]),
+            Group(
+                U32,
+                &[
+                    Group(U32, &[VarData(U8)]),
+                    Group(U32, &[Group(U32, &[VarData(U8)])]),
+                    VarData(U8),
+                    VarData(U8),
+                    VarData(U8),
+                ],
+            ),
+            Group(U32, &[Group(U32, &[VarData(U8)]), VarData(U8)]),
+        ],
+        execution_rules_response_codec::SBE_TEMPLATE_ID => {
+            &[Group(U32, &[Group(U32, &[VarData(U8)]), VarData(U8)])]
+        }
+        my_filters_response_codec::SBE_TEMPLATE_ID => &[
+            Group(U32, &[VarData(U8)]),
+            Group(U32, &[VarData(U8)]),
+            Group(U32, &[VarData(U8)]),
+        ],
+        depth_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[]), Group(U32, &[])],
+        trades_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[])],
+        agg_trades_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[])],
+        klines_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[])],
+        average_price_response_codec::SBE_TEMPLATE_ID => &[],
+        ticker_24_hs_ymbol_full_response_codec::SBE_TEMPLATE_ID => &[VarData(U8)],
+        ticker_24_hf_ull_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[VarData(U8)])],
+        ticker_24_hs_ymbol_mini_response_codec::SBE_TEMPLATE_ID => &[VarData(U8)],
+        ticker_24_hm_ini_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[VarData(U8)])],
+        price_ticker_symbol_response_codec::SBE_TEMPLATE_ID => &[VarData(U8)],
+        price_ticker_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[VarData(U8)])],
+        book_ticker_symbol_response_codec::SBE_TEMPLATE_ID => &[VarData(U8)],
+        book_ticker_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[VarData(U8)])],
+        ticker_symbol_full_response_codec::SBE_TEMPLATE_ID => &[VarData(U8)],
+        ticker_full_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[VarData(U8)])],
+        ticker_symbol_mini_response_codec::SBE_TEMPLATE_ID => &[VarData(U8)],
+        ticker_mini_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[VarData(U8)])],
+        reference_price_response_codec::SBE_TEMPLATE_ID => &[VarData(U8)],
+        reference_price_calculation_response_codec::SBE_TEMPLATE_ID => &[VarData(U8)],
+        block_trades_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[])],
+        new_order_ack_response_codec::SBE_TEMPLATE_ID => &[VarData(U8), VarData(U8)],
+        new_order_result_response_codec::SBE_TEMPLATE_ID => &[VarData(U8), VarData(U8)],
+        new_order_full_response_codec::SBE_TEMPLATE_ID => &[
+            Group(U32, &[VarData(U8)]),
+            Group(U32, &[VarData(U8)]),
+            VarData(U8),
+            VarData(U8),
+        ],
+        order_test_response_codec::SBE_TEMPLATE_ID => &[],
+        order_response_codec::SBE_TEMPLATE_ID => &[VarData(U8), VarData(U8)],
+        cancel_order_response_codec::SBE_TEMPLATE_ID => &[VarData(U8), VarData(U8), VarData(U8)],
+        cancel_open_orders_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[VarData(U16)])],
+        cancel_replace_order_response_codec::SBE_TEMPLATE_ID => &[VarData(U16), VarData(U32)],
+        orders_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[VarData(U8), VarData(U8)])],
+        new_order_list_ack_response_codec::SBE_TEMPLATE_ID => &[
+            Group(U16, &[VarData(U8), VarData(U8)]),
+            Group(U16, &[VarData(U8), VarData(U8)]),
+            VarData(U8),
+            VarData(U8),
+        ],
+        new_order_list_result_response_codec::SBE_TEMPLATE_ID => &[
+            Group(U16, &[VarData(U8), VarData(U8)]),
+            Group(U16, &[VarData(U8), VarData(U8)]),
+            VarData(U8),
+            VarData(U8),
+        ],
+        new_order_list_full_response_codec::SBE_TEMPLATE_ID => &[
+            Group(U16, &[VarData(U8), VarData(U8)]),
+            Group(
+                U16,
+                &[
+                    Group(U32, &[VarData(U8)]),
+                    Group(U32, &[VarData(U8)]),
+                    VarData(U8),
+                    VarData(U8),
+                ],
+            ),
+            VarData(U8),
+            VarData(U8),
+        ],
+        cancel_order_list_response_codec::SBE_TEMPLATE_ID => &[
+            Group(U16, &[VarData(U8), VarData(U8)]),
+            Group(U16, &[VarData(U8), VarData(U8), VarData(U8)]),
+            VarData(U8),
+            VarData(U8),
+        ],
+        order_list_response_codec::SBE_TEMPLATE_ID => &[
+            Group(U16, &[VarData(U8), VarData(U8)]),
+            VarData(U8),
+            VarData(U8),
+        ],
+        order_lists_response_codec::SBE_TEMPLATE_ID => &[Group(
+            U32,
+            &[
+                Group(U32, &[VarData(U8), VarData(U8)]),
+                VarData(U8),
+                VarData(U8),
+            ],
+        )],
+        order_test_with_commissions_response_codec::SBE_TEMPLATE_ID => &[VarData(U8)],
+        order_amendments_response_codec::SBE_TEMPLATE_ID => {
+            &[Group(U32, &[VarData(U8), VarData(U8), VarData(U8)])]
+        }
+        order_amend_keep_priority_response_codec::SBE_TEMPLATE_ID => &[
+            Group(
+                U16,
+                &[
+                    Group(U16, &[VarData(U8), VarData(U8)]),
+                    VarData(U8),
+                    VarData(U8),
+                ],
+            ),
+            Group(U16, &[VarData(U8), VarData(U8)]),
+            VarData(U8),
+            VarData(U8),
+            VarData(U8),
+        ],
+        account_response_codec::SBE_TEMPLATE_ID => &[
+            Group(U32, &[VarData(U8)]),
+            Group(U32, &[VarData(U8)]),
+            Group(U32, &[VarData(U8)]),
+        ],
+        account_trades_response_codec::SBE_TEMPLATE_ID => {
+            &[Group(U32, &[VarData(U8), VarData(U8)])]
+        }
+        account_order_rate_limit_response_codec::SBE_TEMPLATE_ID => &[Group(U32, &[])],
+        account_prevented_matches_response_codec::SBE_TEMPLATE_ID => {
+            &[Group(U32, &[VarData(U8), VarData(U8)])]
+        }
+        account_allocations_response_codec::SBE_TEMPLATE_ID => {
+            &[Group(U32, &[VarData(U8), VarData(U8), VarData(U8)])]
+        }
+        account_commission_response_codec::SBE_TEMPLATE_ID => &[VarData(U8), VarData(U8)],
+        user_data_stream_start_response_codec::SBE_TEMPLATE_ID => &[VarData(U8)],
+        user_data_stream_ping_response_codec::SBE_TEMPLATE_ID => &[],
+        user_data_stream_stop_response_codec::SBE_TEMPLATE_ID => &[],
+        user_data_stream_subscribe_response_codec::SBE_TEMPLATE_ID => &[],
+        user_data_stream_unsubscribe_response_codec::SBE_TEMPLATE_ID => &[],
+        user_data_stream_subscribe_listen_token_response_codec::SBE_TEMPLATE_ID => &[],
+        allocation_report_event_codec::SBE_TEMPLATE_ID => &[VarData(U8), VarData(U8), VarData(U8)],
+        balance_update_event_codec::SBE_TEMPLATE_ID => &[VarData(U8)],
+        event_stream_terminated_event_codec::SBE_TEMPLATE_ID => &[],
+        execution_report_event_codec::SBE_TEMPLATE_ID => &[
+            VarData(U8),
+            VarData(U8),
+            VarData(U8),
+            VarData(U8),
+            VarData(U8),
+            VarData(U8),
+        ],
+        external_lock_update_event_codec::SBE_TEMPLATE_ID => &[VarData(U8)],
+        list_status_event_codec::SBE_TEMPLATE_ID => &[
+            Group(U16, &[VarData(U8), VarData(U8)]),
+            VarData(U8),
+            VarData(U8),
+            VarData(U8),
+        ],
+        outbound_account_position_event_codec::SBE_TEMPLATE_ID => &[Group(U32, &[VarData(U8)])],
+        server_shutdown_event_codec::SBE_TEMPLATE_ID => &[],
+        non_representable_message_codec::SBE_TEMPLATE_ID => &[],
+        _ => return None,
+    })
+}
+
+fn skip_parts(buf: &ReadBuf<'_>, mut offset: usize, parts: &[Part]) -> SbeResult<usize> {
+    for part in parts {
+        offset = match *part {
+            Part::Group(count_width, entry_parts) => {
+                let block_length = buf.try_get_u16_at(offset)? as usize;
+                let count = count_width.read(buf, offset + 2)?;
+                offset += 2 + count_width.size();
+                if entry_parts.is_empty() {
+                    let length = block_length.checked_mul(count).unwrap_or(usize::MAX);
+                    buf.check_bounds(offset, length)?;
+                    offset + length
+                } else {
+                    for _ in 0..count {
+                        buf.check_bounds(offset, block_length)?;
+                        offset = skip_parts(buf, offset + block_length, entry_parts)?;
+                    }
+                    offset
+                }
+            }
+            Part::VarData(length_width) => {
+                let length = length_width.read(buf, offset)?;
+                offset += length_width.size();
+                buf.check_bounds(offset, length)?;
+                offset + length
+            }
+        };
+    }
+    Ok(offset)
+}
+
+/// The number of bytes taken by the message at the start of `buffer`, header
+/// included.
+///
+/// Only the message header, group dimensions and var-data lengths are read.
+/// Fails with `SbeErr::BufferTooShort` if the message does not fit in
+/// `buffer` and with `SbeErr::UnknownTemplateId` for templates outside the
+/// schema.
+pub fn message_length(buffer: &[u8]) -> SbeResult<usize> {
+    let buf = ReadBuf::new(buffer);
+    let header = message_header_codec::MessageHeaderDecoder::default().try_wrap(buf, 0)?;
+    let template_id = header.template_id();
+    let parts = layout(template_id).ok_or(SbeErr::UnknownTemplateId { template_id })?;
+    let offset = message_header_codec::ENCODED_LENGTH + header.block_length() as usize;
+    buf.check_bounds(0, offset)?;
+    skip_parts(&buf, offset, parts)
+}
@@ -0,0 +1,289 @@
+//! Generates, for `tests/round_trip.rs`, an encoder of every message of every
+//! schema `spot_sbe` embeds, drawing its values from a `Source` and recording
+//! them as they should decode, and a decoder reading every accessor back.
+
+use sbe_codegen::{
+    naming::{codec_module, snake_case, type_name},
+    schema::{Body, CompositeType, Data, Presence, Primitive, Schema, Type},
+    walk::walk_body,
+};
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+const LATEST_SCHEMA: &str = "../spot_sbe/spot_latest.xml";
+const OTHER_SCHEMAS: &str = "../spot_sbe/schemas";
+
+fn main() {
+    let schemas =
+        sbe_codegen::schemas(LATEST_SCHEMA, OTHER_SCHEMAS).expect("Failed to list the schemas");
+
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    fs::write(
+        Path::new(&out_dir).join("round_trip.rs"),
+        round_trip(&schemas),
+    )
+    .expect("Failed to write the round-trip tests");
+}
+
+/// The round-trip tests of the messages of `schemas`.
+fn round_trip(schemas: &[PathBuf]) -> String {
+    let mut out = String::new();
+    for path in schemas {
+        let schema = Schema::parse(&fs::read_to_string(path).expect("Failed to read the schema"))
+            .expect("Invalid schema");
+        let header = composite_size(&schema.header);
+        let mut messages = Vec::new();
+        writeln!(
+            out,
+            "#[allow(clippy::all, unused)]\npub mod schema_{id} {{\n    use super::*;\n    use spot_sbe::schema_{id} as sbe;",
+            id = schema.id
+        )
+        .unwrap();
+        for message in schema.messages() {
+            let name = snake_case(&message.name);
+            let module = codec_module(&message.name);
+            let ty = type_name(&message.name);
+            let mut encode = String::new();
+            encode_body(&mut encode, &message.body, "encoder", 0);
+            let mut decode = String::new();
+            walk_body(
+                &mut decode,
+                &message.body,
+                "decoder",
+                "            ",
+                &|value| format!("out.push(format!(\"{{:?}}\", {value}));"),
+            );
+            writeln!(
+                out,
+                r#"
+    pub mod {name} {{
+        use super::*;
+
+        /// The length of the message without its groups' entries and its
+        /// var-data, which the source's budget bounds.
+        pub const LENGTH: usize = {length};
+
+        pub fn encode(source: &mut Source, buf: &mut [u8], expected: &mut Vec<String>) -> usize {{
+            let encoder = sbe::{module}::{ty}Encoder::default()
+                .wrap(WriteBuf::new(buf), sbe::message_header_codec::ENCODED_LENGTH);
+            let mut header = encoder.header(0);
+            let mut encoder = header.parent().unwrap();
+{encode}            encoder.get_limit()
+        }}
+
+        pub fn decode(buf: &[u8]) -> SbeResult<Vec<String>> {{
+            let mut out = Vec::new();
+            let header = sbe::message_header_codec::MessageHeaderDecoder::default()
+                .try_wrap(ReadBuf::new(buf), 0)?;
+            let mut decoder = sbe::{module}::{ty}Decoder::default().try_header(header, 0)?;
+{decode}            out.push(decoder.get_limit().to_string());
+            Ok(out)
+        }}
+    }}"#,
+                length = header + message.block_length + fixed_length(&message.body)
+            )
+            .unwrap();
+            messages.push(name);
+        }
+        writeln!(out, "\n    round_trip! {{").unwrap();
+        for name in messages {
+            writeln!(out, "        {name},").unwrap();
+        }
+        writeln!(out, "    }}\n}}").unwrap();
+    }
+    out
+}
+
+fn composite_size(composite: &CompositeType) -> usize {
+    composite
+        .members
+        .iter()
+        .map(|member| member.offset + member.ty.size())
+        .max()
+        .unwrap_or(0)
+}
+
+/// The length of a body's group dimensions and var-data lengths, which are
+/// present whatever the group counts and var-data values.
+fn fixed_length(body: &Body) -> usize {
+    let dimensions: usize = body
+        .groups
+        .iter()
+        .map(|group| composite_size(&group.dimension))
+        .sum();
+    let lengths: usize = body
+        .data
+        .iter()
+        .map(|data| length_prefix(data).size())
+        .sum();
+    dimensions + lengths
+}
+
+fn length_prefix(data: &Data) -> Primitive {
+    data.ty
+        .primitive_member("length")
+        .expect("Invalid var-data")
+        .1
+}
+
+fn max_value(primitive: Primitive) -> u64 {
+    match primitive {
+        Primitive::UInt8 => u8::MAX.into(),
+        Primitive::UInt16 => u16::MAX.into(),
+        Primitive::UInt32 => u32::MAX.into(),
+        primitive => panic!("Unsupported length encoding {primitive:?}"),
+    }
+}
+
+fn rust_type(primitive: Primitive) -> &'static str {
+    match primitive {
+        Primitive::Char | Primitive::UInt8 => "u8",
+        Primitive::Int8 => "i8",
+        Primitive::Int16 => "i16",
+        Primitive::Int32 => "i32",
+        Primitive::Int64 => "i64",
+        Primitive::UInt16 => "u16",
+        Primitive::UInt32 => "u32",
+        Primitive::UInt64 => "u64",
+        Primitive::Float => "f32",
+        Primitive::Double => "f64",
+    }
+}
+
+/// Draws and encodes every field, then every group with a drawn count, then
+/// every var-data, pushing what the decoder should read back for each.
+fn encode_body(out: &mut String, body: &Body, encoder: &str, depth: usize) {
+    let indent = "    ".repeat(depth + 3);
+    for field in &body.fields {
+        let method = snake_case(&field.name);
+        let (value, argument) = match (&field.ty, &field.presence) {
+            (_, Presence::Constant(_)) => continue,
+            (Type::Encoded(encoded), _) if encoded.length > 1 => (
+                format!(
+                    "let value: [{}; {}] = std::array::from_fn(|_| source.draw());",
+                    rust_type(encoded.primitive),
+                    encoded.length
+                ),
+                "&value",
+            ),
+            (Type::Encoded(encoded), Presence::Optional) => {
+                let ty = rust_type(encoded.primitive);
+                let (null, is_null) = match (&encoded.null_value, encoded.primitive) {
+                    (_, Primitive::Float | Primitive::Double) => {
+                        (format!("{ty}::NAN"), "value.is_nan()".to_owned())
+                    }
+                    (Some(null), _) => (
+                        format!("{null} as {ty}"),
+                        format!("value == {null} as {ty}"),
+                    ),
+                    (None, primitive) if primitive.is_signed() => {
+                        (format!("{ty}::MIN"), format!("value == {ty}::MIN"))
+                    }
+                    (None, _) => (format!("{ty}::MAX"), format!("value == {ty}::MAX")),
+                };
+                writeln!(
+                    out,
+                    "{indent}let value: {ty} = if source.null() {{ {null} }} else {{ source.draw() }};"
+                )
+                .unwrap();
+                writeln!(
+                    out,
+                    "{indent}expected.push(format!(\"{{:?}}\", if {is_null} {{ None }} else {{ Some(value) }}));"
+                )
+                .unwrap();
+                writeln!(out, "{indent}{encoder}.{method}(value);").unwrap();
+                continue;
+            }
+            (Type::Encoded(encoded), _) => (
+                format!(
+                    "let value: {} = source.draw();",
+                    rust_type(encoded.primitive)
+                ),
+                "value",
+            ),
+            (Type::Enum(enumeration), _) => {
+                let path = format!(
+                    "sbe::{}::{}",
+                    snake_case(&enumeration.name),
+                    type_name(&enumeration.name)
+                );
+                let mut variants: Vec<String> = enumeration
+                    .values
+                    .iter()
+                    .map(|(name, _)| format!("{path}::{}", type_name(name)))
+                    .collect();
+                variants.push(format!("{path}::NullVal"));
+                (
+                    format!(
+                        "let value = [{}][source.pick({})];",
+                        variants.join(", "),
+                        variants.len()
+                    ),
+                    "value",
+                )
+            }
+            (Type::Set(set), _) => (
+                format!(
+                    "let value = sbe::{}::{}::new(source.draw());",
+                    snake_case(&set.name),
+                    type_name(&set.name)
+                ),
+                "value",
+            ),
+            (Type::Composite(composite), _) => {
+                panic!("Unsupported composite field {}", composite.name)
+            }
+        };
+        writeln!(out, "{indent}{value}").unwrap();
+        writeln!(out, "{indent}expected.push(format!(\"{{:?}}\", value));").unwrap();
+        writeln!(out, "{indent}{encoder}.{method}({argument});").unwrap();
+    }
+    for group in &body.groups {
+        let method = snake_case(&group.name);
+        let (_, count_type) = group
+            .dimension
+            .primitive_member("numInGroup")
+            .expect("Invalid group dimension");
+        let entry_length = group.block_length + fixed_length(&group.body);
+        let mut entries = String::new();
+        encode_body(&mut entries, &group.body, &method, depth + 1);
+        writeln!(
+            out,
+            "{indent}let count = source.count({max}, {entry_length});
+{indent}expected.push(count.to_string());
+{indent}let mut {method} = {encoder}.{method}_encoder(count as {ty}, Default::default());
+{indent}for _ in 0..count {{
+{indent}    {method}.advance().unwrap();
+{entries}{indent}}}
+{indent}{encoder} = {method}.parent().unwrap();",
+            max = max_value(count_type),
+            ty = rust_type(count_type)
+        )
+        .unwrap();
+    }
+    for data in &body.data {
+        let method = snake_case(&data.name);
+        let max = max_value(length_prefix(data));
+        if data.character_encoding().is_some() {
+            writeln!(
+                out,
+                "{indent}let value = source.text({max});
+{indent}expected.push(format!(\"{{:?}}\", value.as_bytes()));
+{indent}{encoder}.{method}(&value);"
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                out,
+                "{indent}let value = source.bytes({max});
+{indent}expected.push(format!(\"{{:?}}\", value));
+{indent}{encoder}.{method}(&value);"
+            )
+            .unwrap();
+        }
+    }
+}
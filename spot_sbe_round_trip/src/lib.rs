@@ -0,0 +1,3 @@
+//! Holds no code: `tests/round_trip.rs` runs the round-trip tests this crate's
+//! build script generates from the schemas of `spot_sbe`, so that building
+//! `spot_sbe` does not generate them.
@@ -0,0 +1,161 @@
+//! Encodes every message of every embedded schema with values drawn by
+//! proptest, favouring boundaries such as null values, empty and full-length
+//! var-data and empty and large groups, and requires the decoders to read back
+//! the same values and the same length.
+
+use proptest::{collection::vec, prelude::*};
+use spot_sbe::{Decoder, Encoder, ReadBuf, SbeResult, WriteBuf};
+
+/// The bytes the entries of groups and the var-data of a message may take in
+/// total, which also bounds the group counts.
+const BUDGET: usize = 1 << 17;
+
+/// Cases per message, fewer than proptest's default as full-length var-data
+/// makes each case slow in debug builds.
+const CASES: u32 = 64;
+
+trait Draw {
+    fn draw(word: u64) -> Self;
+}
+
+macro_rules! draw_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl Draw for $ty {
+                fn draw(word: u64) -> Self {
+                    match word % 4 {
+                        0 => <$ty>::MIN,
+                        1 => <$ty>::MAX,
+                        2 => 0,
+                        _ => (word >> 2) as $ty,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+draw_integer!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl Draw for f32 {
+    fn draw(word: u64) -> Self {
+        f32::from_bits(word as u32)
+    }
+}
+
+impl Draw for f64 {
+    fn draw(word: u64) -> Self {
+        f64::from_bits(word)
+    }
+}
+
+/// Draws the values of a message from the words generated by proptest. Once
+/// they run out, every draw is zero, which selects the smallest message.
+pub struct Source {
+    words: std::vec::IntoIter<u64>,
+    budget: usize,
+}
+
+impl Source {
+    fn word(&mut self) -> u64 {
+        self.words.next().unwrap_or(0)
+    }
+
+    fn pick(&mut self, choices: usize) -> usize {
+        (self.word() % choices as u64) as usize
+    }
+
+    fn draw<T: Draw>(&mut self) -> T {
+        T::draw(self.word())
+    }
+
+    fn null(&mut self) -> bool {
+        self.pick(4) == 0
+    }
+
+    /// A group count of zero, the most the budget allows, or a few entries.
+    fn count(&mut self, max: u64, entry_length: usize) -> usize {
+        let entry_length = entry_length.max(1);
+        let max = (self.budget / entry_length).min(max as usize);
+        let count = match self.pick(4) {
+            0 => 0,
+            1 => max,
+            _ => self.pick(max.min(3) + 1),
+        };
+        self.budget -= count * entry_length;
+        count
+    }
+
+    /// A var-data length of zero, the most the encoding or the budget allows,
+    /// or anything in between.
+    fn length(&mut self, max: u64) -> usize {
+        let max = self.budget.min(max as usize);
+        let length = match self.pick(4) {
+            0 => 0,
+            1 => max,
+            _ => self.pick(max + 1),
+        };
+        self.budget -= length;
+        length
+    }
+
+    fn text(&mut self, max: u64) -> String {
+        let length = self.length(max);
+        (0..length)
+            .map(|_| char::from(b'a' + self.pick(26) as u8))
+            .collect()
+    }
+
+    fn bytes(&mut self, max: u64) -> Vec<u8> {
+        let length = self.length(max);
+        (0..length).map(|_| self.draw()).collect()
+    }
+}
+
+type Encode = fn(&mut Source, &mut [u8], &mut Vec<String>) -> usize;
+
+fn check(
+    words: Vec<u64>,
+    length: usize,
+    encode: Encode,
+    decode: fn(&[u8]) -> SbeResult<Vec<String>>,
+    message_length: fn(&[u8]) -> SbeResult<usize>,
+) -> Result<(), TestCaseError> {
+    let mut source = Source {
+        words: words.into_iter(),
+        budget: BUDGET,
+    };
+    let mut buf = vec![0; length + BUDGET];
+    let mut expected = Vec::new();
+    let limit = encode(&mut source, &mut buf, &mut expected);
+    buf.truncate(limit);
+
+    let mut decoded = decode(&buf).map_err(|error| TestCaseError::fail(format!("{error:?}")))?;
+    prop_assert_eq!(decoded.pop(), Some(limit.to_string()));
+    prop_assert_eq!(decoded, expected);
+    prop_assert_eq!(message_length(&buf).ok(), Some(limit));
+    Ok(())
+}
+
+macro_rules! round_trip {
+    ($($message:ident),* $(,)?) => {
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+            $(
+                #[test]
+                fn $message(words in vec(any::<u64>(), 0..256)) {
+                    check(
+                        words,
+                        $message::LENGTH,
+                        $message::encode,
+                        $message::decode,
+                        sbe::message_length::message_length,
+                    )?;
+                }
+            )*
+        }
+    };
+}
+
+include!(concat!(env!("OUT_DIR"), "/round_trip.rs"));
@@ -0,0 +1,7 @@
+fn main() {
+    sbe_codegen::build(
+        &["../spot_sbe/spot_latest.xml", "schemas/spot_2.xml"],
+        "spot_sbe_upgrade.rs",
+    )
+    .expect("Failed to generate the codecs");
+}
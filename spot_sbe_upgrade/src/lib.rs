@@ -0,0 +1,11 @@
+#![allow(ambiguous_glob_reexports)]
+#![forbid(unsafe_code)]
+#![allow(clippy::all)]
+#![allow(non_camel_case_types)]
+
+//! Codecs generated at build time from `../spot_sbe/spot_latest.xml` and from
+//! `schemas/spot_2.xml`, a stand-in for schema 2, so that tests can upgrade
+//! messages of another schema ID end to end. `spot_sbe` does not embed the
+//! stand-in: see `schemas/README.md`.
+
+include!(concat!(env!("OUT_DIR"), "/spot_sbe_upgrade.rs"));